@@ -0,0 +1,25 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "ffi")]
+    generate_header();
+}
+
+/// Generates the C header of the functions in `src/ffi.rs` into `include/`
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("cargo sets the manifest dir");
+    let config = cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml"))
+        .expect("cbindgen.toml is valid");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{crate_dir}/src/ffi.rs"))
+        .generate()
+        .expect("C header can be generated")
+        .write_to_file(format!(
+            "{crate_dir}/include/ethereum_private_key_to_address.h"
+        ));
+}
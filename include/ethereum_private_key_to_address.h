@@ -0,0 +1,78 @@
+#ifndef ETHEREUM_PRIVATE_KEY_TO_ADDRESS_H
+#define ETHEREUM_PRIVATE_KEY_TO_ADDRESS_H
+
+/* Generated by cbindgen from src/ffi.rs, don't edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The call succeeded
+ */
+#define ETH_OK 0
+
+/**
+ * A pointer argument is null
+ */
+#define ETH_ERROR_NULL_POINTER -1
+
+/**
+ * The private key is zero or not below the curve order
+ */
+#define ETH_ERROR_INVALID_PRIVATE_KEY -2
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Derives the 20 byte address of the 32 byte private key into `address_out`
+ *
+ * # Safety
+ *
+ * `private_key` must point to 32 readable bytes and `address_out` to 20 writable bytes.
+ */
+int32_t eth_private_key_to_address(const uint8_t *private_key, uint8_t *address_out);
+
+/**
+ * Writes the EIP-55 checksummed address of the 32 byte private key into `address_out` as a NUL
+ * terminated string, `0x` followed by 40 hex characters
+ *
+ * # Safety
+ *
+ * `private_key` must point to 32 readable bytes and `address_out` to 43 writable bytes.
+ */
+int32_t eth_private_key_to_checksum_address(const uint8_t *private_key, char *address_out);
+
+/**
+ * Derives the 64 byte uncompressed public key of the 32 byte private key into
+ * `public_key_out`, without the 0x04 prefix
+ *
+ * # Safety
+ *
+ * `private_key` must point to 32 readable bytes and `public_key_out` to 64 writable bytes.
+ */
+int32_t eth_private_key_to_public_key(const uint8_t *private_key, uint8_t *public_key_out);
+
+/**
+ * Signs the message the same way as `personal_sign` and writes the 65 byte signature
+ * `r || s || v` into `signature_out`, where `v` is 27 or 28
+ *
+ * # Safety
+ *
+ * `private_key` must point to 32 readable bytes, `message` to `message_len` readable bytes
+ * (it can be null if `message_len` is zero) and `signature_out` to 65 writable bytes.
+ */
+int32_t eth_sign_message(const uint8_t *private_key,
+                         const uint8_t *message,
+                         size_t message_len,
+                         uint8_t *signature_out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ETHEREUM_PRIVATE_KEY_TO_ADDRESS_H */
@@ -0,0 +1,27 @@
+import assert from 'node:assert/strict'
+import { test } from 'node:test'
+import {
+  deriveAddresses,
+  privateKeyToAddress,
+  publicKey,
+  publicKeyToAddress,
+  recoverMessageSigner,
+  signMessage,
+  toChecksumAddress,
+} from '../index.js'
+
+const PRIVATE_KEY = 'ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80'
+const ADDRESS = '0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266'
+
+test('derives the address', () => {
+  assert.equal(privateKeyToAddress(PRIVATE_KEY), ADDRESS)
+  assert.equal(publicKeyToAddress(publicKey(PRIVATE_KEY)), ADDRESS)
+  assert.equal(toChecksumAddress(ADDRESS.toLowerCase()), ADDRESS)
+  assert.deepEqual(deriveAddresses([PRIVATE_KEY, `0x${PRIVATE_KEY}`]), [ADDRESS, ADDRESS])
+  assert.throws(() => privateKeyToAddress('00'.repeat(32)))
+})
+
+test('signs the message', () => {
+  const message = Buffer.from('hello world')
+  assert.equal(recoverMessageSigner(message, signMessage(PRIVATE_KEY, message)), ADDRESS)
+})
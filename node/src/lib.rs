@@ -0,0 +1,92 @@
+use ethereum_private_key_to_address::{
+    address_from_pubkey_hex, par_derive_addresses, recover_address, Address, Error, PrivateKey,
+    Signature,
+};
+use napi::bindgen_prelude::Buffer;
+use napi_derive::napi;
+use std::str::FromStr;
+
+/// Converts the error into a JavaScript `Error` with the same message
+fn to_napi_error(error: Error) -> napi::Error {
+    napi::Error::from_reason(error.to_string())
+}
+
+/// Parses the hex private key, the `0x` prefix is optional
+fn parse_private_key(private_key: &str) -> napi::Result<PrivateKey> {
+    PrivateKey::from_str(private_key).map_err(to_napi_error)
+}
+
+/// Calculates the checksummed address of the hex private key, the `0x` prefix is optional
+#[napi]
+pub fn private_key_to_address(private_key: String) -> napi::Result<String> {
+    Ok(parse_private_key(&private_key)?.to_address().to_checksum())
+}
+
+/// Calculates the checksummed addresses of the hex private keys, the keys are split between the
+/// threads of the rayon thread pool
+#[napi]
+pub fn derive_addresses(private_keys: Vec<String>) -> napi::Result<Vec<String>> {
+    let private_keys = private_keys
+        .iter()
+        .map(|private_key| parse_private_key(private_key))
+        .collect::<napi::Result<Vec<_>>>()?;
+    Ok(par_derive_addresses(&private_keys)
+        .iter()
+        .map(Address::to_checksum)
+        .collect())
+}
+
+/// Returns the 64 byte public key of the hex private key as hex, without the 0x04 prefix
+#[napi]
+pub fn public_key(private_key: String) -> napi::Result<String> {
+    Ok(parse_private_key(&private_key)?.public_key())
+}
+
+/// Calculates the checksummed address of the hex public key. Accepts the uncompressed key with or
+/// without the 0x04 prefix and the 33 byte compressed key.
+#[napi]
+pub fn public_key_to_address(public_key: String) -> napi::Result<String> {
+    Ok(address_from_pubkey_hex(&public_key)
+        .map_err(to_napi_error)?
+        .to_checksum())
+}
+
+/// Converts the address into the EIP-55 checksummed form. Fails if the address is mixed-case with
+/// an invalid checksum.
+#[napi]
+pub fn to_checksum_address(address: String) -> napi::Result<String> {
+    Ok(Address::from_str(&address)
+        .map_err(to_napi_error)?
+        .to_checksum())
+}
+
+/// Signs the message the same way as `personal_sign`, returns the 65 byte signature as hex
+#[napi]
+pub fn sign_message(private_key: String, message: Buffer) -> napi::Result<String> {
+    Ok(parse_private_key(&private_key)?
+        .sign_message(message.as_ref())
+        .to_string())
+}
+
+/// Signs the 32 byte hash as is, returns the 65 byte signature as hex
+#[napi]
+pub fn sign_hash(private_key: String, hash: Buffer) -> napi::Result<String> {
+    let hash: [u8; 32] = hash.as_ref().try_into().map_err(|_| {
+        to_napi_error(Error::InvalidLength {
+            expected: "32",
+            actual: hash.len(),
+        })
+    })?;
+    Ok(parse_private_key(&private_key)?
+        .sign_hash(&hash)
+        .to_string())
+}
+
+/// Recovers the checksummed address that signed the message by `personal_sign`
+#[napi]
+pub fn recover_message_signer(message: Buffer, signature: String) -> napi::Result<String> {
+    let signature = Signature::from_str(&signature).map_err(to_napi_error)?;
+    Ok(recover_address(message.as_ref(), &signature)
+        .map_err(to_napi_error)?
+        .to_checksum())
+}
@@ -0,0 +1,377 @@
+use crate::rlp::RlpItem;
+use crate::utils::{keccak256, strip_hex_prefix};
+use crate::{Error, PublicKey, Result};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+use core::str::FromStr;
+
+/// Keccak256 hash of the CREATE3 proxy init code `0x67363d3d37363d34f03d5260086018f3` used by
+/// Solmate and Solady
+const CREATE3_PROXY_HASH: [u8; 32] = [
+    0x21, 0xc3, 0x5d, 0xbe, 0x1b, 0x34, 0x4a, 0x24, 0x88, 0xcf, 0x33, 0x21, 0xd6, 0xce, 0x54, 0x2f,
+    0x8e, 0x9f, 0x30, 0x55, 0x44, 0xff, 0x09, 0xe4, 0x99, 0x3a, 0x62, 0x31, 0x9a, 0x49, 0x7c, 0x1f,
+];
+
+/// Ethereum address stored as raw 20 bytes.
+///
+/// `Display` prints the lowercase hex form with the `0x` prefix, the same format that
+/// `PrivateKey::address()` returns. For the EIP-55 mixed-case form call `to_checksum()`.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, PrivateKey};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let address = private_key.to_address();
+///
+/// assert_eq!(address.to_checksum(), "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+/// assert_eq!(address, Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord, Default)]
+pub struct Address([u8; 20]);
+
+impl Address {
+    /// Creates the address from raw 20 bytes
+    pub const fn new(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+
+    /// Converts your address in the &[u8] format to Address struct. The slice has to be exactly
+    /// 20 bytes long.
+    pub fn from_slice(slice: &[u8]) -> Result<Self> {
+        let bytes: [u8; 20] = slice.try_into().map_err(|_| Error::InvalidLength {
+            expected: "20",
+            actual: slice.len(),
+        })?;
+        Ok(Self(bytes))
+    }
+
+    /// Calculates the address from the public key. The address is the last 20 bytes of the
+    /// Keccak256 hash of the 64 byte uncompressed public key without the 0x04 prefix.
+    pub fn from_public_key(public_key: &PublicKey) -> Self {
+        Self::from_hash(&keccak256(&public_key.serialize_uncompressed()[1..]))
+    }
+
+    /// Calculates the address of the contract deployed by the sender with the `CREATE` opcode or
+    /// a deployment transaction. The address is the last 20 bytes of the Keccak256 hash of
+    /// `rlp([sender, nonce])`.
+    /// ```
+    /// use core::str::FromStr;
+    /// use ethereum_private_key_to_address::Address;
+    ///
+    /// let sender = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+    ///
+    /// assert_eq!(Address::create(&sender, 0).to_checksum(), "0x5FbDB2315678afecb367f032d93F642f64180aa3");
+    /// ```
+    pub fn create(sender: &Address, nonce: u64) -> Self {
+        let encoded = RlpItem::List(vec![
+            RlpItem::from(&sender.0[..]),
+            RlpItem::uint(nonce.into()),
+        ])
+        .encode();
+        Self::from_hash(&keccak256(&encoded))
+    }
+
+    /// Calculates the address of the contract deployed by the deployer with the `CREATE2` opcode
+    /// as described in EIP-1014. The address is the last 20 bytes of the Keccak256 hash of
+    /// `0xff || deployer || salt || keccak256(init_code)`.
+    /// ```
+    /// use core::str::FromStr;
+    /// use ethereum_private_key_to_address::Address;
+    ///
+    /// let deployer = Address::from_str("0xdeadbeef00000000000000000000000000000000").unwrap();
+    /// let address = Address::create2_from_code(&deployer, &[0u8; 32], &[0x00]);
+    ///
+    /// assert_eq!(address.to_checksum(), "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3");
+    /// ```
+    pub fn create2(deployer: &Address, salt: &[u8; 32], init_code_hash: &[u8; 32]) -> Self {
+        let mut data = [0u8; 85];
+        data[0] = 0xff;
+        data[1..21].copy_from_slice(&deployer.0);
+        data[21..53].copy_from_slice(salt);
+        data[53..].copy_from_slice(init_code_hash);
+        Self::from_hash(&keccak256(&data))
+    }
+
+    /// Calculates the `CREATE2` address from the init code itself instead of its hash
+    pub fn create2_from_code(deployer: &Address, salt: &[u8; 32], init_code: &[u8]) -> Self {
+        Self::create2(deployer, salt, &keccak256(init_code))
+    }
+
+    /// Calculates the address of the contract deployed by the deployer with the CREATE3 pattern of
+    /// Solmate and Solady. The deployer creates a minimal proxy by `CREATE2` with the salt and the
+    /// proxy deploys the contract by `CREATE` with nonce 1, so the address depends only on the
+    /// deployer and the salt, not on the init code.
+    ///
+    /// Factories which derive the salt from the caller, e.g. CreateX or the ZeframLou factory, have
+    /// to be given the derived salt.
+    /// ```
+    /// use core::str::FromStr;
+    /// use ethereum_private_key_to_address::Address;
+    ///
+    /// let deployer = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+    /// let mut salt = [0u8; 32];
+    /// salt[31] = 1;
+    ///
+    /// assert_eq!(Address::create3(&deployer, &salt).to_string(), "0x8467c13a70b243eb33ba2468c715a3cf02c3742e");
+    /// ```
+    pub fn create3(deployer: &Address, salt: &[u8; 32]) -> Self {
+        let proxy = Self::create2(deployer, salt, &CREATE3_PROXY_HASH);
+        Self::create(&proxy, 1)
+    }
+
+    /// Creates the address from the last 20 bytes of the hash
+    pub(crate) fn from_hash(hash: &[u8; 32]) -> Self {
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash[12..]);
+        Self(address)
+    }
+
+    /// Returns the address as a reference to the raw 20 bytes
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// Returns the raw 20 bytes of the address as a Vec<u8>
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// Returns the number of zero bytes the address starts with. Each zero byte costs 12 gas less
+    /// in the calldata of every transaction passing the address.
+    pub fn leading_zero_bytes(&self) -> usize {
+        self.0.iter().take_while(|byte| **byte == 0).count()
+    }
+
+    /// Returns the address as a lowercase hex string with the `0x` prefix
+    pub fn to_lowercase(&self) -> String {
+        format!("0x{}", hex::encode(self.0))
+    }
+
+    /// Returns the address as a mixed-case checksummed hex string as described in EIP-55
+    pub fn to_checksum(&self) -> String {
+        let lowercase = hex::encode(self.0);
+        let hash = keccak256(lowercase.as_bytes());
+
+        let mut checksummed = String::with_capacity(42);
+        checksummed.push_str("0x");
+        for (i, c) in lowercase.chars().enumerate() {
+            let nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                checksummed.push(c.to_ascii_uppercase());
+            } else {
+                checksummed.push(c);
+            }
+        }
+        checksummed
+    }
+}
+
+/// Calculates the address from the public key given as a hex string. Accepts the uncompressed
+/// key with or without the 0x04 prefix and the 33 byte compressed key.
+/// ```
+/// use ethereum_private_key_to_address::address_from_pubkey_hex;
+///
+/// let address = address_from_pubkey_hex("038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75").unwrap();
+///
+/// assert_eq!(address.to_string(), "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+/// ```
+pub fn address_from_pubkey_hex(public_key: &str) -> Result<Address> {
+    let public_key = PublicKey::from_str(public_key)?;
+    Ok(Address::from_public_key(&public_key))
+}
+
+impl FromStr for Address {
+    type Err = Error;
+
+    /// Parses the address from hex string. The `0x` prefix is optional. If the string contains
+    /// both uppercase and lowercase letters the EIP-55 checksum is verified.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex_str = strip_hex_prefix(s);
+        let bytes = hex::decode(hex_str)?;
+        let address = Self::from_slice(&bytes)?;
+
+        let has_lowercase = hex_str.chars().any(|c| c.is_ascii_lowercase());
+        let has_uppercase = hex_str.chars().any(|c| c.is_ascii_uppercase());
+        if has_lowercase && has_uppercase && address.to_checksum()[2..] != *hex_str {
+            return Err(Error::InvalidChecksum);
+        }
+
+        Ok(address)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(value: [u8; 20]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Address> for [u8; 20] {
+    fn from(value: Address) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<&[u8]> for Address {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
+    }
+}
+
+impl AsRef<[u8]> for Address {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{address_from_pubkey_hex, Address, Error, PublicKey};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_checksum() {
+        let addresses = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for checksummed in addresses {
+            let address = Address::from_str(&checksummed.to_lowercase()).unwrap();
+            assert_eq!(checksummed, address.to_checksum());
+        }
+    }
+
+    #[test]
+    fn test_invalid_checksum() {
+        assert_eq!(
+            Address::from_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+            Err(Error::InvalidChecksum)
+        );
+        assert!(Address::from_str("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_ok());
+        assert!(Address::from_str("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED").is_ok());
+    }
+
+    #[test]
+    fn test_from_public_key() {
+        let uncompressed = "048318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed753547f11ca8696646f2f3acb08e31016afac23e630c5d11f59f61fef57b0d2aa5";
+        let compressed = "038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75";
+        let expected = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+
+        let public_key = PublicKey::from_str(uncompressed).unwrap();
+        assert_eq!(expected, Address::from_public_key(&public_key));
+        assert_eq!(expected, address_from_pubkey_hex(uncompressed).unwrap());
+        assert_eq!(
+            expected,
+            address_from_pubkey_hex(&uncompressed[2..]).unwrap()
+        );
+        assert_eq!(expected, address_from_pubkey_hex(compressed).unwrap());
+        assert!(address_from_pubkey_hex("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_create() {
+        let sender = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+        let expected = [
+            (0, "0x5FbDB2315678afecb367f032d93F642f64180aa3"),
+            (1, "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512"),
+            (2, "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0"),
+        ];
+        for (nonce, address) in expected {
+            assert_eq!(address, Address::create(&sender, nonce).to_checksum());
+        }
+    }
+
+    #[test]
+    fn test_create2() {
+        let zero = Address::default();
+        let expected = [
+            (zero, "00", "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"),
+            (
+                zero,
+                "deadbeef",
+                "0x70f2b2914A2a4b783FaEFb75f459A580616Fcb5e",
+            ),
+            (zero, "", "0xE33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0"),
+        ];
+        for (deployer, init_code, address) in expected {
+            let init_code = hex::decode(init_code).unwrap();
+            let hash = crate::utils::keccak256(&init_code);
+            assert_eq!(
+                address,
+                Address::create2(&deployer, &[0u8; 32], &hash).to_checksum()
+            );
+            assert_eq!(
+                address,
+                Address::create2_from_code(&deployer, &[0u8; 32], &init_code).to_checksum()
+            );
+        }
+    }
+
+    #[test]
+    fn test_create3() {
+        let proxy_code = hex::decode("67363d3d37363d34f03d5260086018f3").unwrap();
+        assert_eq!(
+            "21c35dbe1b344a2488cf3321d6ce542f8e9f305544ff09e4993a62319a497c1f",
+            hex::encode(super::CREATE3_PROXY_HASH)
+        );
+        assert_eq!(
+            super::CREATE3_PROXY_HASH,
+            crate::utils::keccak256(&proxy_code)
+        );
+
+        let deployer = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+        let mut salt = [0u8; 32];
+        salt[31] = 1;
+        let proxy = Address::create2_from_code(&deployer, &salt, &proxy_code);
+        assert_eq!(
+            "0xba285d410d2935c3af6bbb7b40ce7373ea7b92aa",
+            proxy.to_string()
+        );
+        assert_eq!(
+            "0x8467c13a70b243eb33ba2468c715a3cf02c3742e",
+            Address::create3(&deployer, &salt).to_string()
+        );
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        assert!(Address::from_str("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea").is_err());
+        assert!(Address::from_slice(&[0u8; 19]).is_err());
+    }
+
+    #[test]
+    fn test_display_and_bytes() {
+        let address = Address::from_str("f39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+        assert_eq!(
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            address.to_string()
+        );
+        assert_eq!(address.to_lowercase(), address.to_string());
+        assert_eq!(address.to_vec(), address.as_bytes().to_vec());
+        assert_eq!(Address::from_slice(&address.to_vec()).unwrap(), address);
+        assert_eq!(0, address.leading_zero_bytes());
+        assert_eq!(20, Address::default().leading_zero_bytes());
+        assert_eq!(
+            2,
+            Address::from_str("0x0000f00000000000000000000000000000000000")
+                .unwrap()
+                .leading_zero_bytes()
+        );
+    }
+}
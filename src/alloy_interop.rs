@@ -0,0 +1,106 @@
+use crate::{Address, Error, PrivateKey, PublicKey, Signature};
+use alloy_primitives::{FixedBytes, PrimitiveSignature, B256, B512, U256};
+
+impl From<Address> for alloy_primitives::Address {
+    fn from(value: Address) -> Self {
+        Self::new(value.into())
+    }
+}
+
+impl From<alloy_primitives::Address> for Address {
+    fn from(value: alloy_primitives::Address) -> Self {
+        Self::from(value.into_array())
+    }
+}
+
+/// Converts the public key into the 64 byte uncompressed key without the 0x04 prefix
+impl From<PublicKey> for B512 {
+    fn from(value: PublicKey) -> Self {
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&value.serialize_uncompressed()[1..]);
+        Self::new(bytes)
+    }
+}
+
+/// Parses the 64 byte uncompressed key without the 0x04 prefix, fails if it's not on the curve
+impl TryFrom<B512> for PublicKey {
+    type Error = Error;
+
+    fn try_from(value: B512) -> Result<Self, Self::Error> {
+        Self::from_slice(value.as_slice())
+    }
+}
+
+/// Converts the signature into 65 bytes `r || s || v` where `v` is 27 or 28
+impl From<Signature> for FixedBytes<65> {
+    fn from(value: Signature) -> Self {
+        Self::new(value.to_bytes())
+    }
+}
+
+/// Parses 65 bytes `r || s || v`, the same as `Signature::from_slice()`
+impl TryFrom<FixedBytes<65>> for Signature {
+    type Error = Error;
+
+    fn try_from(value: FixedBytes<65>) -> Result<Self, Self::Error> {
+        Self::from_slice(value.as_slice())
+    }
+}
+
+impl From<Signature> for PrimitiveSignature {
+    fn from(value: Signature) -> Self {
+        Self::new(
+            U256::from_be_bytes(value.r()),
+            U256::from_be_bytes(value.s()),
+            value.y_parity(),
+        )
+    }
+}
+
+/// Parses the 32 byte big-endian private key, fails if it's zero or not below the curve order.
+/// There is no conversion back, see `PrivateKey`.
+impl TryFrom<B256> for PrivateKey {
+    type Error = Error;
+
+    fn try_from(value: B256) -> Result<Self, Self::Error> {
+        Self::from_slice(value.as_slice())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn test_alloy_conversions() {
+        let private_key = PrivateKey::try_from(
+            B256::from_str("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(PrivateKey::try_from(B256::ZERO).is_err());
+
+        let address = private_key.to_address();
+        let alloy_address: alloy_primitives::Address = address.into();
+        assert_eq!(
+            alloy_primitives::Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+                .unwrap(),
+            alloy_address
+        );
+        assert_eq!(address, Address::from(alloy_address));
+
+        let public_key = private_key.to_public_key();
+        assert_eq!(
+            public_key,
+            PublicKey::try_from(B512::from(public_key)).unwrap()
+        );
+        assert!(PublicKey::try_from(B512::ZERO).is_err());
+
+        let signature = private_key.sign_message("hello world");
+        assert_eq!(
+            signature,
+            Signature::try_from(FixedBytes::<65>::from(signature)).unwrap()
+        );
+    }
+}
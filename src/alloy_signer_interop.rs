@@ -0,0 +1,196 @@
+use crate::PrivateKey;
+use alloy_consensus::SignableTransaction;
+use alloy_network::{TxSigner, TxSignerSync};
+use alloy_primitives::{Address, ChainId, PrimitiveSignature, B256};
+use alloy_signer::{Signer, SignerSync};
+use async_trait::async_trait;
+
+/// Private key with the chain id, the signer of alloy. Signs hashes, messages and EIP-712 typed
+/// data as `Signer` and transactions as `TxSigner`, so it goes into alloy's `EthereumWallet`.
+///
+/// With a chain id the transactions without one get it and the transactions of another chain are
+/// rejected, as with alloy's `PrivateKeySigner`.
+/// ```
+/// use std::str::FromStr;
+/// use ethereum_private_key_to_address::{AlloySigner, PrivateKey};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let signer = AlloySigner::new(private_key).with_chain_id(Some(1));
+///
+/// assert_eq!(signer.chain_id(), Some(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlloySigner {
+    /// Key the signer signs with
+    private_key: PrivateKey,
+    /// Chain id of the transactions, `None` for any
+    chain_id: Option<ChainId>,
+}
+
+impl AlloySigner {
+    /// Creates the signer of the key without a chain id
+    pub fn new(private_key: PrivateKey) -> Self {
+        Self {
+            private_key,
+            chain_id: None,
+        }
+    }
+
+    /// Sets the chain id of the transactions
+    pub fn with_chain_id(mut self, chain_id: Option<ChainId>) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Returns the key
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.private_key
+    }
+
+    /// Returns the chain id of the transactions
+    pub fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+}
+
+impl From<PrivateKey> for AlloySigner {
+    fn from(value: PrivateKey) -> Self {
+        Self::new(value)
+    }
+}
+
+#[async_trait]
+impl Signer for AlloySigner {
+    async fn sign_hash(&self, hash: &B256) -> alloy_signer::Result<PrimitiveSignature> {
+        self.sign_hash_sync(hash)
+    }
+
+    fn address(&self) -> Address {
+        self.private_key.to_address().into()
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.chain_id = chain_id;
+    }
+}
+
+impl SignerSync for AlloySigner {
+    fn sign_hash_sync(&self, hash: &B256) -> alloy_signer::Result<PrimitiveSignature> {
+        Ok(self.private_key.sign_hash(&hash.0).into())
+    }
+
+    fn chain_id_sync(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+}
+
+#[async_trait]
+impl TxSigner<PrimitiveSignature> for AlloySigner {
+    fn address(&self) -> Address {
+        Signer::address(self)
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<PrimitiveSignature>,
+    ) -> alloy_signer::Result<PrimitiveSignature> {
+        self.sign_transaction_sync(tx)
+    }
+}
+
+impl TxSignerSync<PrimitiveSignature> for AlloySigner {
+    fn address(&self) -> Address {
+        Signer::address(self)
+    }
+
+    fn sign_transaction_sync(
+        &self,
+        tx: &mut dyn SignableTransaction<PrimitiveSignature>,
+    ) -> alloy_signer::Result<PrimitiveSignature> {
+        if let Some(chain_id) = self.chain_id {
+            if !tx.set_chain_id_checked(chain_id) {
+                return Err(alloy_signer::Error::TransactionChainIdMismatch {
+                    signer: chain_id,
+                    tx: tx.chain_id().expect(
+                        "the chain id can't be set only if the transaction has another one",
+                    ),
+                });
+            }
+        }
+        self.sign_hash_sync(&tx.signature_hash())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::Signature;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::TxKind;
+    use core::str::FromStr;
+
+    #[tokio::test]
+    async fn test_alloy_signer() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let signer = AlloySigner::new(private_key.clone());
+        assert_eq!(
+            Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap(),
+            Signer::address(&signer)
+        );
+
+        let signature = Signer::sign_message(&signer, b"hello world").await.unwrap();
+        assert_eq!(
+            PrimitiveSignature::from(private_key.sign_message("hello world")),
+            signature
+        );
+        assert_eq!(
+            Signer::address(&signer),
+            signature.recover_address_from_msg("hello world").unwrap()
+        );
+        assert_eq!(
+            Signature::from_slice(&signature.as_bytes()).unwrap(),
+            private_key.sign_message("hello world")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_alloy_tx_signer() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let mut signer = AlloySigner::from(private_key);
+        signer.set_chain_id(Some(1));
+        assert_eq!(Some(1), Signer::chain_id(&signer));
+
+        let mut tx = TxLegacy {
+            chain_id: None,
+            nonce: 0,
+            gas_price: 20_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::ZERO),
+            ..Default::default()
+        };
+        let signature = signer.sign_transaction(&mut tx).await.unwrap();
+        assert_eq!(Some(1), tx.chain_id);
+        assert_eq!(
+            Signer::address(&signer),
+            signature
+                .recover_address_from_prehash(&tx.signature_hash())
+                .unwrap()
+        );
+
+        let mut other_chain = TxLegacy {
+            chain_id: Some(5),
+            ..tx
+        };
+        assert!(signer.sign_transaction_sync(&mut other_chain).is_err());
+    }
+}
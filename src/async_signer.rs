@@ -0,0 +1,120 @@
+use crate::{hash_message, Address, Result, Signature, Signer, Transaction};
+use async_trait::async_trait;
+
+/// Asynchronous version of `Signer` for signers that wait on I/O, e.g. a KMS, an HSM or a signer
+/// behind a network API. Only `address()` and `sign_digest()` have to be implemented, the other
+/// methods are built on top of them.
+///
+/// Local signers can be used through `BlockingSigner`.
+/// ```
+/// use std::str::FromStr;
+/// use ethereum_private_key_to_address::{AsyncSigner, BlockingSigner, PrivateKey};
+///
+/// async fn sign_in(signer: &dyn AsyncSigner) -> String {
+///     signer.sign_message(b"hello world").await.unwrap().to_string()
+/// }
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let signer = BlockingSigner::new(private_key);
+/// let signature = sign_in(&signer); // await it on the runtime
+/// ```
+#[async_trait]
+pub trait AsyncSigner: Send + Sync {
+    /// Returns the address of the signer
+    fn address(&self) -> Address;
+
+    /// Signs the 32 byte digest as is
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<Signature>;
+
+    /// Signs the message the same way as `personal_sign`, the message is hashed by
+    /// `hash_message()`
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        self.sign_digest(&hash_message(message)).await
+    }
+
+    /// Signs the transaction and returns the raw signed transaction
+    async fn sign_transaction(&self, transaction: &(dyn Transaction + Sync)) -> Result<Vec<u8>> {
+        let signature = self.sign_digest(&transaction.signing_hash()).await?;
+        Ok(transaction.encode_signed(&signature))
+    }
+}
+
+/// Adapter that implements `AsyncSigner` for a blocking `Signer`. The signer is called directly
+/// on the current task, which is fine for local keys that sign in microseconds but not for
+/// signers that block on I/O.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockingSigner<S> {
+    /// Wrapped signer
+    signer: S,
+}
+
+impl<S: Signer> BlockingSigner<S> {
+    /// Wraps the signer
+    pub fn new(signer: S) -> Self {
+        Self { signer }
+    }
+
+    /// Returns the wrapped signer
+    pub fn inner(&self) -> &S {
+        &self.signer
+    }
+
+    /// Unwraps the signer
+    pub fn into_inner(self) -> S {
+        self.signer
+    }
+}
+
+#[async_trait]
+impl<S: Signer + Send + Sync> AsyncSigner for BlockingSigner<S> {
+    fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<Signature> {
+        self.signer.sign_digest(digest)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        self.signer.sign_message(message)
+    }
+
+    async fn sign_transaction(&self, transaction: &(dyn Transaction + Sync)) -> Result<Vec<u8>> {
+        self.signer.sign_transaction(transaction)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::{Eip1559Transaction, PrivateKey};
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_blocking_signer() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let signer: Box<dyn AsyncSigner> = Box::new(BlockingSigner::new(private_key.clone()));
+        assert_eq!(private_key.to_address(), signer.address());
+
+        let digest = [7u8; 32];
+        assert_eq!(
+            private_key.sign_hash(&digest),
+            signer.sign_digest(&digest).await.unwrap()
+        );
+        assert_eq!(
+            private_key.sign_message("hello world"),
+            signer.sign_message(b"hello world").await.unwrap()
+        );
+
+        let transaction = Eip1559Transaction::new(1)
+            .with_nonce(3)
+            .with_max_fee_per_gas(30_000_000_000);
+        assert_eq!(
+            private_key.sign_transaction(&transaction),
+            signer.sign_transaction(&transaction).await.unwrap()
+        );
+    }
+}
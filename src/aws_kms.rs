@@ -0,0 +1,95 @@
+use crate::{Address, AsyncSigner, Error, PublicKey, Result, Signature};
+use async_trait::async_trait;
+use aws_sdk_kms::error::DisplayErrorContext;
+use aws_sdk_kms::primitives::Blob;
+use aws_sdk_kms::types::{KeySpec, MessageType, SigningAlgorithmSpec};
+use aws_sdk_kms::Client;
+
+/// Signer backed by an AWS KMS asymmetric key with the `ECC_SECG_P256K1` key spec and the
+/// `SIGN_VERIFY` usage. The private key never leaves KMS.
+///
+/// KMS returns only r and s in DER and doesn't enforce low-s, so the signature is normalized and
+/// the recovery id is found locally from the public key.
+/// ```no_run
+/// use ethereum_private_key_to_address::{AsyncSigner, AwsKmsSigner};
+///
+/// # async fn run(client: aws_sdk_kms::Client) -> ethereum_private_key_to_address::Result<()> {
+/// // The client is created from the `aws-config` configuration
+/// let signer = AwsKmsSigner::new(client, "alias/my-ethereum-key").await?;
+/// let signature = signer.sign_message(b"hello world").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AwsKmsSigner {
+    /// KMS client
+    client: Client,
+    /// Key id, key ARN, alias name or alias ARN
+    key_id: String,
+    /// Public key of the KMS key
+    public_key: PublicKey,
+}
+
+impl AwsKmsSigner {
+    /// Creates the signer for the KMS key, the public key is fetched from KMS
+    pub async fn new(client: Client, key_id: impl Into<String>) -> Result<Self> {
+        let key_id = key_id.into();
+        let output = client
+            .get_public_key()
+            .key_id(&key_id)
+            .send()
+            .await
+            .map_err(|error| Error::Signer(DisplayErrorContext(error).to_string()))?;
+
+        if output.key_spec() != Some(&KeySpec::EccSecgP256K1) {
+            return Err(Error::Signer(format!(
+                "KMS key {key_id} isn't a secp256k1 key"
+            )));
+        }
+        let der = output
+            .public_key()
+            .ok_or_else(|| Error::Signer("KMS didn't return the public key".to_string()))?;
+        let public_key = PublicKey::from_spki_der(der.as_ref())?;
+
+        Ok(Self {
+            client,
+            key_id,
+            public_key,
+        })
+    }
+
+    /// Returns the key id
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Returns the public key of the KMS key
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+}
+
+#[async_trait]
+impl AsyncSigner for AwsKmsSigner {
+    fn address(&self) -> Address {
+        self.public_key.address()
+    }
+
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<Signature> {
+        let output = self
+            .client
+            .sign()
+            .key_id(&self.key_id)
+            .message(Blob::new(digest.to_vec()))
+            .message_type(MessageType::Digest)
+            .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
+            .send()
+            .await
+            .map_err(|error| Error::Signer(DisplayErrorContext(error).to_string()))?;
+
+        let der = output
+            .signature()
+            .ok_or_else(|| Error::Signer("KMS didn't return the signature".to_string()))?;
+        Signature::from_der(der.as_ref(), false)?.recover_parity(digest, &self.public_key)
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{Address, AsyncSigner, Error, PublicKey, Result, Signature};
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Version of the Key Vault REST API
+const API_VERSION: &str = "7.4";
+
+/// OAuth 2.0 scope of Key Vault
+const SCOPE: &str = "https://vault.azure.net/.default";
+
+/// Tokens are renewed this long before they expire
+const TOKEN_MARGIN: Duration = Duration::from_secs(60);
+
+/// Credential used to authenticate to Key Vault
+#[derive(Clone, PartialEq, Eq)]
+pub enum AzureCredential {
+    /// Access token obtained elsewhere, e.g. from `az account get-access-token --resource
+    /// https://vault.azure.net` or a managed identity. It isn't renewed.
+    AccessToken(String),
+    /// Client secret of a Microsoft Entra ID application (service principal), the token is
+    /// requested and renewed by the signer
+    ClientSecret {
+        /// Directory (tenant) id
+        tenant_id: String,
+        /// Application (client) id
+        client_id: String,
+        /// Client secret
+        client_secret: String,
+    },
+}
+
+impl fmt::Debug for AzureCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AccessToken(_) => f.debug_tuple("AccessToken").field(&"<redacted>").finish(),
+            Self::ClientSecret {
+                tenant_id,
+                client_id,
+                ..
+            } => f
+                .debug_struct("ClientSecret")
+                .field("tenant_id", tenant_id)
+                .field("client_id", client_id)
+                .field("client_secret", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+/// Response of `GET /keys/{name}/{version}`
+#[derive(Deserialize)]
+struct KeyResponse {
+    key: JsonWebKey,
+}
+
+/// Public part of the key in the JWK format
+#[derive(Deserialize)]
+struct JsonWebKey {
+    kid: String,
+    crv: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+}
+
+/// Response of `POST /keys/{name}/{version}/sign`
+#[derive(Deserialize)]
+struct SignResponse {
+    value: String,
+}
+
+/// Response of the Microsoft identity platform token endpoint
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// Signer backed by an Azure Key Vault (or Managed HSM) EC key on the `P-256K` curve, signing
+/// with `ES256K`. The private key never leaves Key Vault.
+///
+/// Key Vault returns only `r || s` and doesn't enforce low-s, so the signature is normalized and
+/// the recovery id is found locally from the public key. The key id can be given with or without
+/// the version, without it the current version at the time of creating the signer is used.
+/// ```no_run
+/// use ethereum_private_key_to_address::{AsyncSigner, AzureCredential, AzureKeyVaultSigner};
+///
+/// # async fn run() -> ethereum_private_key_to_address::Result<()> {
+/// let credential = AzureCredential::ClientSecret {
+///     tenant_id: "00000000-0000-0000-0000-000000000000".to_string(),
+///     client_id: "00000000-0000-0000-0000-000000000000".to_string(),
+///     client_secret: std::env::var("AZURE_CLIENT_SECRET").unwrap(),
+/// };
+/// let signer =
+///     AzureKeyVaultSigner::new("https://my-vault.vault.azure.net/keys/ethereum", credential).await?;
+/// let signature = signer.sign_message(b"hello world").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AzureKeyVaultSigner {
+    /// HTTP client
+    client: reqwest::Client,
+    /// Credential used to get the access token
+    credential: AzureCredential,
+    /// Access token and the time it expires
+    token: Mutex<Option<(String, Instant)>>,
+    /// Key id including the version
+    key_id: String,
+    /// Public key of the key
+    public_key: PublicKey,
+}
+
+impl AzureKeyVaultSigner {
+    /// Creates the signer for the key, the public key is fetched from Key Vault
+    pub async fn new(key_id: &str, credential: AzureCredential) -> Result<Self> {
+        Self::with_client(reqwest::Client::new(), key_id, credential).await
+    }
+
+    /// Creates the signer that sends the requests by the given HTTP client
+    pub async fn with_client(
+        client: reqwest::Client,
+        key_id: &str,
+        credential: AzureCredential,
+    ) -> Result<Self> {
+        let token = Mutex::new(None);
+        let response: KeyResponse = client
+            .get(format!(
+                "{}?api-version={API_VERSION}",
+                key_id.trim_end_matches('/')
+            ))
+            .bearer_auth(access_token(&client, &credential, &token).await?)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(request_error)?
+            .json()
+            .await
+            .map_err(request_error)?;
+
+        let key = response.key;
+        if key.crv.as_deref() != Some("P-256K") {
+            return Err(Error::Signer(format!(
+                "Key Vault key {} isn't a P-256K key",
+                key.kid
+            )));
+        }
+        let (Some(x), Some(y)) = (key.x, key.y) else {
+            return Err(Error::Signer(
+                "Key Vault didn't return the public key".to_string(),
+            ));
+        };
+        let mut point = vec![0x04];
+        for coordinate in [x, y] {
+            point.extend(
+                URL_SAFE_NO_PAD
+                    .decode(coordinate)
+                    .map_err(|_| Error::InvalidPublicKey)?,
+            );
+        }
+
+        Ok(Self {
+            client,
+            credential,
+            token,
+            key_id: key.kid,
+            public_key: PublicKey::from_slice(&point)?,
+        })
+    }
+
+    /// Returns the key id including the version
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Returns the public key of the key
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+}
+
+impl fmt::Debug for AzureKeyVaultSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AzureKeyVaultSigner")
+            .field("key_id", &self.key_id)
+            .field("address", &format_args!("{}", self.public_key.address()))
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl AsyncSigner for AzureKeyVaultSigner {
+    fn address(&self) -> Address {
+        self.public_key.address()
+    }
+
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<Signature> {
+        let token = access_token(&self.client, &self.credential, &self.token).await?;
+        let response: SignResponse = self
+            .client
+            .post(format!("{}/sign?api-version={API_VERSION}", self.key_id))
+            .bearer_auth(token)
+            .json(&json!({ "alg": "ES256K", "value": URL_SAFE_NO_PAD.encode(digest) }))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(request_error)?
+            .json()
+            .await
+            .map_err(request_error)?;
+
+        let rs = URL_SAFE_NO_PAD.decode(response.value).map_err(|_| {
+            Error::Signer("Key Vault returned invalid base64 signature".to_string())
+        })?;
+        if rs.len() != 64 {
+            return Err(Error::Signer(format!(
+                "Key Vault returned {} byte signature, expected 64",
+                rs.len()
+            )));
+        }
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&rs[..32]);
+        s.copy_from_slice(&rs[32..]);
+        Signature::new(r, s, false)?.recover_parity(digest, &self.public_key)
+    }
+}
+
+/// Returns the access token of the credential. The token of the client secret is cached and a
+/// new one is requested when it's about to expire.
+async fn access_token(
+    client: &reqwest::Client,
+    credential: &AzureCredential,
+    cache: &Mutex<Option<(String, Instant)>>,
+) -> Result<String> {
+    let (tenant_id, client_id, client_secret) = match credential {
+        AzureCredential::AccessToken(token) => return Ok(token.clone()),
+        AzureCredential::ClientSecret {
+            tenant_id,
+            client_id,
+            client_secret,
+        } => (tenant_id, client_id, client_secret),
+    };
+    let cached = cache.lock().expect("lock isn't poisoned").clone();
+    if let Some((token, expires_at)) = cached {
+        if Instant::now() + TOKEN_MARGIN < expires_at {
+            return Ok(token);
+        }
+    }
+
+    let response: TokenResponse = client
+        .post(format!(
+            "https://login.microsoftonline.com/{tenant_id}/oauth2/v2.0/token"
+        ))
+        .form(&[
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("scope", SCOPE),
+        ])
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(request_error)?
+        .json()
+        .await
+        .map_err(request_error)?;
+
+    let expires_at = Instant::now() + Duration::from_secs(response.expires_in);
+    *cache.lock().expect("lock isn't poisoned") = Some((response.access_token.clone(), expires_at));
+    Ok(response.access_token)
+}
+
+/// Converts the error of the Key Vault or the token request
+fn request_error(error: reqwest::Error) -> Error {
+    Error::Signer(format!("Azure request failed: {error}"))
+}
@@ -0,0 +1,67 @@
+use crate::keccak_batch::keccak256_public_keys;
+use crate::{backend, Address, PrivateKey};
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Number of keys a rayon task derives together
+#[cfg(feature = "rayon")]
+const CHUNK_SIZE: usize = 256;
+
+/// Derives the addresses of the private keys, in the same order. All keys share one secp256k1
+/// context and the public keys are hashed in one batch, which makes converting thousands of keys
+/// cheap.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{derive_addresses, PrivateKey};
+///
+/// let keys = vec![
+///     PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap(),
+///     PrivateKey::from_str("59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d").unwrap(),
+/// ];
+///
+/// let addresses = derive_addresses(&keys);
+/// assert_eq!("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266", addresses[0].to_string());
+/// assert_eq!("0x70997970c51812dc3a010c7d01b50e0d17dc79c8", addresses[1].to_string());
+/// ```
+pub fn derive_addresses(keys: &[PrivateKey]) -> Vec<Address> {
+    let public_keys: Vec<[u8; 64]> = keys
+        .iter()
+        .map(|key| {
+            let serialized =
+                backend::serialize_uncompressed(&backend::public_key(&key.private_key));
+            serialized[1..]
+                .try_into()
+                .expect("public key is 64 bytes long")
+        })
+        .collect();
+    keccak256_public_keys(&public_keys)
+        .iter()
+        .map(Address::from_hash)
+        .collect()
+}
+
+/// Same as `derive_addresses()` but the keys are split between the threads of the rayon thread
+/// pool
+#[cfg(feature = "rayon")]
+pub fn par_derive_addresses(keys: &[PrivateKey]) -> Vec<Address> {
+    keys.par_chunks(CHUNK_SIZE)
+        .flat_map_iter(derive_addresses)
+        .collect()
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_derive_addresses() {
+        let keys: Vec<PrivateKey> = (0..100).map(|_| PrivateKey::random()).collect();
+        let expected: Vec<Address> = keys.iter().map(|key| key.to_address()).collect();
+
+        assert_eq!(expected, derive_addresses(&keys));
+        #[cfg(feature = "rayon")]
+        assert_eq!(expected, par_derive_addresses(&keys));
+        assert!(derive_addresses(&[]).is_empty());
+    }
+}
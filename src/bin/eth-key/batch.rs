@@ -0,0 +1,95 @@
+use crate::output::{Format, Record};
+use ethereum_private_key_to_address::{Error, Result, Wallet};
+use std::io::{BufRead, Write};
+use zeroize::Zeroizing;
+
+/// Writes the checksummed address of every line of the input, returns the number of lines that
+/// failed. Lines are hex private keys, mnemonics or `xprv` keys, see `Wallet::detect()`. Without
+/// the format every input line has one output line so they can be pasted side by side, empty and
+/// failed lines have an empty one. With the format the records of the empty and failed lines are
+/// left out. The errors are reported with the line number and don't stop the run.
+pub fn derive_lines(
+    input: impl BufRead,
+    mut output: impl Write,
+    mut errors: impl Write,
+    format: Option<Format>,
+) -> Result<usize> {
+    let io_error = |error: std::io::Error| Error::Io(error.to_string());
+    if let Some(format) = format {
+        format.write_header(&mut output).map_err(io_error)?;
+    }
+    let mut failed = 0;
+    for (index, line) in input.lines().enumerate() {
+        let line = Zeroizing::new(line.map_err(io_error)?);
+        let private_key = if line.trim().is_empty() {
+            None
+        } else {
+            match Wallet::parse(&line, None) {
+                Ok(private_key) => Some(private_key),
+                Err(error) => {
+                    failed += 1;
+                    writeln!(errors, "line {}: {error}", index + 1).map_err(io_error)?;
+                    None
+                }
+            }
+        };
+        match (format, private_key) {
+            (Some(format), Some(private_key)) => format
+                .write_record(&mut output, &Record::new(&private_key))
+                .map_err(io_error)?,
+            (Some(_), None) => {}
+            (None, Some(private_key)) => {
+                writeln!(output, "{}", private_key.to_address().to_checksum()).map_err(io_error)?
+            }
+            (None, None) => writeln!(output).map_err(io_error)?,
+        }
+    }
+    output.flush().map_err(io_error)?;
+    Ok(failed)
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_derive_lines() {
+        let input = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80
+
+0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d
+not a key
+test test test test test test test test test test test junk
+";
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        let failed = derive_lines(input.as_bytes(), &mut output, &mut errors, None).unwrap();
+
+        assert_eq!(1, failed);
+        assert_eq!(
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+
+0x70997970C51812dc3A010C7d01b50e0d17dc79C8
+
+0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+",
+            String::from_utf8(output).unwrap()
+        );
+        assert!(String::from_utf8(errors).unwrap().starts_with("line 4: "));
+    }
+
+    #[test]
+    fn test_derive_lines_with_format() {
+        let input = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80
+
+not a key
+";
+        let mut output = Vec::new();
+        let failed =
+            derive_lines(input.as_bytes(), &mut output, Vec::new(), Some(Format::Csv)).unwrap();
+
+        assert_eq!(1, failed);
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(2, output.lines().count());
+        assert!(output.ends_with(",0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266\n"));
+    }
+}
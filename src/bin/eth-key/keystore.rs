@@ -0,0 +1,165 @@
+use clap::{Args, ValueEnum};
+use ethereum_private_key_to_address::{Error, KdfPreset, KeystoreOptions, PrivateKey, Result};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// Source of the keystore password, the terminal prompt if neither is given
+#[derive(Args)]
+pub struct PasswordArgs {
+    /// Environment variable with the password
+    #[arg(long, value_name = "VARIABLE", conflicts_with = "password_file")]
+    password_env: Option<String>,
+    /// File with the password, the trailing newline is ignored
+    #[arg(long, value_name = "FILE")]
+    password_file: Option<PathBuf>,
+}
+
+/// scrypt parameters of the created keystore, see `KdfPreset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Kdf {
+    /// `n = 4096`, unlocks in tens of milliseconds
+    Light,
+    /// `n = 262144`, the default of geth, unlocks in about a second
+    #[default]
+    Standard,
+    /// `n = 1048576`, unlocks in several seconds
+    Paranoid,
+}
+
+impl From<Kdf> for KdfPreset {
+    fn from(kdf: Kdf) -> Self {
+        match kdf {
+            Kdf::Light => KdfPreset::Light,
+            Kdf::Standard => KdfPreset::Standard,
+            Kdf::Paranoid => KdfPreset::Paranoid,
+        }
+    }
+}
+
+/// Reads the password from the environment variable, the file or the terminal. The terminal
+/// asks twice when `confirm` is set, until both match.
+pub fn read_password(password_args: &PasswordArgs, confirm: bool) -> Result<Zeroizing<String>> {
+    if let Some(variable) = &password_args.password_env {
+        return env::var(variable)
+            .map(Zeroizing::new)
+            .map_err(|_| Error::PasswordRequired);
+    }
+    if let Some(path) = &password_args.password_file {
+        let password = Zeroizing::new(
+            fs::read_to_string(path).map_err(|error| Error::Io(format!("{path:?}: {error}")))?,
+        );
+        return Ok(Zeroizing::new(
+            password.trim_end_matches(['\n', '\r']).to_string(),
+        ));
+    }
+    loop {
+        let password = prompt("Password: ")?;
+        if !confirm || *password == *prompt("Repeat password: ")? {
+            return Ok(password);
+        }
+        eprintln!("Passwords don't match, try again");
+    }
+}
+
+/// Reads the password from the terminal without echoing it
+fn prompt(message: &str) -> Result<Zeroizing<String>> {
+    rpassword::prompt_password(message)
+        .map(Zeroizing::new)
+        .map_err(|error| Error::Io(format!("terminal: {error}")))
+}
+
+/// Decrypts the keystore V3 file
+pub fn decrypt(path: &Path, password: &str) -> Result<PrivateKey> {
+    let json = fs::read_to_string(path).map_err(|error| Error::Io(format!("{path:?}: {error}")))?;
+    PrivateKey::from_keystore(&json, password)
+}
+
+/// Encrypts the private key into the keystore V3 JSON
+pub fn create(private_key: &PrivateKey, password: &str, kdf: Kdf) -> Result<String> {
+    private_key.to_keystore(password, &KeystoreOptions::preset(kdf.into()))
+}
+
+/// Writes the keystore into a new file, readable and writable only by the owner on Unix. Fails if
+/// the file exists, so another keystore is never overwritten.
+pub fn write_new(path: &Path, json: &str) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).map_err(|error| match error.kind() {
+        io::ErrorKind::AlreadyExists => Error::Io(format!("{path:?} already exists")),
+        _ => Error::Io(format!("{path:?}: {error}")),
+    })?;
+    file.write_all(json.as_bytes())
+        .map_err(|error| Error::Io(format!("{path:?}: {error}")))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_keystore() {
+        let path = env::temp_dir().join(format!(
+            "ethereum-private-key-to-address-eth-key-keystore-{}",
+            std::process::id()
+        ));
+        let password_path = path.with_extension("password");
+        fs::write(&password_path, "password\n").unwrap();
+        let password = read_password(
+            &PasswordArgs {
+                password_env: None,
+                password_file: Some(password_path.clone()),
+            },
+            true,
+        )
+        .unwrap();
+        assert_eq!("password", password.as_str());
+
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let json = create(&private_key, &password, Kdf::Light).unwrap();
+        write_new(&path, &json).unwrap();
+        assert_eq!(private_key, decrypt(&path, &password).unwrap());
+        assert_eq!(Err(Error::InvalidPassword), decrypt(&path, "other"));
+        assert_eq!(
+            Err(Error::Io(format!("{path:?} already exists"))),
+            write_new(&path, &json)
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(0o600, mode & 0o777);
+        }
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&password_path).unwrap();
+    }
+
+    #[test]
+    fn test_password_env() {
+        let variable = format!("ETH_KEY_TEST_PASSWORD_{}", std::process::id());
+        let password_args = PasswordArgs {
+            password_env: Some(variable.clone()),
+            password_file: None,
+        };
+        assert_eq!(
+            Err(Error::PasswordRequired),
+            read_password(&password_args, false)
+        );
+        env::set_var(&variable, "password");
+        assert_eq!(
+            "password",
+            read_password(&password_args, false).unwrap().as_str()
+        );
+    }
+}
@@ -0,0 +1,259 @@
+mod batch;
+mod keystore;
+mod output;
+
+use clap::{Args, Parser, Subcommand};
+use ethereum_private_key_to_address::{Error, PrivateKey, Result};
+use keystore::{Kdf, PasswordArgs};
+use output::{Format, Record};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use zeroize::Zeroizing;
+
+/// Derives Ethereum addresses and public keys from private keys
+#[derive(Parser)]
+#[command(name = "eth-key", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Prints the fingerprint, the public key and both address forms in the format instead of the
+    /// plain text
+    #[arg(long, global = true, value_enum)]
+    format: Option<Format>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prints the address and the public key of the private key
+    Address(KeyArgs),
+    /// Prints the checksummed address of every private key, mnemonic or xprv in the file, one per
+    /// line. Invalid lines are reported on stderr and leave an empty line in the output.
+    Batch {
+        /// File with one key per line, stdin if it's not given
+        file: Option<PathBuf>,
+    },
+    /// Encrypts and decrypts keystore V3 files of geth and other wallets
+    Keystore {
+        #[command(subcommand)]
+        command: KeystoreCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeystoreCommand {
+    /// Decrypts the keystore and prints the address and the private key. With `--format` only the
+    /// record without the private key is printed.
+    Decrypt {
+        /// Keystore V3 JSON file
+        file: PathBuf,
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+    /// Encrypts the private key, or a new random one, into a keystore V3 JSON
+    Create {
+        #[command(flatten)]
+        key: KeyArgs,
+        /// Generates a new random private key instead of reading one
+        #[arg(long, conflicts_with_all = ["private_key", "file"])]
+        random: bool,
+        #[command(flatten)]
+        password: PasswordArgs,
+        /// scrypt parameters of the keystore
+        #[arg(long, value_enum, default_value_t = Kdf::Standard)]
+        kdf: Kdf,
+        /// New file the keystore is written to, readable only by the owner, stdout if it's not
+        /// given. Fails if the file exists
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Source of the private key, stdin if neither the key nor the file is given
+#[derive(Args)]
+struct KeyArgs {
+    /// Hex private key, the `0x` prefix is optional. Arguments end up in the shell history and
+    /// the process list, prefer `--file` or stdin.
+    #[arg(conflicts_with = "file")]
+    private_key: Option<String>,
+    /// File with the hex private key
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(exit_code) => exit_code,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the command, fails if any key of the batch is invalid
+fn run(cli: Cli) -> Result<ExitCode> {
+    match cli.command {
+        Command::Address(key_args) => {
+            let private_key = read_private_key(&key_args)?;
+            print_record(&private_key, cli.format, || {
+                println!("Address:    {}", private_key.to_address().to_checksum());
+                println!("Public key: 0x{}", private_key.public_key_full());
+            })?;
+        }
+        Command::Batch { file } => {
+            let output = io::BufWriter::new(io::stdout().lock());
+            let failed = match file {
+                Some(path) => {
+                    let file = fs::File::open(&path)
+                        .map_err(|error| Error::Io(format!("{path:?}: {error}")))?;
+                    batch::derive_lines(io::BufReader::new(file), output, io::stderr(), cli.format)?
+                }
+                None => batch::derive_lines(io::stdin().lock(), output, io::stderr(), cli.format)?,
+            };
+            if failed > 0 {
+                eprintln!("{failed} invalid lines");
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Keystore { command } => run_keystore(command, cli.format)?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Runs the keystore command
+fn run_keystore(command: KeystoreCommand, format: Option<Format>) -> Result<()> {
+    match command {
+        KeystoreCommand::Decrypt { file, password } => {
+            let password = keystore::read_password(&password, false)?;
+            let private_key = keystore::decrypt(&file, &password)?;
+            print_record(&private_key, format, || {
+                println!("Address:     {}", private_key.to_address().to_checksum());
+                println!(
+                    "Private key: 0x{}",
+                    private_key.expose_secret_hex().as_str()
+                );
+            })
+        }
+        KeystoreCommand::Create {
+            key,
+            random,
+            password,
+            kdf,
+            output,
+        } => {
+            let private_key = if random {
+                PrivateKey::random()
+            } else {
+                read_private_key(&key)?
+            };
+            let password = keystore::read_password(&password, true)?;
+            let json = keystore::create(&private_key, &password, kdf)?;
+            match output {
+                Some(path) => {
+                    keystore::write_new(&path, &json)?;
+                    print_record(&private_key, format, || {
+                        println!("Address: {}", private_key.to_address().to_checksum());
+                    })
+                }
+                None => {
+                    println!("{json}");
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// Prints the record of the key in the format, or calls `text` without one
+fn print_record(
+    private_key: &PrivateKey,
+    format: Option<Format>,
+    text: impl FnOnce(),
+) -> Result<()> {
+    match format {
+        Some(format) => {
+            let mut output = io::stdout().lock();
+            format
+                .write_header(&mut output)
+                .and_then(|_| format.write_record(&mut output, &Record::new(private_key)))
+                .map_err(|error| Error::Io(error.to_string()))
+        }
+        None => {
+            text();
+            Ok(())
+        }
+    }
+}
+
+/// Reads the private key from the argument, the file or stdin, surrounding whitespace is ignored
+fn read_private_key(key_args: &KeyArgs) -> Result<PrivateKey> {
+    let input = Zeroizing::new(match (&key_args.private_key, &key_args.file) {
+        (Some(private_key), _) => private_key.clone(),
+        (None, Some(path)) => read_file(path)?,
+        (None, None) => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|error| Error::Io(format!("stdin: {error}")))?;
+            input
+        }
+    });
+    PrivateKey::from_str(input.trim())
+}
+
+/// Reads the file into a string
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|error| Error::Io(format!("{path:?}: {error}")))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use clap::CommandFactory;
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+        assert!(Cli::try_parse_from(["eth-key", "address", PRIVATE_KEY, "-f", "key"]).is_err());
+        assert!(
+            Cli::try_parse_from(["eth-key", "keystore", "create", "--random", "-f", "key"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["eth-key", "keystore", "decrypt", "key.json"]).is_ok());
+    }
+
+    #[test]
+    fn test_read_private_key() {
+        let path = std::env::temp_dir().join(format!(
+            "ethereum-private-key-to-address-eth-key-{}",
+            std::process::id()
+        ));
+        fs::write(&path, format!("0x{PRIVATE_KEY}\n")).unwrap();
+
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let from_file = read_private_key(&KeyArgs {
+            private_key: None,
+            file: Some(path.clone()),
+        });
+        assert_eq!(Ok(private_key.clone()), from_file);
+        let from_arg = read_private_key(&KeyArgs {
+            private_key: Some(PRIVATE_KEY.to_string()),
+            file: None,
+        });
+        assert_eq!(Ok(private_key), from_arg);
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            read_private_key(&KeyArgs {
+                private_key: None,
+                file: Some(path),
+            }),
+            Err(Error::Io(_))
+        ));
+    }
+}
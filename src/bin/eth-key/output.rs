@@ -0,0 +1,127 @@
+use clap::ValueEnum;
+use ethereum_private_key_to_address::PrivateKey;
+use serde_json::json;
+use std::io::{self, Write};
+
+/// Structured output format selected by `--format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One JSON object per line
+    Json,
+    /// Comma-separated values with a header
+    Csv,
+    /// Columns aligned with spaces with a header
+    Table,
+}
+
+/// Everything printed about a key, none of it reveals the private key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// BIP-32 fingerprint of the public key as hex
+    pub fingerprint: String,
+    /// 65 byte uncompressed public key as hex with the `0x04` prefix
+    pub public_key: String,
+    /// Lowercase address
+    pub address: String,
+    /// EIP-55 checksummed address
+    pub checksum_address: String,
+}
+
+impl Record {
+    /// Creates the record of the private key
+    pub fn new(private_key: &PrivateKey) -> Self {
+        let public_key = private_key.to_public_key();
+        let address = public_key.address();
+        Self {
+            fingerprint: hex::encode(public_key.fingerprint()),
+            public_key: format!("0x{}", public_key.to_hex_full()),
+            address: address.to_lowercase(),
+            checksum_address: address.to_checksum(),
+        }
+    }
+}
+
+impl Format {
+    /// Writes the header, JSON doesn't have one
+    pub fn write_header(self, output: &mut impl Write) -> io::Result<()> {
+        match self {
+            Self::Json => Ok(()),
+            Self::Csv => writeln!(output, "fingerprint,public_key,address,checksum_address"),
+            Self::Table => writeln!(
+                output,
+                "{:<11}  {:<132}  {:<42}  CHECKSUM ADDRESS",
+                "FINGERPRINT", "PUBLIC KEY", "ADDRESS"
+            ),
+        }
+    }
+
+    /// Writes the record as one line. All fields are hex of fixed length, so CSV needs no quoting
+    /// and the table columns stay aligned without buffering the records.
+    pub fn write_record(self, output: &mut impl Write, record: &Record) -> io::Result<()> {
+        match self {
+            Self::Json => {
+                let json = json!({
+                    "fingerprint": record.fingerprint,
+                    "public_key": record.public_key,
+                    "address": record.address,
+                    "checksum_address": record.checksum_address,
+                });
+                writeln!(output, "{json}")
+            }
+            Self::Csv => writeln!(
+                output,
+                "{},{},{},{}",
+                record.fingerprint, record.public_key, record.address, record.checksum_address
+            ),
+            Self::Table => writeln!(
+                output,
+                "{:<11}  {:<132}  {:<42}  {}",
+                record.fingerprint, record.public_key, record.address, record.checksum_address
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_formats() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let record = Record::new(&private_key);
+        assert_eq!("a5547601", record.fingerprint);
+        assert_eq!("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266", record.address);
+
+        let mut json = Vec::new();
+        Format::Json.write_header(&mut json).unwrap();
+        Format::Json.write_record(&mut json, &record).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            value["checksum_address"]
+        );
+
+        let mut csv = Vec::new();
+        Format::Csv.write_header(&mut csv).unwrap();
+        Format::Csv.write_record(&mut csv, &record).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!("fingerprint,public_key,address,checksum_address", lines[0]);
+        assert_eq!(4, lines[1].split(',').count());
+
+        let mut table = Vec::new();
+        Format::Table.write_header(&mut table).unwrap();
+        Format::Table.write_record(&mut table, &record).unwrap();
+        let table = String::from_utf8(table).unwrap();
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(
+            lines[0].find("CHECKSUM ADDRESS"),
+            lines[1].find("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+        );
+    }
+}
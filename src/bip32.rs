@@ -0,0 +1,299 @@
+use crate::derivation_path::HARDENED;
+use crate::{backend, Error, IntoDerivationPath, PrivateKey, Result};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+/// Version bytes of the mainnet extended private key, serialized as `xprv`
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+
+/// BIP-32 extended private key. Contains the private key together with the chain code, so child
+/// private keys can be derived from it.
+///
+/// ```
+/// use ethereum_private_key_to_address::{ExtendedPrivateKey, Mnemonic};
+///
+/// let mnemonic = Mnemonic::parse("test test test test test test test test test test test junk").unwrap();
+/// let master = ExtendedPrivateKey::from_seed(&mnemonic.to_seed()[..]).unwrap();
+///
+/// let account = master.derive_path("m/44'/60'/0'/0").unwrap();
+/// let first = account.derive_child(0).unwrap();
+///
+/// assert_eq!(first.private_key().address(), "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExtendedPrivateKey {
+    /// Private Key
+    private_key: PrivateKey,
+    /// Chain code
+    chain_code: Zeroizing<[u8; 32]>,
+    /// Depth in the tree, 0 for the master key
+    depth: u8,
+    /// Fingerprint of the parent key, zeroes for the master key
+    parent_fingerprint: [u8; 4],
+    /// Index of this key in the parent, 0 for the master key
+    child_number: u32,
+}
+
+impl ExtendedPrivateKey {
+    /// Child indexes starting from this value are hardened
+    pub const HARDENED: u32 = HARDENED;
+
+    /// Creates the master key from the seed, for example the BIP-39 seed of a mnemonic
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        let (private_key, chain_code) = split_hmac(b"Bitcoin seed", seed, |il| {
+            backend::secret_key_from_slice(il).map_err(|_| Error::InvalidSeed)
+        })?;
+
+        Ok(Self {
+            private_key: PrivateKey { private_key },
+            chain_code,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+        })
+    }
+
+    /// Derives the child key at the given index. Indexes starting from
+    /// `ExtendedPrivateKey::HARDENED` derive hardened keys.
+    pub fn derive_child(&self, index: u32) -> Result<Self> {
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or(Error::InvalidChildKey(index))?;
+        let parent = &self.private_key.private_key;
+
+        let mut data = Zeroizing::new(Vec::with_capacity(37));
+        if index >= HARDENED {
+            data.push(0);
+            data.extend_from_slice(&backend::secret_bytes(parent));
+        } else {
+            data.extend_from_slice(&self.private_key.public_key_compressed_slice());
+        }
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let (private_key, chain_code) = split_hmac(&self.chain_code[..], &data, |il| {
+            let tweak = il.try_into().expect("IL is 32 bytes long");
+            backend::add_tweak(parent, tweak).map_err(|_| Error::InvalidChildKey(index))
+        })?;
+
+        Ok(Self {
+            private_key: PrivateKey { private_key },
+            chain_code,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+        })
+    }
+
+    /// Derives the key at the given path like `m/44'/60'/0'/0/0`. The path can be a string or
+    /// `DerivationPath` and must start with `m`, which stands for this key and not necessarily the
+    /// master key, so it should be called on the master key to get the standard wallet accounts.
+    pub fn derive_path(&self, path: impl IntoDerivationPath) -> Result<Self> {
+        path.into_derivation_path()?
+            .indexes()
+            .iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    /// Returns the private key
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.private_key
+    }
+
+    /// Converts the extended key into the private key
+    pub fn into_private_key(self) -> PrivateKey {
+        self.private_key
+    }
+
+    /// Returns the chain code
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Returns the depth in the tree, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the index of this key in the parent, 0 for the master key
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// Returns the fingerprint of the parent key, zeroes for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Serializes the key into the Base58Check `xprv...` string
+    pub fn to_xprv(&self) -> Zeroizing<String> {
+        let mut data = Zeroizing::new(Vec::with_capacity(78));
+        data.extend_from_slice(&XPRV_VERSION);
+        data.push(self.depth);
+        data.extend_from_slice(&self.parent_fingerprint);
+        data.extend_from_slice(&self.child_number.to_be_bytes());
+        data.extend_from_slice(&self.chain_code[..]);
+        data.push(0);
+        data.extend_from_slice(&self.private_key.secret_bytes()[..]);
+        Zeroizing::new(bs58::encode(&data[..]).with_check().into_string())
+    }
+
+    /// Returns the fingerprint of this key, the first 4 bytes of RIPEMD160(SHA256(public key))
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.private_key.to_public_key().fingerprint()
+    }
+}
+
+impl FromStr for ExtendedPrivateKey {
+    type Err = Error;
+
+    /// Parses the Base58Check `xprv...` string
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| Error::InvalidExtendedKey(reason.to_string());
+
+        let data = Zeroizing::new(
+            bs58::decode(s.trim())
+                .with_check(None)
+                .into_vec()
+                .map_err(|_| invalid("invalid Base58Check encoding"))?,
+        );
+        if data.len() != 78 {
+            return Err(invalid("extended key has to be 78 bytes long"));
+        }
+        if data[..4] != XPRV_VERSION {
+            return Err(invalid("only mainnet xprv keys are supported"));
+        }
+        if data[45] != 0 {
+            return Err(invalid("extended key doesn't contain private key"));
+        }
+
+        let depth = data[4];
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&data[5..9]);
+        let child_number = u32::from_be_bytes(data[9..13].try_into().expect("4 bytes"));
+        if depth == 0 && (parent_fingerprint != [0; 4] || child_number != 0) {
+            return Err(invalid("master key can't have parent or child number"));
+        }
+        let mut chain_code = Zeroizing::new([0u8; 32]);
+        chain_code.copy_from_slice(&data[13..45]);
+
+        Ok(Self {
+            private_key: PrivateKey::from_slice(&data[46..])?,
+            chain_code,
+            depth,
+            parent_fingerprint,
+            child_number,
+        })
+    }
+}
+
+/// The private key and chain code are redacted
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("private_key", &self.private_key)
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &hex::encode(self.parent_fingerprint))
+            .field("child_number", &self.child_number)
+            .finish()
+    }
+}
+
+/// Calculates HMAC-SHA512 and splits the result into the key created from the left half by the
+/// given function and the chain code from the right half
+fn split_hmac(
+    key: &[u8],
+    data: &[u8],
+    to_key: impl FnOnce(&[u8]) -> Result<backend::SecretKey>,
+) -> Result<(backend::SecretKey, Zeroizing<[u8; 32]>)> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts key of any length");
+    mac.update(data);
+    let mut result = Zeroizing::new([0u8; 64]);
+    result.copy_from_slice(&mac.finalize().into_bytes());
+
+    let key = to_key(&result[..32])?;
+    let mut chain_code = Zeroizing::new([0u8; 32]);
+    chain_code.copy_from_slice(&result[32..]);
+    Ok((key, chain_code))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::DerivationPath;
+
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn test_master_key() {
+        let master = ExtendedPrivateKey::from_seed(&hex::decode(SEED).unwrap()).unwrap();
+        assert_eq!(
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            master.private_key().expose_secret_hex().as_str()
+        );
+        assert_eq!(
+            "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+            hex::encode(master.chain_code())
+        );
+        assert_eq!("3442193e", hex::encode(master.fingerprint()));
+        assert_eq!(0, master.depth());
+    }
+
+    #[test]
+    fn test_xprv() {
+        let master = ExtendedPrivateKey::from_seed(&hex::decode(SEED).unwrap()).unwrap();
+        let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        assert_eq!(xprv, master.to_xprv().as_str());
+        assert_eq!(master, ExtendedPrivateKey::from_str(xprv).unwrap());
+
+        let child = master.derive_child(ExtendedPrivateKey::HARDENED).unwrap();
+        let xprv = "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7";
+        assert_eq!(xprv, child.to_xprv().as_str());
+        assert_eq!(child, ExtendedPrivateKey::from_str(xprv).unwrap());
+
+        assert!(ExtendedPrivateKey::from_str(&xprv[..xprv.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_bip32_vector_one() {
+        // Test vector 1 from BIP-32, chain m/0'/1/2'/2/1000000000
+        let master = ExtendedPrivateKey::from_seed(&hex::decode(SEED).unwrap()).unwrap();
+
+        let child = master.derive_child(ExtendedPrivateKey::HARDENED).unwrap();
+        assert_eq!(
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            child.private_key().expose_secret_hex().as_str()
+        );
+        assert_eq!(
+            "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+            hex::encode(child.chain_code())
+        );
+        assert_eq!(master.fingerprint(), child.parent_fingerprint());
+        assert_eq!(1, child.depth());
+
+        let key = master.derive_path("m/0'/1/2'/2/1000000000").unwrap();
+        assert_eq!(
+            key,
+            master
+                .derive_path(&DerivationPath::new(vec![
+                    HARDENED,
+                    1,
+                    2 + HARDENED,
+                    2,
+                    1000000000
+                ]))
+                .unwrap()
+        );
+        assert_eq!(
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+            key.private_key().expose_secret_hex().as_str()
+        );
+        assert_eq!(5, key.depth());
+        assert_eq!(1000000000, key.child_number());
+    }
+}
@@ -0,0 +1,331 @@
+use crate::{Error, Result};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+use core::str::FromStr;
+
+/// Child indexes starting from this value are hardened
+pub(crate) const HARDENED: u32 = 0x8000_0000;
+
+/// BIP-32 derivation path like `m/44'/60'/0'/0/0`.
+///
+/// The path can be parsed from a string, where hardened indexes are marked with `'` or `h`, or
+/// built with the typed BIP-44 builder:
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::DerivationPath;
+///
+/// let parsed = DerivationPath::from_str("m/44'/60'/0'/0/3").unwrap();
+/// let built = DerivationPath::builder()
+///     .purpose(44)
+///     .coin_type(60)
+///     .account(0)
+///     .change(0)
+///     .index(3)
+///     .unwrap();
+///
+/// assert_eq!(parsed, built);
+/// assert_eq!(parsed, DerivationPath::ethereum(3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DerivationPath {
+    /// Child indexes, hardened ones have the highest bit set
+    indexes: Vec<u32>,
+}
+
+impl DerivationPath {
+    /// Child indexes starting from this value are hardened
+    pub const HARDENED: u32 = HARDENED;
+
+    /// Creates the path from raw child indexes. Hardened indexes have to include
+    /// `DerivationPath::HARDENED`.
+    pub fn new(indexes: Vec<u32>) -> Self {
+        Self { indexes }
+    }
+
+    /// Returns the standard Ethereum path `m/44'/60'/0'/0/{index}` used by most wallets
+    pub fn ethereum(index: u32) -> Self {
+        Self::new(vec![44 + HARDENED, 60 + HARDENED, HARDENED, 0, index])
+    }
+
+    /// Returns the typed BIP-44 builder of the path
+    /// `m/purpose'/coin_type'/account'/change/index`
+    pub fn builder() -> PurposeBuilder {
+        PurposeBuilder
+    }
+
+    /// Returns the child indexes of the path
+    pub fn indexes(&self) -> &[u32] {
+        &self.indexes
+    }
+
+    /// Returns new path with the child index appended
+    pub fn child(&self, index: u32) -> Self {
+        let mut indexes = self.indexes.clone();
+        indexes.push(index);
+        Self::new(indexes)
+    }
+
+    /// Returns the number of levels in the path
+    pub fn len(&self) -> usize {
+        self.indexes.len()
+    }
+
+    /// Returns true if the path is just `m`
+    pub fn is_empty(&self) -> bool {
+        self.indexes.is_empty()
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| Error::InvalidDerivationPath(format!("{s}: {reason}"));
+
+        let mut parts = s.trim().split('/');
+        if parts.next() != Some("m") {
+            return Err(invalid("path has to start with m"));
+        }
+        let indexes = parts
+            .map(|part| {
+                let (index, hardened) = match part.strip_suffix(['\'', 'h', 'H']) {
+                    Some(index) => (index, true),
+                    None => (part, false),
+                };
+                if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(invalid("index has to be a number"));
+                }
+                let index: u32 = index
+                    .parse()
+                    .map_err(|_| invalid("index is out of bounds"))?;
+                if index >= HARDENED {
+                    return Err(invalid("index is out of bounds"));
+                }
+                Ok(if hardened { index + HARDENED } else { index })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { indexes })
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.indexes {
+            if *index >= HARDENED {
+                write!(f, "/{}'", index - HARDENED)?;
+            } else {
+                write!(f, "/{index}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Conversion into `DerivationPath`, implemented for strings and the path itself so the
+/// derivation APIs accept both
+pub trait IntoDerivationPath {
+    /// Converts the value into the derivation path
+    fn into_derivation_path(self) -> Result<DerivationPath>;
+}
+
+impl IntoDerivationPath for DerivationPath {
+    fn into_derivation_path(self) -> Result<DerivationPath> {
+        Ok(self)
+    }
+}
+
+impl IntoDerivationPath for &DerivationPath {
+    fn into_derivation_path(self) -> Result<DerivationPath> {
+        Ok(self.clone())
+    }
+}
+
+impl IntoDerivationPath for &str {
+    fn into_derivation_path(self) -> Result<DerivationPath> {
+        DerivationPath::from_str(self)
+    }
+}
+
+impl IntoDerivationPath for &String {
+    fn into_derivation_path(self) -> Result<DerivationPath> {
+        DerivationPath::from_str(self)
+    }
+}
+
+/// First step of the BIP-44 builder, sets the hardened purpose
+#[derive(Debug, Clone, Copy)]
+pub struct PurposeBuilder;
+
+impl PurposeBuilder {
+    /// Sets the purpose, usually 44. The index is hardened.
+    pub fn purpose(self, purpose: u32) -> CoinTypeBuilder {
+        CoinTypeBuilder { purpose }
+    }
+}
+
+/// Second step of the BIP-44 builder, sets the hardened coin type
+#[derive(Debug, Clone, Copy)]
+pub struct CoinTypeBuilder {
+    purpose: u32,
+}
+
+impl CoinTypeBuilder {
+    /// Sets the coin type, 60 for Ethereum. The index is hardened.
+    pub fn coin_type(self, coin_type: u32) -> AccountBuilder {
+        AccountBuilder {
+            purpose: self.purpose,
+            coin_type,
+        }
+    }
+}
+
+/// Third step of the BIP-44 builder, sets the hardened account
+#[derive(Debug, Clone, Copy)]
+pub struct AccountBuilder {
+    purpose: u32,
+    coin_type: u32,
+}
+
+impl AccountBuilder {
+    /// Sets the account. The index is hardened.
+    pub fn account(self, account: u32) -> ChangeBuilder {
+        ChangeBuilder {
+            purpose: self.purpose,
+            coin_type: self.coin_type,
+            account,
+        }
+    }
+}
+
+/// Fourth step of the BIP-44 builder, sets the change
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeBuilder {
+    purpose: u32,
+    coin_type: u32,
+    account: u32,
+}
+
+impl ChangeBuilder {
+    /// Sets the change, 0 for external and 1 for internal chain. Ethereum wallets always use 0.
+    pub fn change(self, change: u32) -> IndexBuilder {
+        IndexBuilder {
+            purpose: self.purpose,
+            coin_type: self.coin_type,
+            account: self.account,
+            change,
+        }
+    }
+}
+
+/// Last step of the BIP-44 builder, sets the address index
+#[derive(Debug, Clone, Copy)]
+pub struct IndexBuilder {
+    purpose: u32,
+    coin_type: u32,
+    account: u32,
+    change: u32,
+}
+
+impl IndexBuilder {
+    /// Sets the address index and returns the path. Fails if any of the indexes is out of
+    /// bounds, every index has to be less than 2^31.
+    pub fn index(self, index: u32) -> Result<DerivationPath> {
+        let levels = [
+            ("purpose", self.purpose),
+            ("coin type", self.coin_type),
+            ("account", self.account),
+            ("change", self.change),
+            ("index", index),
+        ];
+        for (name, value) in levels {
+            if value >= HARDENED {
+                return Err(Error::InvalidDerivationPath(format!(
+                    "{name} {value} is out of bounds"
+                )));
+            }
+        }
+        if self.change > 1 {
+            return Err(Error::InvalidDerivationPath(format!(
+                "change has to be 0 or 1, got {}",
+                self.change
+            )));
+        }
+
+        Ok(DerivationPath::new(vec![
+            self.purpose + HARDENED,
+            self.coin_type + HARDENED,
+            self.account + HARDENED,
+            self.change,
+            index,
+        ]))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            DerivationPath::from_str("m/44'/60'/0'/0/3")
+                .unwrap()
+                .indexes(),
+            [44 + HARDENED, 60 + HARDENED, HARDENED, 0, 3]
+        );
+        assert_eq!(
+            DerivationPath::from_str("m/0h/1").unwrap().indexes(),
+            [HARDENED, 1]
+        );
+        assert!(DerivationPath::from_str("m").unwrap().is_empty());
+        assert!(DerivationPath::from_str("44'/60'").is_err());
+        assert!(DerivationPath::from_str("m/2147483648").is_err());
+        assert!(DerivationPath::from_str("m/a").is_err());
+        assert!(DerivationPath::from_str("m//0").is_err());
+        assert!(DerivationPath::from_str("m/0''").is_err());
+        assert!(DerivationPath::from_str("m/+1").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        for path in ["m", "m/44'/60'/0'/0/0", "m/0/2147483647'"] {
+            assert_eq!(path, DerivationPath::from_str(path).unwrap().to_string());
+        }
+        assert_eq!(
+            "m/0'",
+            DerivationPath::from_str("m/0h").unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        let path = DerivationPath::builder()
+            .purpose(44)
+            .coin_type(60)
+            .account(1)
+            .change(0)
+            .index(7)
+            .unwrap();
+        assert_eq!("m/44'/60'/1'/0/7", path.to_string());
+        assert_eq!("m/44'/60'/1'/0/7/1", path.child(1).to_string());
+
+        assert!(DerivationPath::builder()
+            .purpose(44)
+            .coin_type(60)
+            .account(HARDENED)
+            .change(0)
+            .index(0)
+            .is_err());
+        assert!(DerivationPath::builder()
+            .purpose(44)
+            .coin_type(60)
+            .account(0)
+            .change(2)
+            .index(0)
+            .is_err());
+    }
+}
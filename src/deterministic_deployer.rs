@@ -0,0 +1,60 @@
+use crate::Address;
+use alloc::vec::Vec;
+
+/// Address of the deterministic deployment proxy (Arachnid's CREATE2 factory), the same on every
+/// chain where it's deployed by its presigned transaction. Foundry deploys `new Contract{salt}()`
+/// through it.
+pub const DETERMINISTIC_DEPLOYER: Address = Address::new([
+    0x4e, 0x59, 0xb4, 0x48, 0x47, 0xb3, 0x79, 0x57, 0x85, 0x88, 0x92, 0x0c, 0xa7, 0x8f, 0xbf, 0x26,
+    0xc0, 0xb4, 0x95, 0x6c,
+]);
+
+/// Calculates the address of the contract deployed through the deterministic deployment proxy
+/// with the salt and the init code, the constructor arguments included
+/// ```
+/// use ethereum_private_key_to_address::{deterministic_deployment_address, deterministic_deployment_calldata};
+///
+/// let init_code = [0x00];
+/// let address = deterministic_deployment_address(&[0u8; 32], &init_code);
+///
+/// assert_eq!(address.to_string(), "0x24c4fd2db1cf4cb1aec651cc0e060a00d400e784");
+/// // Sent to the proxy to deploy the contract
+/// let calldata = deterministic_deployment_calldata(&[0u8; 32], &init_code);
+/// ```
+pub fn deterministic_deployment_address(salt: &[u8; 32], init_code: &[u8]) -> Address {
+    Address::create2_from_code(&DETERMINISTIC_DEPLOYER, salt, init_code)
+}
+
+/// Returns the calldata of the transaction to the deterministic deployment proxy which deploys
+/// the init code with the salt. The proxy takes the raw `salt || init_code`, not ABI encoded.
+pub fn deterministic_deployment_calldata(salt: &[u8; 32], init_code: &[u8]) -> Vec<u8> {
+    let mut calldata = Vec::with_capacity(32 + init_code.len());
+    calldata.extend_from_slice(salt);
+    calldata.extend_from_slice(init_code);
+    calldata
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn test_deterministic_deployment() {
+        assert_eq!(
+            Address::from_str("0x4e59b44847b379578588920cA78FbF26c0B4956C").unwrap(),
+            DETERMINISTIC_DEPLOYER
+        );
+
+        let salt = [0u8; 32];
+        let init_code = [0x00];
+        assert_eq!(
+            Address::from_str("0x24c4fd2db1cf4cb1aec651cc0e060a00d400e784").unwrap(),
+            deterministic_deployment_address(&salt, &init_code)
+        );
+
+        let calldata = deterministic_deployment_calldata(&salt, &init_code);
+        assert_eq!(salt, calldata[..32]);
+        assert_eq!(init_code, calldata[32..]);
+    }
+}
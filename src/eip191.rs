@@ -0,0 +1,88 @@
+use crate::utils::keccak256;
+use crate::{Address, PrivateKey, Signature};
+use alloc::{format, vec};
+
+/// Hashes the message the same way as `personal_sign`, Keccak-256 of
+/// `"\x19Ethereum Signed Message:\n" + len(message) + message` (EIP-191 version `0x45`)
+pub fn hash_message(message: impl AsRef<[u8]>) -> [u8; 32] {
+    let message = message.as_ref();
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    data.extend_from_slice(message);
+    keccak256(&data)
+}
+
+/// Hashes the data intended for the validator contract, Keccak-256 of
+/// `0x19 || 0x00 || validator address || data` (EIP-191 version `0x00`)
+pub fn hash_with_validator(validator: &Address, data: impl AsRef<[u8]>) -> [u8; 32] {
+    let mut input = vec![0x19, 0x00];
+    input.extend_from_slice(validator.as_bytes());
+    input.extend_from_slice(data.as_ref());
+    keccak256(&input)
+}
+
+impl PrivateKey {
+    /// Signs the message the same way as `personal_sign` and `eth_sign`, the message is prefixed
+    /// and hashed by `hash_message()`
+    /// ```
+    /// use core::str::FromStr;
+    /// use ethereum_private_key_to_address::PrivateKey;
+    ///
+    /// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+    /// let signature = private_key.sign_message("hello world");
+    ///
+    /// println!("{signature}");
+    /// ```
+    pub fn sign_message(&self, message: impl AsRef<[u8]>) -> Signature {
+        self.sign_hash(&hash_message(message))
+    }
+
+    /// Signs the data intended for the validator contract, the data is hashed by
+    /// `hash_with_validator()`. Used by meta-transaction schemes where only the given contract
+    /// should accept the signature.
+    pub fn sign_with_validator(&self, validator: &Address, data: impl AsRef<[u8]>) -> Signature {
+        self.sign_hash(&hash_with_validator(validator, data))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_hash_message() {
+        assert_eq!(
+            "d9eba16ed0ecae432b71fe008c98cc872bb4cc214d3220a36f365326cf807d68",
+            hex::encode(hash_message("hello world"))
+        );
+    }
+
+    #[test]
+    fn test_hash_with_validator() {
+        let validator = Address::from_str("0x5FbDB2315678afecb367f032d93F642f64180aa3").unwrap();
+        assert_eq!(
+            "babcf87cd80d4bb13161cf23ac181aea59011fe1b13d0cd591d1d697f455a609",
+            hex::encode(hash_with_validator(&validator, "hello world"))
+        );
+    }
+
+    #[test]
+    fn test_sign_message() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        assert_eq!(
+            "0xa461f509887bd19e312c0c58467ce8ff8e300d3c1a90b608a760c5b80318eaf15fe57c96f9175d6cd4daad4663763baa7e78836e067d0163e9a2ccf2ff753f5b1b",
+            private_key.sign_message(b"hello world").to_string()
+        );
+
+        let validator = Address::from_str("0x5FbDB2315678afecb367f032d93F642f64180aa3").unwrap();
+        assert_eq!(
+            "0x37385fee9be8407a0b13c2678e64d6ffe18be6e00dcd8fed6378e67b696c6d005288fe993532b9d97d40b4309ff107469375f3f4c0f3d2e6d99147d1555d73b01c",
+            private_key
+                .sign_with_validator(&validator, "hello world")
+                .to_string()
+        );
+    }
+}
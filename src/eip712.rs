@@ -0,0 +1,426 @@
+use crate::utils::keccak256;
+use crate::{Address, PrivateKey, Signature};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+/// Struct that can be hashed and signed as EIP-712 typed data.
+///
+/// `encode_type()` returns the type the same way as Solidity, the primary type followed by the
+/// referenced struct types sorted by name. `encode_data()` returns the encoded members in the
+/// order of the type, use `Eip712Encoder` to build it.
+/// ```
+/// use ethereum_private_key_to_address::{Address, Eip712, Eip712Encoder};
+///
+/// struct Person {
+///     name: String,
+///     wallet: Address,
+/// }
+///
+/// impl Eip712 for Person {
+///     fn encode_type() -> String {
+///         "Person(string name,address wallet)".to_string()
+///     }
+///
+///     fn encode_data(&self) -> Vec<u8> {
+///         Eip712Encoder::new()
+///             .string(&self.name)
+///             .address(&self.wallet)
+///             .finish()
+///     }
+/// }
+/// ```
+pub trait Eip712 {
+    /// Returns the encoded type, e.g. `Mail(Person from,Person to,string contents)Person(string
+    /// name,address wallet)`
+    fn encode_type() -> String;
+
+    /// Returns the members encoded into 32 byte words in the order of the type
+    fn encode_data(&self) -> Vec<u8>;
+
+    /// Returns Keccak-256 of the encoded type
+    fn type_hash() -> [u8; 32] {
+        keccak256(Self::encode_type().as_bytes())
+    }
+
+    /// Returns `hashStruct`, Keccak-256 of the type hash followed by the encoded data
+    fn struct_hash(&self) -> [u8; 32] {
+        let mut data = Self::type_hash().to_vec();
+        data.extend_from_slice(&self.encode_data());
+        keccak256(&data)
+    }
+}
+
+/// Encodes struct members into 32 byte words as specified by EIP-712 `encodeData`.
+///
+/// Atomic values are padded to 32 bytes, `string` and `bytes` are replaced by their Keccak-256
+/// hash, nested structs by their struct hash and arrays by the hash of the encoded items.
+#[derive(Debug, Clone, Default)]
+pub struct Eip712Encoder {
+    /// Encoded words
+    data: Vec<u8>,
+}
+
+impl Eip712Encoder {
+    /// Creates the encoder with no members
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a 32 byte word as is
+    pub fn word(mut self, word: [u8; 32]) -> Self {
+        self.data.extend_from_slice(&word);
+        self
+    }
+
+    /// Appends `address`
+    pub fn address(self, address: &Address) -> Self {
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(address.as_bytes());
+        self.word(word)
+    }
+
+    /// Appends `uint8` to `uint128`, or any bigger `uint` whose value fits into `u128`
+    pub fn uint(self, value: u128) -> Self {
+        let mut word = [0u8; 32];
+        word[16..].copy_from_slice(&value.to_be_bytes());
+        self.word(word)
+    }
+
+    /// Appends `uint256` given as 32 big-endian bytes
+    pub fn uint256(self, value: [u8; 32]) -> Self {
+        self.word(value)
+    }
+
+    /// Appends `int8` to `int128`, the value is sign extended
+    pub fn int(self, value: i128) -> Self {
+        let mut word = if value < 0 { [0xff; 32] } else { [0u8; 32] };
+        word[16..].copy_from_slice(&value.to_be_bytes());
+        self.word(word)
+    }
+
+    /// Appends `bool`
+    pub fn bool(self, value: bool) -> Self {
+        self.uint(value as u128)
+    }
+
+    /// Appends `bytes1` to `bytes32`, the value is padded with zeroes on the right
+    ///
+    /// # Panics
+    /// If the value is longer than 32 bytes
+    pub fn fixed_bytes(self, value: &[u8]) -> Self {
+        assert!(value.len() <= 32, "fixed bytes are at most 32 bytes long");
+        let mut word = [0u8; 32];
+        word[..value.len()].copy_from_slice(value);
+        self.word(word)
+    }
+
+    /// Appends dynamic `bytes`
+    pub fn bytes(self, value: &[u8]) -> Self {
+        self.word(keccak256(value))
+    }
+
+    /// Appends `string`
+    pub fn string(self, value: &str) -> Self {
+        self.bytes(value.as_bytes())
+    }
+
+    /// Appends a nested struct
+    pub fn value<T: Eip712>(self, value: &T) -> Self {
+        self.word(value.struct_hash())
+    }
+
+    /// Appends an array given as the encoded items, e.g. struct hashes of the items
+    pub fn array(self, items: impl IntoIterator<Item = [u8; 32]>) -> Self {
+        let items = items.into_iter().flatten().collect::<Vec<_>>();
+        self.word(keccak256(&items))
+    }
+
+    /// Returns the encoded members
+    pub fn finish(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// EIP-712 domain, which binds the signature to the application. Only the fields that are set
+/// are part of the domain type.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, Eip712Domain};
+///
+/// let domain = Eip712Domain::new()
+///     .with_name("Ether Mail")
+///     .with_version("1")
+///     .with_chain_id(1)
+///     .with_verifying_contract(
+///         Address::from_str("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").unwrap(),
+///     );
+///
+/// assert_eq!(
+///     "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f",
+///     hex::encode(domain.separator())
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Eip712Domain {
+    /// Name of the application or protocol
+    name: Option<String>,
+    /// Current major version of the application
+    version: Option<String>,
+    /// EIP-155 chain id
+    chain_id: Option<u64>,
+    /// Address of the contract that verifies the signature
+    verifying_contract: Option<Address>,
+    /// Disambiguating salt
+    salt: Option<[u8; 32]>,
+}
+
+impl Eip712Domain {
+    /// Creates the domain with no fields
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the version
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Sets the chain id
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the verifying contract
+    pub fn with_verifying_contract(mut self, verifying_contract: Address) -> Self {
+        self.verifying_contract = Some(verifying_contract);
+        self
+    }
+
+    /// Sets the salt
+    pub fn with_salt(mut self, salt: [u8; 32]) -> Self {
+        self.salt = Some(salt);
+        self
+    }
+
+    /// Returns the name
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the version
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns the chain id
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+
+    /// Returns the verifying contract
+    pub fn verifying_contract(&self) -> Option<Address> {
+        self.verifying_contract
+    }
+
+    /// Returns the salt
+    pub fn salt(&self) -> Option<[u8; 32]> {
+        self.salt
+    }
+
+    /// Returns the encoded domain type with only the fields that are set, e.g.
+    /// `EIP712Domain(string name,uint256 chainId)`
+    pub fn encode_type(&self) -> String {
+        let fields = [
+            (self.name.is_some(), "string name"),
+            (self.version.is_some(), "string version"),
+            (self.chain_id.is_some(), "uint256 chainId"),
+            (
+                self.verifying_contract.is_some(),
+                "address verifyingContract",
+            ),
+            (self.salt.is_some(), "bytes32 salt"),
+        ];
+        let fields = fields
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, field)| *field)
+            .collect::<Vec<_>>();
+        format!("EIP712Domain({})", fields.join(","))
+    }
+
+    /// Returns the domain separator, the struct hash of the domain
+    pub fn separator(&self) -> [u8; 32] {
+        let mut encoder = Eip712Encoder::new().word(keccak256(self.encode_type().as_bytes()));
+        if let Some(name) = &self.name {
+            encoder = encoder.string(name);
+        }
+        if let Some(version) = &self.version {
+            encoder = encoder.string(version);
+        }
+        if let Some(chain_id) = self.chain_id {
+            encoder = encoder.uint(chain_id.into());
+        }
+        if let Some(verifying_contract) = &self.verifying_contract {
+            encoder = encoder.address(verifying_contract);
+        }
+        if let Some(salt) = self.salt {
+            encoder = encoder.word(salt);
+        }
+        keccak256(&encoder.finish())
+    }
+}
+
+/// Hashes the struct hash in the domain the same way as `eth_signTypedData_v4`, Keccak-256 of
+/// `0x19 || 0x01 || domain separator || struct hash`
+pub fn hash_typed_data_struct(domain: &Eip712Domain, struct_hash: &[u8; 32]) -> [u8; 32] {
+    hash_with_separator(&domain.separator(), struct_hash)
+}
+
+/// Keccak-256 of `0x19 || 0x01 || domain separator || struct hash`
+pub(crate) fn hash_with_separator(separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    let mut data = vec![0x19, 0x01];
+    data.extend_from_slice(separator);
+    data.extend_from_slice(struct_hash);
+    keccak256(&data)
+}
+
+/// Hashes the typed data in the domain the same way as `eth_signTypedData_v4`
+pub fn hash_typed_data<T: Eip712>(domain: &Eip712Domain, value: &T) -> [u8; 32] {
+    hash_typed_data_struct(domain, &value.struct_hash())
+}
+
+impl PrivateKey {
+    /// Signs the typed data in the domain the same way as `eth_signTypedData_v4`, the data is
+    /// hashed by `hash_typed_data()`
+    pub fn sign_typed_data<T: Eip712>(&self, domain: &Eip712Domain, value: &T) -> Signature {
+        self.sign_hash(&hash_typed_data(domain, value))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    struct Person {
+        name: &'static str,
+        wallet: Address,
+    }
+
+    impl Eip712 for Person {
+        fn encode_type() -> String {
+            "Person(string name,address wallet)".to_string()
+        }
+
+        fn encode_data(&self) -> Vec<u8> {
+            Eip712Encoder::new()
+                .string(self.name)
+                .address(&self.wallet)
+                .finish()
+        }
+    }
+
+    struct Mail {
+        from: Person,
+        to: Person,
+        contents: &'static str,
+    }
+
+    impl Eip712 for Mail {
+        fn encode_type() -> String {
+            format!(
+                "Mail(Person from,Person to,string contents){}",
+                Person::encode_type()
+            )
+        }
+
+        fn encode_data(&self) -> Vec<u8> {
+            Eip712Encoder::new()
+                .value(&self.from)
+                .value(&self.to)
+                .string(self.contents)
+                .finish()
+        }
+    }
+
+    // Example from the EIP-712 specification
+    fn mail() -> (Eip712Domain, Mail) {
+        let address = |address| Address::from_str(address).unwrap();
+        let domain = Eip712Domain::new()
+            .with_name("Ether Mail")
+            .with_version("1")
+            .with_chain_id(1)
+            .with_verifying_contract(address("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"));
+        let mail = Mail {
+            from: Person {
+                name: "Cow",
+                wallet: address("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+            },
+            to: Person {
+                name: "Bob",
+                wallet: address("0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+            },
+            contents: "Hello, Bob!",
+        };
+        (domain, mail)
+    }
+
+    #[test]
+    fn test_hash_typed_data() {
+        let (domain, mail) = mail();
+        assert_eq!(
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f",
+            hex::encode(domain.separator())
+        );
+        assert_eq!(
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e",
+            hex::encode(mail.struct_hash())
+        );
+        assert_eq!(
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2",
+            hex::encode(hash_typed_data(&domain, &mail))
+        );
+    }
+
+    #[test]
+    fn test_sign_typed_data() {
+        let (domain, mail) = mail();
+        let private_key = PrivateKey::try_from(keccak256(b"cow")).unwrap();
+        assert_eq!(
+            "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826",
+            private_key.address()
+        );
+        assert_eq!(
+            "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c",
+            private_key.sign_typed_data(&domain, &mail).to_string()
+        );
+    }
+
+    #[test]
+    fn test_encoder() {
+        let data = Eip712Encoder::new()
+            .int(-1)
+            .bool(true)
+            .fixed_bytes(&[0xab])
+            .finish();
+        assert_eq!([0xff; 32], data[..32]);
+        assert_eq!(1, data[63]);
+        assert_eq!(0xab, data[64]);
+        assert_eq!([0u8; 31], data[65..]);
+
+        let domain = Eip712Domain::new().with_chain_id(5);
+        assert_eq!("EIP712Domain(uint256 chainId)", domain.encode_type());
+        let mut expected = keccak256(b"EIP712Domain(uint256 chainId)").to_vec();
+        expected.extend_from_slice(&Eip712Encoder::new().uint(5).finish());
+        assert_eq!(keccak256(&expected), domain.separator());
+    }
+}
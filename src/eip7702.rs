@@ -0,0 +1,159 @@
+use crate::rlp::{encode_bytes, encode_list, encode_uint};
+use crate::transaction::encode_signature;
+use crate::utils::keccak256;
+use crate::{Address, PrivateKey, Signature};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Magic byte prepended to the RLP encoded authorization before hashing
+const MAGIC: u8 = 0x05;
+
+/// EIP-7702 authorization, which sets the code of the signer's account to delegate to the
+/// contract at the address. Chain id zero makes the authorization valid on every chain.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, Authorization, PrivateKey};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let delegate = Address::from_str("0x63c0c19a282a1b52b07dd5a65b58948a07dae32b").unwrap();
+///
+/// let authorization = Authorization::new(1, delegate, 0);
+/// let signed = private_key.sign_authorization(&authorization);
+///
+/// assert_eq!(signed.authorization(), &authorization);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Authorization {
+    /// EIP-155 chain id, zero for every chain
+    chain_id: u64,
+    /// Contract the account delegates to
+    address: Address,
+    /// Nonce of the signer's account when the authorization is processed
+    nonce: u64,
+}
+
+impl Authorization {
+    /// Creates the authorization of the delegation to the address
+    pub fn new(chain_id: u64, address: Address, nonce: u64) -> Self {
+        Self {
+            chain_id,
+            address,
+            nonce,
+        }
+    }
+
+    /// Returns the chain id
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Returns the contract the account delegates to
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns the nonce
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Returns the hash that is signed, Keccak-256 of `0x05 || rlp([chain_id, address, nonce])`
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let mut data = vec![MAGIC];
+        data.extend_from_slice(&encode_list(&self.fields()));
+        keccak256(&data)
+    }
+
+    /// Returns the RLP encoded fields
+    fn fields(&self) -> Vec<Vec<u8>> {
+        vec![
+            encode_uint(self.chain_id.into()),
+            encode_bytes(self.address.as_bytes()),
+            encode_uint(self.nonce.into()),
+        ]
+    }
+}
+
+/// Authorization with the signature, an item of the authorization list of the type `0x04`
+/// transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SignedAuthorization {
+    /// Signed authorization
+    authorization: Authorization,
+    /// Signature of the account owner
+    signature: Signature,
+}
+
+impl SignedAuthorization {
+    /// Creates the signed authorization from the authorization and its signature
+    pub fn new(authorization: Authorization, signature: Signature) -> Self {
+        Self {
+            authorization,
+            signature,
+        }
+    }
+
+    /// Returns the authorization
+    pub fn authorization(&self) -> &Authorization {
+        &self.authorization
+    }
+
+    /// Returns the signature
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// Returns the RLP encoded tuple `[chain_id, address, nonce, y_parity, r, s]`
+    pub fn to_rlp(&self) -> Vec<u8> {
+        let mut fields = self.authorization.fields();
+        fields.extend(encode_signature(&self.signature));
+        encode_list(&fields)
+    }
+}
+
+impl PrivateKey {
+    /// Signs the EIP-7702 authorization, which delegates the code of this account to the
+    /// contract
+    pub fn sign_authorization(&self, authorization: &Authorization) -> SignedAuthorization {
+        let signature = self.sign_hash(&authorization.signing_hash());
+        SignedAuthorization::new(*authorization, signature)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_sign_authorization() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let delegate = Address::from_str("0x63c0c19a282a1b52b07dd5a65b58948a07dae32b").unwrap();
+
+        let authorization = Authorization::new(1, delegate, 0);
+        assert_eq!(
+            "f743228656c74db57ce53cafe32c6023a8aad310a4bcc217d125f9f7796520fc",
+            hex::encode(authorization.signing_hash())
+        );
+        let signed = private_key.sign_authorization(&authorization);
+        assert!(signed.signature().y_parity());
+        assert_eq!(
+            "f85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b8001a014f9a17afa048b99205f825ccc89b9ac68ae9c594beb7c1fabc20941f9d7002ca0682690ac3dbab8a1aa1b73e4044dd8b3b5e427e6c3b4532ad3718ed3938beef2",
+            hex::encode(signed.to_rlp())
+        );
+
+        // Valid on every chain
+        let authorization = Authorization::new(0, delegate, 12);
+        assert_eq!(
+            "8484c82e49d8137471a8483fad23627faaa86b8ab18d03de9491f6174251c30f",
+            hex::encode(authorization.signing_hash())
+        );
+        assert_eq!(
+            "f85a809463c0c19a282a1b52b07dd5a65b58948a07dae32b0c80a00b0a96126f1bc3e56d3cc0a7436b9be8fe89948288b5e6f521fa38a92d2dc6fea046ce7a55b0ead6bb2abca47ff990270c6d11f8e633bdbccc1d334555e53ce26c",
+            hex::encode(private_key.sign_authorization(&authorization).to_rlp())
+        );
+    }
+}
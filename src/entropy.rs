@@ -0,0 +1,155 @@
+use crate::utils::keccak256;
+use crate::{Error, PrivateKey, Result};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand_core::CryptoRngCore;
+use zeroize::Zeroizing;
+
+/// Minimum amount of user supplied entropy in bits
+pub const MIN_USER_ENTROPY_BITS: usize = 128;
+
+/// Entropy supplied by the user, for example from physical dice rolls or coin flips.
+///
+/// The entropy is never used on its own, it's always mixed with the operating system's random
+/// number generator. So even if the dice are biased the private key is at least as strong as a
+/// key from `PrivateKey::random()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserEntropy<'a> {
+    /// Results of six-sided dice rolls as digits `1` to `6`. Each roll adds ~2.58 bits.
+    DiceRolls(&'a str),
+    /// Results of coin flips as `0`/`1` or `H`/`T`. Each flip adds 1 bit.
+    CoinFlips(&'a str),
+}
+
+impl UserEntropy<'_> {
+    /// Returns the amount of entropy in bits that the input contains. Whitespace is ignored.
+    pub fn bits(&self) -> Result<usize> {
+        // The first byte of the normalized input is the tag, not a symbol
+        let symbols = self.normalize()?.len() - 1;
+        Ok(symbols * self.bits_per_symbol_millis() / 1000)
+    }
+
+    /// Entropy per symbol multiplied by 1000, so it can be counted without floats
+    fn bits_per_symbol_millis(&self) -> usize {
+        match self {
+            // log2(6) = 2.585
+            UserEntropy::DiceRolls(_) => 2585,
+            UserEntropy::CoinFlips(_) => 1000,
+        }
+    }
+
+    /// Validates the input and returns it without whitespace with every symbol normalized. The
+    /// input is prefixed with a tag of the entropy kind, so dice rolls and coin flips with the
+    /// same digits don't produce the same key.
+    fn normalize(&self) -> Result<Zeroizing<Vec<u8>>> {
+        let (input, tag) = match self {
+            UserEntropy::DiceRolls(input) => (input, b'd'),
+            UserEntropy::CoinFlips(input) => (input, b'c'),
+        };
+        let mut normalized = Zeroizing::new(vec![tag]);
+        for c in input.chars().filter(|c| !c.is_whitespace()) {
+            let symbol = match (self, c.to_ascii_uppercase()) {
+                (UserEntropy::DiceRolls(_), '1'..='6') => c as u8,
+                (UserEntropy::CoinFlips(_), '0' | 'T') => b'0',
+                (UserEntropy::CoinFlips(_), '1' | 'H') => b'1',
+                _ => return Err(Error::InvalidEntropy(c)),
+            };
+            normalized.push(symbol);
+        }
+        Ok(normalized)
+    }
+}
+
+impl PrivateKey {
+    /// Generates a new private key by mixing the operating system's random number generator with
+    /// entropy supplied by the user. Fails if the user entropy has less than
+    /// `MIN_USER_ENTROPY_BITS` bits.
+    /// ```
+    /// use ethereum_private_key_to_address::{PrivateKey, UserEntropy};
+    ///
+    /// let rolls = "3615 2243 6152 4411 3626 5152 3341 6225 1436 5123 2644 1536 3251";
+    /// let private_key = PrivateKey::from_user_entropy(&UserEntropy::DiceRolls(rolls)).unwrap();
+    ///
+    /// println!("{}", private_key.address());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_user_entropy(entropy: &UserEntropy) -> Result<Self> {
+        Self::from_user_entropy_with(&mut rand_core::OsRng, entropy)
+    }
+
+    /// Same as `from_user_entropy()` but mixes the user entropy with the given random number
+    /// generator instead of the operating system's one
+    pub fn from_user_entropy_with<R: CryptoRngCore + ?Sized>(
+        rng: &mut R,
+        entropy: &UserEntropy,
+    ) -> Result<Self> {
+        let bits = entropy.bits()?;
+        if bits < MIN_USER_ENTROPY_BITS {
+            return Err(Error::InsufficientEntropy {
+                bits,
+                required: MIN_USER_ENTROPY_BITS,
+            });
+        }
+        let normalized = entropy.normalize()?;
+
+        let mut input = Zeroizing::new(vec![0u8; 32]);
+        input.extend_from_slice(&normalized);
+        loop {
+            rng.fill_bytes(&mut input[..32]);
+            let mixed = Zeroizing::new(keccak256(&input));
+            if let Ok(private_key) = Self::from_slice(&mixed[..]) {
+                return Ok(private_key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{Error, PrivateKey, UserEntropy, MIN_USER_ENTROPY_BITS};
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    const DICE_ROLLS: &str = "3615 2243 6152 4411 3626 5152 3341 6225 1436 5123 2644 1536 3251";
+
+    #[test]
+    fn test_entropy_bits() {
+        assert_eq!(UserEntropy::DiceRolls("123456").bits().unwrap(), 15);
+        assert_eq!(UserEntropy::CoinFlips("HT HT 01").bits().unwrap(), 6);
+        assert_eq!(
+            UserEntropy::DiceRolls("1237").bits(),
+            Err(Error::InvalidEntropy('7'))
+        );
+        assert_eq!(
+            UserEntropy::CoinFlips("HTX").bits(),
+            Err(Error::InvalidEntropy('X'))
+        );
+    }
+
+    #[test]
+    fn test_insufficient_entropy() {
+        assert_eq!(
+            PrivateKey::from_user_entropy(&UserEntropy::DiceRolls("123456")),
+            Err(Error::InsufficientEntropy {
+                bits: 15,
+                required: MIN_USER_ENTROPY_BITS
+            })
+        );
+    }
+
+    #[test]
+    fn test_entropy_is_mixed() {
+        let entropy = UserEntropy::DiceRolls(DICE_ROLLS);
+        let first =
+            PrivateKey::from_user_entropy_with(&mut ChaCha20Rng::seed_from_u64(1), &entropy)
+                .unwrap();
+        let same = PrivateKey::from_user_entropy_with(&mut ChaCha20Rng::seed_from_u64(1), &entropy)
+            .unwrap();
+        let different_rng =
+            PrivateKey::from_user_entropy_with(&mut ChaCha20Rng::seed_from_u64(2), &entropy)
+                .unwrap();
+        assert_eq!(first, same);
+        assert_ne!(first, different_rng);
+        assert_ne!(first, PrivateKey::from_user_entropy(&entropy).unwrap());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::utils::{address_word, uint256};
+use crate::{Address, Error, Result};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+/// Selector of `createAccount(address,uint256)`
+const CREATE_ACCOUNT_SELECTOR: [u8; 4] = [0x5f, 0xbf, 0xb9, 0xcf];
+
+/// Selector of `initialize(address)`
+const INITIALIZE_SELECTOR: [u8; 4] = [0xc4, 0xd6, 0x6d, 0xe8];
+
+/// `initCode` of the ERC-4337 user operation, the factory address followed by the calldata the
+/// EntryPoint sends to the factory to deploy the account. In EntryPoint v0.7 the two parts are
+/// the separate `factory` and `factoryData` fields.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, InitCode};
+///
+/// let factory = Address::from_str("0x9406cc6185a346906296840746125a0e44976454").unwrap();
+/// let init_code = InitCode::new(factory, vec![0x5f, 0xbf, 0xb9, 0xcf]);
+///
+/// assert_eq!(InitCode::from_bytes(&init_code.to_bytes()).unwrap(), init_code);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InitCode {
+    /// Factory deploying the account
+    factory: Address,
+    /// Calldata of the factory call
+    factory_data: Vec<u8>,
+}
+
+impl InitCode {
+    /// Creates the init code calling the factory with the data
+    pub fn new(factory: Address, factory_data: Vec<u8>) -> Self {
+        Self {
+            factory,
+            factory_data,
+        }
+    }
+
+    /// Splits the `initCode` into the factory and its calldata, fails if it's shorter than an
+    /// address. Empty `initCode` means the account is already deployed, it has no factory.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 20 {
+            return Err(Error::InvalidLength {
+                expected: "at least 20",
+                actual: bytes.len(),
+            });
+        }
+        Ok(Self::new(
+            Address::from_slice(&bytes[..20])?,
+            bytes[20..].to_vec(),
+        ))
+    }
+
+    /// Returns the factory
+    pub fn factory(&self) -> &Address {
+        &self.factory
+    }
+
+    /// Returns the calldata of the factory call
+    pub fn factory_data(&self) -> &[u8] {
+        &self.factory_data
+    }
+
+    /// Returns the `initCode`, the factory followed by the calldata
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.factory.to_vec();
+        bytes.extend_from_slice(&self.factory_data);
+        bytes
+    }
+}
+
+/// `SimpleAccountFactory` of the ERC-4337 reference implementation, and the factories derived from
+/// it which deploy an `ERC1967Proxy` of the account implementation by `CREATE2`. Computes the
+/// `sender` the EntryPoint `getSenderAddress()` returns for the init code, without a node.
+///
+/// The proxy creation code depends on the compiler and OpenZeppelin versions the factory was
+/// built with, get it from the verified source or the factory deployment.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, SimpleAccountFactory};
+///
+/// # fn run(proxy_creation_code: Vec<u8>) {
+/// let factory = Address::from_str("0x9406cc6185a346906296840746125a0e44976454").unwrap();
+/// let implementation = Address::from_str("0x8abb13360b87be5eeb1b98647a016add927a136c").unwrap();
+/// let owner = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+///
+/// let factory = SimpleAccountFactory::new(factory, implementation, proxy_creation_code);
+/// let init_code = factory.init_code(&owner, 0);
+/// let sender = factory.sender_address(&init_code).unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SimpleAccountFactory {
+    /// Address of the factory
+    address: Address,
+    /// Account implementation behind the proxies, `accountImplementation()` of the factory
+    account_implementation: Address,
+    /// Creation code of `ERC1967Proxy` without the constructor arguments
+    proxy_creation_code: Vec<u8>,
+}
+
+impl SimpleAccountFactory {
+    /// Creates the factory at the address deploying the proxies of the implementation
+    pub fn new(
+        address: Address,
+        account_implementation: Address,
+        proxy_creation_code: Vec<u8>,
+    ) -> Self {
+        Self {
+            address,
+            account_implementation,
+            proxy_creation_code,
+        }
+    }
+
+    /// Returns the address of the factory
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    /// Returns the account implementation
+    pub fn account_implementation(&self) -> &Address {
+        &self.account_implementation
+    }
+
+    /// Returns the init code deploying the account of the owner with the salt by
+    /// `createAccount(owner, salt)`
+    pub fn init_code(&self, owner: &Address, salt: u128) -> InitCode {
+        let mut data = CREATE_ACCOUNT_SELECTOR.to_vec();
+        data.extend_from_slice(&address_word(owner));
+        data.extend_from_slice(&uint256(salt));
+        InitCode::new(self.address, data)
+    }
+
+    /// Calculates the address of the account of the owner with the salt, the same as
+    /// `getAddress(owner, salt)` of the factory
+    pub fn account_address(&self, owner: &Address, salt: u128) -> Address {
+        // abi.encode(implementation, abi.encodeCall(SimpleAccount.initialize, (owner)))
+        let mut init_code = self.proxy_creation_code.clone();
+        init_code.extend_from_slice(&address_word(&self.account_implementation));
+        init_code.extend_from_slice(&uint256(0x40));
+        init_code.extend_from_slice(&uint256(36));
+        init_code.extend_from_slice(&INITIALIZE_SELECTOR);
+        init_code.extend_from_slice(&address_word(owner));
+        init_code.extend_from_slice(&[0u8; 28]);
+        Address::create2_from_code(&self.address, &uint256(salt), &init_code)
+    }
+
+    /// Calculates the `sender` of the user operation with the init code, fails if the init code
+    /// doesn't call `createAccount()` of this factory
+    pub fn sender_address(&self, init_code: &InitCode) -> Result<Address> {
+        if init_code.factory() != &self.address {
+            return Err(Error::InvalidInitCode(format!(
+                "calls factory {}, not {}",
+                init_code.factory(),
+                self.address
+            )));
+        }
+
+        let data = init_code.factory_data();
+        if data.len() != 68 || data[..4] != CREATE_ACCOUNT_SELECTOR {
+            return Err(Error::InvalidInitCode(
+                "doesn't call createAccount(address,uint256)".to_string(),
+            ));
+        }
+        // The owner is an address and the salt has to fit into u128
+        if data[4..16]
+            .iter()
+            .chain(&data[36..52])
+            .any(|byte| *byte != 0)
+        {
+            return Err(Error::InvalidInitCode(
+                "createAccount() arguments are out of range".to_string(),
+            ));
+        }
+
+        let owner = Address::from_slice(&data[16..36])?;
+        let mut salt = [0u8; 16];
+        salt.copy_from_slice(&data[52..]);
+        Ok(self.account_address(&owner, u128::from_be_bytes(salt)))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_sender_address() {
+        let factory = Address::from_str("0x9406cc6185a346906296840746125a0e44976454").unwrap();
+        let implementation =
+            Address::from_str("0x8abb13360b87be5eeb1b98647a016add927a136c").unwrap();
+        let owner = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+        let factory = SimpleAccountFactory::new(factory, implementation, vec![0x60, 0x80]);
+
+        let init_code = factory.init_code(&owner, 5);
+        assert_eq!(
+            concat!(
+                "9406cc6185a346906296840746125a0e44976454",
+                "5fbfb9cf",
+                "000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "0000000000000000000000000000000000000000000000000000000000000005",
+            ),
+            hex::encode(init_code.to_bytes())
+        );
+        assert_eq!(
+            init_code,
+            InitCode::from_bytes(&init_code.to_bytes()).unwrap()
+        );
+
+        let proxy_init_code = hex::decode(concat!(
+            "6080",
+            "0000000000000000000000008abb13360b87be5eeb1b98647a016add927a136c",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000024",
+            "c4d66de8000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cf",
+            "ffb9226600000000000000000000000000000000000000000000000000000000",
+        ))
+        .unwrap();
+        let expected = Address::create2_from_code(factory.address(), &uint256(5), &proxy_init_code);
+        assert_eq!(expected, factory.account_address(&owner, 5));
+        assert_eq!(expected, factory.sender_address(&init_code).unwrap());
+
+        let other = InitCode::new(owner, init_code.factory_data().to_vec());
+        assert!(factory.sender_address(&other).is_err());
+        assert!(InitCode::from_bytes(&[0u8; 19]).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use alloc::string::String;
+
+/// Errors returned by this library
+#[derive(Debug, thiserror::Error, PartialEq, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error), uniffi(flat_error))]
+pub enum Error {
+    /// The input is not valid hex
+    #[error("invalid hex: {0}")]
+    InvalidHex(hex::FromHexError),
+    /// The input has wrong length
+    #[error("invalid length: expected {expected} bytes, got {actual}")]
+    InvalidLength {
+        /// Accepted length(s) of the input
+        expected: &'static str,
+        /// Length of the given input
+        actual: usize,
+    },
+    /// The private key is greater than or equal to the secp256k1 curve order
+    #[error("private key is out of range of the secp256k1 curve order")]
+    OutOfRange,
+    /// The private key is zero
+    #[error("private key can't be zero")]
+    ZeroKey,
+    /// The public key is not a valid secp256k1 point
+    #[error("invalid public key")]
+    InvalidPublicKey,
+    /// The mixed-case address doesn't match its EIP-55 checksum
+    #[error("address has invalid EIP-55 checksum")]
+    InvalidChecksum,
+    /// The user supplied entropy contains a character that isn't a valid symbol
+    #[error("invalid character {0:?} in the user entropy")]
+    InvalidEntropy(char),
+    /// The user supplied entropy doesn't contain enough bits
+    #[error("user entropy has {bits} bits, at least {required} bits are required")]
+    InsufficientEntropy {
+        /// Bits of entropy in the input
+        bits: usize,
+        /// Minimum required bits
+        required: usize,
+    },
+    /// The mnemonic phrase is not valid BIP-39 mnemonic
+    #[error("invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+    /// The mnemonic can't have the given number of words
+    #[error("invalid word count {0}, mnemonic has to have 12, 15, 18, 21 or 24 words")]
+    InvalidWordCount(usize),
+    /// The derivation path is not valid BIP-32 path
+    #[error("invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+    /// The extended key can't be parsed
+    #[error("invalid extended key: {0}")]
+    InvalidExtendedKey(String),
+    /// The seed produced invalid master key
+    #[error("seed produced invalid master key")]
+    InvalidSeed,
+    /// Derivation produced invalid child key at the given index
+    #[error("derivation produced invalid child key at index {0}")]
+    InvalidChildKey(u32),
+    /// The SLIP-39 sharing scheme or the secret to split is not valid
+    #[error("invalid sharing scheme: {0}")]
+    InvalidSharingScheme(String),
+    /// The SLIP-39 shares can't be decoded or combined
+    #[error("invalid share: {0}")]
+    InvalidShare(String),
+    /// The keystore JSON can't be parsed or uses unsupported parameters
+    #[error("invalid keystore: {0}")]
+    InvalidKeystore(String),
+    /// The MAC of the keystore doesn't match, the password is wrong or the keystore is corrupted
+    #[error("invalid password or corrupted keystore")]
+    InvalidPassword,
+    /// There is no keystore of the address in the keystore directory
+    #[error("no keystore found for address {0}")]
+    KeystoreNotFound(crate::Address),
+    /// Reading a file or directory failed
+    #[error("I/O error: {0}")]
+    Io(String),
+    /// The format of the wallet input can't be detected
+    #[error("unknown wallet format, expected private key, mnemonic, xprv or keystore JSON")]
+    UnknownWalletFormat,
+    /// The wallet is encrypted but no password was given
+    #[error("password is required to decrypt the wallet")]
+    PasswordRequired,
+    /// The signature has invalid `v` value
+    #[error("invalid signature recovery id {0}")]
+    InvalidRecoveryId(u64),
+    /// The EIP-712 typed data JSON can't be parsed or encoded
+    #[error("invalid typed data: {0}")]
+    InvalidTypedData(String),
+    /// The RLP data is malformed or not in the canonical form
+    #[error("invalid RLP: {0}")]
+    InvalidRlp(String),
+    /// The Sign-In with Ethereum message isn't in the EIP-4361 format
+    #[error("invalid SIWE message: {0}")]
+    InvalidSiweMessage(String),
+    /// The signature doesn't belong to the expected public key
+    #[error("signature doesn't match the public key")]
+    InvalidSignature,
+    /// The s value of the signature isn't below the secp256k1 curve order
+    #[error("signature s is out of range of the secp256k1 curve order")]
+    SignatureOutOfRange,
+    /// The Uniform Resource or its CBOR payload is malformed
+    #[error("invalid UR: {0}")]
+    InvalidUr(String),
+    /// The ERC-4337 init code doesn't call the expected factory
+    #[error("invalid init code: {0}")]
+    InvalidInitCode(String),
+    /// The vanity address pattern isn't valid hex or can't match an address
+    #[error("invalid address pattern: {0}")]
+    InvalidPattern(String),
+    /// The platform keychain failed or holds an unexpected key
+    #[error("keychain error: {0}")]
+    Keychain(String),
+    /// The OpenCL device failed or there's none
+    #[error("GPU error: {0}")]
+    Gpu(String),
+    /// Error returned by the signer backend, e.g. a KMS, an HSM or a hardware wallet
+    #[error("signer error: {0}")]
+    Signer(String),
+    /// Invalid SEC1 or PKCS#8 encoding of the private key
+    #[error("invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    /// Error returned by the secp256k1 library
+    #[cfg(not(feature = "k256"))]
+    #[error("secp256k1 error: {0}")]
+    Secp256k1(#[from] secp256k1::Error),
+    /// Error returned by the k256 library, the message of its opaque error
+    #[cfg(feature = "k256")]
+    #[error("secp256k1 error: {0}")]
+    Secp256k1(String),
+}
+
+/// Converted by hand instead of `#[from]`, which would make the hex error the source. The hex
+/// error only implements `Error` with `std`.
+impl From<hex::FromHexError> for Error {
+    fn from(error: hex::FromHexError) -> Self {
+        Self::InvalidHex(error)
+    }
+}
+
+/// Result type used across this library
+pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -0,0 +1,54 @@
+use crate::PrivateKey;
+use ethers_signers::LocalWallet;
+use k256::ecdsa::SigningKey;
+
+impl From<&PrivateKey> for SigningKey {
+    fn from(value: &PrivateKey) -> Self {
+        Self::from_slice(&value.secret_bytes()[..]).expect("private key is a valid signing key")
+    }
+}
+
+impl From<&SigningKey> for PrivateKey {
+    fn from(value: &SigningKey) -> Self {
+        Self::from_slice(&value.to_bytes()).expect("signing key is a valid private key")
+    }
+}
+
+/// Creates the ethers wallet of the key with the chain id 1, set another one with
+/// `Signer::with_chain_id()`
+impl From<&PrivateKey> for LocalWallet {
+    fn from(value: &PrivateKey) -> Self {
+        Self::from(SigningKey::from(value))
+    }
+}
+
+impl From<&LocalWallet> for PrivateKey {
+    fn from(value: &LocalWallet) -> Self {
+        Self::from(value.signer())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use core::str::FromStr;
+    use ethers_signers::Signer;
+
+    #[test]
+    fn test_ethers_conversions() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+
+        let signing_key = SigningKey::from(&private_key);
+        assert_eq!(private_key, PrivateKey::from(&signing_key));
+
+        let wallet = LocalWallet::from(&private_key);
+        assert_eq!(
+            private_key.to_address().as_bytes(),
+            wallet.address().as_fixed_bytes()
+        );
+        assert_eq!(private_key, PrivateKey::from(&wallet));
+    }
+}
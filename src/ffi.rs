@@ -0,0 +1,214 @@
+use crate::PrivateKey;
+use std::ffi::c_char;
+use std::{ptr, slice};
+
+/// The call succeeded
+pub const ETH_OK: i32 = 0;
+
+/// A pointer argument is null
+pub const ETH_ERROR_NULL_POINTER: i32 = -1;
+
+/// The private key is zero or not below the curve order
+pub const ETH_ERROR_INVALID_PRIVATE_KEY: i32 = -2;
+
+/// Derives the 20 byte address of the 32 byte private key into `address_out`
+///
+/// # Safety
+///
+/// `private_key` must point to 32 readable bytes and `address_out` to 20 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn eth_private_key_to_address(
+    private_key: *const u8,
+    address_out: *mut u8,
+) -> i32 {
+    let private_key = match read_private_key(private_key) {
+        Ok(private_key) => private_key,
+        Err(code) => return code,
+    };
+    if address_out.is_null() {
+        return ETH_ERROR_NULL_POINTER;
+    }
+    ptr::copy_nonoverlapping(
+        private_key.to_address().as_bytes().as_ptr(),
+        address_out,
+        20,
+    );
+    ETH_OK
+}
+
+/// Writes the EIP-55 checksummed address of the 32 byte private key into `address_out` as a NUL
+/// terminated string, `0x` followed by 40 hex characters
+///
+/// # Safety
+///
+/// `private_key` must point to 32 readable bytes and `address_out` to 43 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn eth_private_key_to_checksum_address(
+    private_key: *const u8,
+    address_out: *mut c_char,
+) -> i32 {
+    let private_key = match read_private_key(private_key) {
+        Ok(private_key) => private_key,
+        Err(code) => return code,
+    };
+    if address_out.is_null() {
+        return ETH_ERROR_NULL_POINTER;
+    }
+    let checksum = private_key.to_address().to_checksum();
+    ptr::copy_nonoverlapping(checksum.as_ptr().cast(), address_out, checksum.len());
+    *address_out.add(checksum.len()) = 0;
+    ETH_OK
+}
+
+/// Derives the 64 byte uncompressed public key of the 32 byte private key into
+/// `public_key_out`, without the 0x04 prefix
+///
+/// # Safety
+///
+/// `private_key` must point to 32 readable bytes and `public_key_out` to 64 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn eth_private_key_to_public_key(
+    private_key: *const u8,
+    public_key_out: *mut u8,
+) -> i32 {
+    let private_key = match read_private_key(private_key) {
+        Ok(private_key) => private_key,
+        Err(code) => return code,
+    };
+    if public_key_out.is_null() {
+        return ETH_ERROR_NULL_POINTER;
+    }
+    let public_key = private_key.public_key_slice();
+    ptr::copy_nonoverlapping(public_key[1..].as_ptr(), public_key_out, 64);
+    ETH_OK
+}
+
+/// Signs the message the same way as `personal_sign` and writes the 65 byte signature
+/// `r || s || v` into `signature_out`, where `v` is 27 or 28
+///
+/// # Safety
+///
+/// `private_key` must point to 32 readable bytes, `message` to `message_len` readable bytes
+/// (it can be null if `message_len` is zero) and `signature_out` to 65 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn eth_sign_message(
+    private_key: *const u8,
+    message: *const u8,
+    message_len: usize,
+    signature_out: *mut u8,
+) -> i32 {
+    let private_key = match read_private_key(private_key) {
+        Ok(private_key) => private_key,
+        Err(code) => return code,
+    };
+    if signature_out.is_null() || (message.is_null() && message_len > 0) {
+        return ETH_ERROR_NULL_POINTER;
+    }
+    let message = if message_len == 0 {
+        &[][..]
+    } else {
+        slice::from_raw_parts(message, message_len)
+    };
+    let signature = private_key.sign_message(message).to_bytes();
+    ptr::copy_nonoverlapping(signature.as_ptr(), signature_out, 65);
+    ETH_OK
+}
+
+/// Reads the 32 byte private key, returns the error code if it's null or invalid
+unsafe fn read_private_key(private_key: *const u8) -> Result<PrivateKey, i32> {
+    if private_key.is_null() {
+        return Err(ETH_ERROR_NULL_POINTER);
+    }
+    PrivateKey::from_slice(slice::from_raw_parts(private_key, 32))
+        .map_err(|_| ETH_ERROR_INVALID_PRIVATE_KEY)
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::{verify, Address, Signature};
+    use std::ffi::CStr;
+    use std::str::FromStr;
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn test_ffi() {
+        let private_key: [u8; 32] = hex::decode(PRIVATE_KEY).unwrap().try_into().unwrap();
+        let address = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+
+        let mut address_out = [0u8; 20];
+        let code =
+            unsafe { eth_private_key_to_address(private_key.as_ptr(), address_out.as_mut_ptr()) };
+        assert_eq!(ETH_OK, code);
+        assert_eq!(address, Address::from(address_out));
+
+        let mut checksum_out: [c_char; 43] = [1; 43];
+        let code = unsafe {
+            eth_private_key_to_checksum_address(private_key.as_ptr(), checksum_out.as_mut_ptr())
+        };
+        assert_eq!(ETH_OK, code);
+        let checksum = unsafe { CStr::from_ptr(checksum_out.as_ptr()) };
+        assert_eq!(address.to_checksum(), checksum.to_str().unwrap());
+
+        let mut public_key_out = [0u8; 64];
+        let code = unsafe {
+            eth_private_key_to_public_key(private_key.as_ptr(), public_key_out.as_mut_ptr())
+        };
+        assert_eq!(ETH_OK, code);
+        assert_eq!(
+            PrivateKey::from_str(PRIVATE_KEY).unwrap().public_key(),
+            hex::encode(public_key_out)
+        );
+
+        let mut signature_out = [0u8; 65];
+        let message = b"hello world";
+        let code = unsafe {
+            eth_sign_message(
+                private_key.as_ptr(),
+                message.as_ptr(),
+                message.len(),
+                signature_out.as_mut_ptr(),
+            )
+        };
+        assert_eq!(ETH_OK, code);
+        let signature = Signature::from_slice(&signature_out).unwrap();
+        assert!(verify(message, &signature, &address));
+
+        let code = unsafe {
+            eth_sign_message(
+                private_key.as_ptr(),
+                ptr::null(),
+                0,
+                signature_out.as_mut_ptr(),
+            )
+        };
+        assert_eq!(ETH_OK, code);
+    }
+
+    #[test]
+    fn test_ffi_errors() {
+        let mut address_out = [0u8; 20];
+        let code = unsafe { eth_private_key_to_address(ptr::null(), address_out.as_mut_ptr()) };
+        assert_eq!(ETH_ERROR_NULL_POINTER, code);
+
+        let zero = [0u8; 32];
+        let code = unsafe { eth_private_key_to_address(zero.as_ptr(), address_out.as_mut_ptr()) };
+        assert_eq!(ETH_ERROR_INVALID_PRIVATE_KEY, code);
+
+        let private_key = [1u8; 32];
+        let code = unsafe { eth_private_key_to_address(private_key.as_ptr(), ptr::null_mut()) };
+        assert_eq!(ETH_ERROR_NULL_POINTER, code);
+
+        let mut signature_out = [0u8; 65];
+        let code = unsafe {
+            eth_sign_message(
+                private_key.as_ptr(),
+                ptr::null(),
+                1,
+                signature_out.as_mut_ptr(),
+            )
+        };
+        assert_eq!(ETH_ERROR_NULL_POINTER, code);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{Address, AsyncSigner, Error, PublicKey, Result, Signature};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt;
+
+/// Base URL of the Cloud KMS REST API
+const API_URL: &str = "https://cloudkms.googleapis.com/v1";
+
+/// Algorithm of the secp256k1 keys
+const ALGORITHM: &str = "EC_SIGN_SECP256K1_SHA256";
+
+/// Response of `cryptoKeyVersions.getPublicKey`
+#[derive(Deserialize)]
+struct PublicKeyResponse {
+    pem: String,
+    algorithm: String,
+}
+
+/// Response of `cryptoKeyVersions.asymmetricSign`
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+/// Signer backed by a Google Cloud KMS key version with the `EC_SIGN_SECP256K1_SHA256`
+/// algorithm. The private key never leaves KMS.
+///
+/// The requests are authorized by the OAuth 2.0 access token, e.g. from
+/// `gcloud auth print-access-token` or the metadata server. Tokens expire, replace it with
+/// `set_access_token()`. KMS returns only r and s in DER and doesn't enforce low-s, so the
+/// signature is normalized and the recovery id is found locally from the public key.
+/// ```no_run
+/// use ethereum_private_key_to_address::{AsyncSigner, GcpKmsSigner};
+///
+/// # async fn run(access_token: String) -> ethereum_private_key_to_address::Result<()> {
+/// let signer = GcpKmsSigner::new(
+///     "projects/my-project/locations/global/keyRings/ethereum/cryptoKeys/signer/cryptoKeyVersions/1",
+///     access_token,
+/// )
+/// .await?;
+/// let signature = signer.sign_message(b"hello world").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct GcpKmsSigner {
+    /// HTTP client
+    client: reqwest::Client,
+    /// Resource name of the key version
+    key_name: String,
+    /// OAuth 2.0 access token
+    access_token: String,
+    /// Public key of the key version
+    public_key: PublicKey,
+}
+
+impl GcpKmsSigner {
+    /// Creates the signer for the key version, the public key is fetched from KMS
+    pub async fn new(key_name: impl Into<String>, access_token: impl Into<String>) -> Result<Self> {
+        Self::with_client(reqwest::Client::new(), key_name, access_token).await
+    }
+
+    /// Creates the signer that sends the requests by the given HTTP client
+    pub async fn with_client(
+        client: reqwest::Client,
+        key_name: impl Into<String>,
+        access_token: impl Into<String>,
+    ) -> Result<Self> {
+        let key_name = key_name.into();
+        let access_token = access_token.into();
+        let response: PublicKeyResponse = client
+            .get(format!("{API_URL}/{key_name}/publicKey"))
+            .bearer_auth(&access_token)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(request_error)?
+            .json()
+            .await
+            .map_err(request_error)?;
+
+        if response.algorithm != ALGORITHM {
+            return Err(Error::Signer(format!(
+                "KMS key {key_name} has algorithm {}, expected {ALGORITHM}",
+                response.algorithm
+            )));
+        }
+        let public_key = PublicKey::from_spki_pem(&response.pem)?;
+
+        Ok(Self {
+            client,
+            key_name,
+            access_token,
+            public_key,
+        })
+    }
+
+    /// Replaces the expired access token
+    pub fn set_access_token(&mut self, access_token: impl Into<String>) {
+        self.access_token = access_token.into();
+    }
+
+    /// Returns the resource name of the key version
+    pub fn key_name(&self) -> &str {
+        &self.key_name
+    }
+
+    /// Returns the public key of the key version
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+}
+
+impl fmt::Debug for GcpKmsSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GcpKmsSigner")
+            .field("key_name", &self.key_name)
+            .field("address", &format_args!("{}", self.public_key.address()))
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl AsyncSigner for GcpKmsSigner {
+    fn address(&self) -> Address {
+        self.public_key.address()
+    }
+
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<Signature> {
+        // The digest is sent as is, KMS doesn't hash it again
+        let response: SignResponse = self
+            .client
+            .post(format!("{API_URL}/{}:asymmetricSign", self.key_name))
+            .bearer_auth(&self.access_token)
+            .json(&json!({ "digest": { "sha256": STANDARD.encode(digest) } }))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(request_error)?
+            .json()
+            .await
+            .map_err(request_error)?;
+
+        let der = STANDARD
+            .decode(response.signature)
+            .map_err(|_| Error::Signer("KMS returned invalid base64 signature".to_string()))?;
+        Signature::from_der(&der, false)?.recover_parity(digest, &self.public_key)
+    }
+}
+
+/// Converts the error of the KMS request
+fn request_error(error: reqwest::Error) -> Error {
+    Error::Signer(format!("Cloud KMS request failed: {error}"))
+}
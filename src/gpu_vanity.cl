@@ -0,0 +1,334 @@
+// Vanity search kernel. Every work item starts at BATCH points of the secp256k1 curve, adds the
+// generator point to all of them `steps` times and hashes every point into an address. Addresses
+// matching the mask are reported by their index, the host verifies them. BATCH is defined by the
+// host.
+//
+// Field elements are 8 little-endian 32-bit limbs modulo p = 2^256 - 2^32 - 977.
+
+__constant uint P[8] = {
+    0xFFFFFC2F, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF,
+    0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
+};
+
+// p - 2, the exponent of the inverse
+__constant uint P_MINUS_2[8] = {
+    0xFFFFFC2D, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF,
+    0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
+};
+
+__constant uint GX[8] = {
+    0x16F81798, 0x59F2815B, 0x2DCE28D9, 0x029BFCDB,
+    0xCE870B07, 0x55A06295, 0xF9DCBBAC, 0x79BE667E,
+};
+
+__constant uint GY[8] = {
+    0xFB10D4B8, 0x9C47D08F, 0xA6855419, 0xFD17B448,
+    0x0E1108A8, 0x5DA4FBFC, 0x26A3C465, 0x483ADA77,
+};
+
+__constant ulong KECCAK_RC[24] = {
+    0x0000000000000001UL, 0x0000000000008082UL, 0x800000000000808AUL, 0x8000000080008000UL,
+    0x000000000000808BUL, 0x0000000080000001UL, 0x8000000080008081UL, 0x8000000000008009UL,
+    0x000000000000008AUL, 0x0000000000000088UL, 0x0000000080008009UL, 0x000000008000000AUL,
+    0x000000008000808BUL, 0x800000000000008BUL, 0x8000000000008089UL, 0x8000000000008003UL,
+    0x8000000000008002UL, 0x8000000000000080UL, 0x000000000000800AUL, 0x800000008000000AUL,
+    0x8000000080008081UL, 0x8000000000008080UL, 0x0000000080000001UL, 0x8000000080008008UL,
+};
+
+__constant uint KECCAK_ROTATION[24] = {
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+};
+
+__constant uint KECCAK_PI[24] = {
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+};
+
+// Returns true if a >= p
+bool fe_gte_p(const uint *a) {
+    for (int i = 7; i >= 0; i--) {
+        if (a[i] != P[i]) {
+            return a[i] > P[i];
+        }
+    }
+    return true;
+}
+
+// Adds 2^256 - p to a, which subtracts p if the result overflows
+void fe_add_reduction(uint *a) {
+    ulong carry = (ulong)a[0] + 0x3D1;
+    a[0] = (uint)carry;
+    carry = (carry >> 32) + (ulong)a[1] + 1;
+    a[1] = (uint)carry;
+    carry >>= 32;
+    for (int i = 2; i < 8; i++) {
+        carry += a[i];
+        a[i] = (uint)carry;
+        carry >>= 32;
+    }
+}
+
+void fe_add(uint *r, const uint *a, const uint *b) {
+    ulong carry = 0;
+    for (int i = 0; i < 8; i++) {
+        carry += (ulong)a[i] + b[i];
+        r[i] = (uint)carry;
+        carry >>= 32;
+    }
+    if (carry || fe_gte_p(r)) {
+        fe_add_reduction(r);
+    }
+}
+
+void fe_sub(uint *r, const uint *a, const uint *b) {
+    long borrow = 0;
+    for (int i = 0; i < 8; i++) {
+        borrow += (long)a[i] - (long)b[i];
+        r[i] = (uint)borrow;
+        borrow >>= 32;
+    }
+    if (borrow) {
+        ulong carry = 0;
+        for (int i = 0; i < 8; i++) {
+            carry += (ulong)r[i] + P[i];
+            r[i] = (uint)carry;
+            carry >>= 32;
+        }
+    }
+}
+
+void fe_mul(uint *r, const uint *a, const uint *b) {
+    uint t[16];
+    for (int i = 0; i < 16; i++) {
+        t[i] = 0;
+    }
+    for (int i = 0; i < 8; i++) {
+        ulong carry = 0;
+        for (int j = 0; j < 8; j++) {
+            carry += (ulong)a[i] * b[j] + t[i + j];
+            t[i + j] = (uint)carry;
+            carry >>= 32;
+        }
+        t[i + 8] = (uint)carry;
+    }
+
+    // 2^256 = 2^32 + 977 modulo p
+    ulong carry = 0;
+    for (int i = 0; i < 8; i++) {
+        carry += (ulong)t[i] + (ulong)t[i + 8] * 0x3D1;
+        if (i > 0) {
+            carry += t[i + 7];
+        }
+        r[i] = (uint)carry;
+        carry >>= 32;
+    }
+    carry += t[15];
+
+    ulong high = carry;
+    carry = (ulong)r[0] + high * 0x3D1;
+    r[0] = (uint)carry;
+    carry = (carry >> 32) + (ulong)r[1] + high;
+    r[1] = (uint)carry;
+    carry >>= 32;
+    for (int i = 2; i < 8; i++) {
+        carry += r[i];
+        r[i] = (uint)carry;
+        carry >>= 32;
+    }
+    if (carry) {
+        fe_add_reduction(r);
+    }
+    if (fe_gte_p(r)) {
+        fe_add_reduction(r);
+    }
+}
+
+void fe_copy(uint *r, const uint *a) {
+    for (int i = 0; i < 8; i++) {
+        r[i] = a[i];
+    }
+}
+
+// Inverse by Fermat's little theorem, a^(p - 2)
+void fe_inv(uint *r, const uint *a) {
+    uint result[8] = {1, 0, 0, 0, 0, 0, 0, 0};
+    for (int i = 255; i >= 0; i--) {
+        fe_mul(result, result, result);
+        if ((P_MINUS_2[i / 32] >> (i % 32)) & 1) {
+            fe_mul(result, result, a);
+        }
+    }
+    fe_copy(r, result);
+}
+
+// Adds the generator point to the affine point (x, y) given the inverse of gx - x, the point can't
+// be the generator or its negation
+void point_add_g(uint *x, uint *y, const uint *inverse) {
+    uint gx[8], gy[8], dy[8], lambda[8], x3[8], t[8];
+    for (int i = 0; i < 8; i++) {
+        gx[i] = GX[i];
+        gy[i] = GY[i];
+    }
+
+    fe_sub(dy, gy, y);
+    fe_mul(lambda, dy, inverse);
+
+    fe_mul(x3, lambda, lambda);
+    fe_sub(x3, x3, x);
+    fe_sub(x3, x3, gx);
+
+    fe_sub(t, x, x3);
+    fe_mul(t, lambda, t);
+    fe_sub(y, t, y);
+    fe_copy(x, x3);
+}
+
+// Adds the generator point to each of the affine points. The differences gx - x are inverted
+// together by Montgomery's trick, one inversion and three multiplications per point.
+void points_add_g(uint x[BATCH][8], uint y[BATCH][8]) {
+    uint gx[8], dx[8], inverse[8], t[8], products[BATCH][8];
+    for (int i = 0; i < 8; i++) {
+        gx[i] = GX[i];
+    }
+
+    // products[k] is the product of the differences up to the point k
+    fe_sub(products[0], gx, x[0]);
+    for (int k = 1; k < BATCH; k++) {
+        fe_sub(dx, gx, x[k]);
+        fe_mul(products[k], products[k - 1], dx);
+    }
+
+    // inverse is the inverse of products[k], the inverse of the difference k is split off it
+    fe_inv(inverse, products[BATCH - 1]);
+    for (int k = BATCH - 1; k > 0; k--) {
+        fe_sub(dx, gx, x[k]);
+        fe_mul(t, inverse, products[k - 1]);
+        fe_mul(inverse, inverse, dx);
+        point_add_g(x[k], y[k], t);
+    }
+    point_add_g(x[0], y[0], inverse);
+}
+
+void keccak_f(ulong *state) {
+    ulong c[5];
+    for (int round = 0; round < 24; round++) {
+        for (int i = 0; i < 5; i++) {
+            c[i] = state[i] ^ state[i + 5] ^ state[i + 10] ^ state[i + 15] ^ state[i + 20];
+        }
+        for (int i = 0; i < 5; i++) {
+            ulong d = c[(i + 4) % 5] ^ rotate(c[(i + 1) % 5], (ulong)1);
+            for (int j = 0; j < 25; j += 5) {
+                state[j + i] ^= d;
+            }
+        }
+
+        ulong current = state[1];
+        for (int i = 0; i < 24; i++) {
+            uint j = KECCAK_PI[i];
+            ulong next = state[j];
+            state[j] = rotate(current, (ulong)KECCAK_ROTATION[i]);
+            current = next;
+        }
+
+        for (int j = 0; j < 25; j += 5) {
+            for (int i = 0; i < 5; i++) {
+                c[i] = state[j + i];
+            }
+            for (int i = 0; i < 5; i++) {
+                state[j + i] ^= ~c[(i + 1) % 5] & c[(i + 2) % 5];
+            }
+        }
+
+        state[0] ^= KECCAK_RC[round];
+    }
+}
+
+// Keccak256 of the 64 byte big-endian public key, the address is the last 20 bytes of the hash
+void address_of(const uint *x, const uint *y, uchar *address) {
+    ulong state[25];
+    for (int i = 0; i < 25; i++) {
+        state[i] = 0;
+    }
+    // Lane i holds bytes 8i..8i+8 of the message in little-endian
+    for (int i = 0; i < 8; i++) {
+        const uint *coordinate = i < 4 ? x : y;
+        int limb = 7 - 2 * (i % 4);
+        ulong high = coordinate[limb];
+        ulong low = coordinate[limb - 1];
+        ulong lane = 0;
+        for (int b = 0; b < 4; b++) {
+            lane |= ((high >> (24 - 8 * b)) & 0xFF) << (8 * b);
+            lane |= ((low >> (24 - 8 * b)) & 0xFF) << (8 * (b + 4));
+        }
+        state[i] = lane;
+    }
+    state[8] ^= 0x01;
+    state[16] ^= 0x8000000000000000UL;
+    keccak_f(state);
+
+    for (int i = 0; i < 20; i++) {
+        int byte = i + 12;
+        address[i] = (uchar)(state[byte / 8] >> (8 * (byte % 8)));
+    }
+}
+
+// Reads the start points of the work item, point k is the start of the walk `id * BATCH + k`
+void load_points(__global const uint *points, ulong id, uint x[BATCH][8], uint y[BATCH][8]) {
+    for (int k = 0; k < BATCH; k++) {
+        ulong offset = 16 * (id * BATCH + k);
+        for (int i = 0; i < 8; i++) {
+            x[k][i] = points[offset + i];
+            y[k][i] = points[offset + 8 + i];
+        }
+    }
+}
+
+__kernel void search(
+    __global const uint *points,
+    __constant const uchar *mask,
+    __constant const uchar *value,
+    uint steps,
+    __global uint *count,
+    __global ulong *results,
+    uint max_results
+) {
+    ulong id = get_global_id(0);
+    uint x[BATCH][8], y[BATCH][8];
+    load_points(points, id, x, y);
+
+    uchar address[20];
+    for (uint step = 0; step < steps; step++) {
+        for (int k = 0; k < BATCH; k++) {
+            address_of(x[k], y[k], address);
+            bool matches = true;
+            for (int i = 0; i < 20; i++) {
+                matches &= (address[i] & mask[i]) == value[i];
+            }
+            if (matches) {
+                uint index = atomic_inc(count);
+                if (index < max_results) {
+                    results[index] = (id * BATCH + k) * steps + step;
+                }
+            }
+        }
+        points_add_g(x, y);
+    }
+}
+
+// Writes the address of every point by its index, the host checks them against the CPU
+__kernel void addresses(__global const uint *points, uint steps, __global uchar *addresses) {
+    ulong id = get_global_id(0);
+    uint x[BATCH][8], y[BATCH][8];
+    load_points(points, id, x, y);
+
+    uchar address[20];
+    for (uint step = 0; step < steps; step++) {
+        for (int k = 0; k < BATCH; k++) {
+            address_of(x[k], y[k], address);
+            ulong index = (id * BATCH + k) * steps + step;
+            for (int i = 0; i < 20; i++) {
+                addresses[20 * index + i] = address[i];
+            }
+        }
+        points_add_g(x, y);
+    }
+}
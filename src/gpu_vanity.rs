@@ -0,0 +1,339 @@
+use crate::search::PROGRESS_INTERVAL;
+use crate::utils::uint256;
+use crate::{backend, Address, AddressPattern, Error, PrivateKey, Result, SearchProgress};
+use ocl::ProQue;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// OpenCL source of the search kernel
+const KERNEL: &str = include_str!("gpu_vanity.cl");
+
+/// Maximum number of candidates the kernel reports per launch
+const MAX_RESULTS: usize = 1024;
+
+/// Number of points each work item walks side by side, the kernel inverts their differences from
+/// the generator point together
+const BATCH: usize = 16;
+
+/// Number of work items per launch by default
+const DEFAULT_WORK_ITEMS: usize = 4096;
+
+/// Number of steps of each work item per launch by default
+const DEFAULT_STEPS: u32 = 64;
+
+/// Vanity search on the GPU over OpenCL. Each work item starts at 16 points derived on the CPU
+/// from a random key and walks the following keys by adding the generator point to all of them,
+/// hashing every public key into an address on the GPU. The additions of a step share one field
+/// inversion by Montgomery's trick. The GPU filters the addresses by the prefix, the suffix and the
+/// leading zero bytes, the CPU derives every candidate again and checks the whole pattern, so the
+/// contained hex, the checksum case and the regular expression are checked on the CPU only.
+///
+/// The found key is the random key plus the index of the candidate, which is as secure as any
+/// other random key as long as the random key stays secret.
+/// ```no_run
+/// use ethereum_private_key_to_address::{AddressPattern, GpuVanitySearch};
+///
+/// let pattern = AddressPattern::new().with_prefix("0xdeadbeef").unwrap();
+/// let (private_key, address) = GpuVanitySearch::new(pattern).search().unwrap();
+///
+/// assert_eq!(private_key.to_address(), address);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuVanitySearch {
+    /// Pattern the address has to match
+    pattern: AddressPattern,
+    /// Number of work items per launch
+    work_items: usize,
+    /// Number of keys each work item tries per launch
+    steps: u32,
+}
+
+impl GpuVanitySearch {
+    /// Creates the search for the pattern on the default OpenCL device
+    pub fn new(pattern: AddressPattern) -> Self {
+        Self {
+            pattern,
+            work_items: DEFAULT_WORK_ITEMS,
+            steps: DEFAULT_STEPS,
+        }
+    }
+
+    /// Sets the number of work items per launch. More work items keep large GPUs busy, but the
+    /// start points of all of them are derived on the CPU.
+    ///
+    /// # Panics
+    ///
+    /// If the number of work items is zero.
+    pub fn with_work_items(mut self, work_items: usize) -> Self {
+        assert!(work_items > 0, "number of work items can't be zero");
+        self.work_items = work_items;
+        self
+    }
+
+    /// Sets the number of steps of each work item per launch, each step tries 16 keys. More steps
+    /// spend less time on the CPU, but each launch takes longer to cancel.
+    ///
+    /// # Panics
+    ///
+    /// If the number of steps is zero.
+    pub fn with_steps(mut self, steps: u32) -> Self {
+        assert!(steps > 0, "number of steps can't be zero");
+        self.steps = steps;
+        self
+    }
+
+    /// Returns the pattern
+    pub fn pattern(&self) -> &AddressPattern {
+        &self.pattern
+    }
+
+    /// Returns the number of work items per launch
+    pub fn work_items(&self) -> usize {
+        self.work_items
+    }
+
+    /// Returns the number of steps of each work item per launch
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Searches until a matching key is found, returns the key and its address. Fails if there's
+    /// no OpenCL device or the pattern can't match any address.
+    pub fn search(&self) -> Result<(PrivateKey, Address)> {
+        let never = AtomicBool::new(false);
+        self.search_cancellable(&never, |_| {})
+            .map(|found| found.expect("search can't be cancelled"))
+    }
+
+    /// Searches until a matching key is found or the cancel flag is set, calling the callback with
+    /// the progress about twice a second. Returns `None` if the search was cancelled, the flag is
+    /// checked after every launch.
+    pub fn search_cancellable(
+        &self,
+        cancel: &AtomicBool,
+        mut progress: impl FnMut(SearchProgress),
+    ) -> Result<Option<(PrivateKey, Address)>> {
+        let (mask, value) = self.mask()?;
+
+        let pro_que = ProQue::builder()
+            .src(source())
+            .dims(self.work_items)
+            .build()
+            .map_err(gpu_error)?;
+        let points = pro_que
+            .buffer_builder::<u32>()
+            .len(16 * BATCH * self.work_items)
+            .build()
+            .map_err(gpu_error)?;
+        let mask = pro_que
+            .buffer_builder::<u8>()
+            .len(20)
+            .copy_host_slice(&mask)
+            .build()
+            .map_err(gpu_error)?;
+        let value = pro_que
+            .buffer_builder::<u8>()
+            .len(20)
+            .copy_host_slice(&value)
+            .build()
+            .map_err(gpu_error)?;
+        let count = pro_que
+            .buffer_builder::<u32>()
+            .len(1)
+            .build()
+            .map_err(gpu_error)?;
+        let results = pro_que
+            .buffer_builder::<u64>()
+            .len(MAX_RESULTS)
+            .build()
+            .map_err(gpu_error)?;
+        let kernel = pro_que
+            .kernel_builder("search")
+            .arg(&points)
+            .arg(&mask)
+            .arg(&value)
+            .arg(self.steps)
+            .arg(&count)
+            .arg(&results)
+            .arg(MAX_RESULTS as u32)
+            .build()
+            .map_err(gpu_error)?;
+
+        let start = Instant::now();
+        let mut reported = start;
+        let mut attempts = 0u64;
+        loop {
+            let base = PrivateKey::random();
+            points
+                .write(&self.start_points(&base)?[..])
+                .enq()
+                .map_err(gpu_error)?;
+            count.write(&[0u32][..]).enq().map_err(gpu_error)?;
+            // The kernel only accesses the buffers within the bounds given by its arguments
+            unsafe { kernel.enq().map_err(gpu_error)? };
+
+            let mut found = [0u32];
+            count.read(&mut found[..]).enq().map_err(gpu_error)?;
+            let mut indexes = vec![0u64; (found[0] as usize).min(MAX_RESULTS)];
+            if !indexes.is_empty() {
+                results.read(&mut indexes[..]).enq().map_err(gpu_error)?;
+            }
+            for index in indexes {
+                let private_key = PrivateKey {
+                    private_key: backend::add_tweak(&base.private_key, &uint256(index.into()))?,
+                };
+                let address = private_key.to_address();
+                if self.pattern.matches(&address) {
+                    return Ok(Some((private_key, address)));
+                }
+            }
+
+            attempts += (BATCH * self.work_items) as u64 * u64::from(self.steps);
+            if reported.elapsed() >= PROGRESS_INTERVAL {
+                progress(SearchProgress::new(attempts, start.elapsed()));
+                reported = Instant::now();
+            }
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Returns the limbs of the start points of all walks, walk `i` starts at the key
+    /// `base + i * steps`
+    fn start_points(&self, base: &PrivateKey) -> Result<Vec<u32>> {
+        let step = backend::public_key(&backend::secret_key_from_slice(&uint256(
+            self.steps.into(),
+        ))?);
+        let mut point = backend::public_key(&base.private_key);
+        let mut start_points = Vec::with_capacity(16 * BATCH * self.work_items);
+        for _ in 0..BATCH * self.work_items {
+            let serialized = backend::serialize_uncompressed(&point);
+            start_points.extend(limbs(&serialized[1..33]));
+            start_points.extend(limbs(&serialized[33..]));
+            point = backend::combine(&point, &step)?;
+        }
+        Ok(start_points)
+    }
+
+    /// Returns the mask and the value of the address bytes fixed by the pattern
+    fn mask(&self) -> Result<([u8; 20], [u8; 20])> {
+        let fixed = self.pattern.fixed_chars().ok_or_else(|| {
+            Error::InvalidPattern("conditions conflict, no address can match".to_string())
+        })?;
+
+        let mut mask = [0u8; 20];
+        let mut value = [0u8; 20];
+        for (position, c) in fixed.iter().enumerate() {
+            if let Some(nibble) = c.and_then(|c| c.to_digit(16)) {
+                let shift = if position % 2 == 0 { 4 } else { 0 };
+                mask[position / 2] |= 0x0f << shift;
+                value[position / 2] |= (nibble as u8) << shift;
+            }
+        }
+        Ok((mask, value))
+    }
+}
+
+/// Returns the kernel source with the batch size defined
+fn source() -> String {
+    format!("#define BATCH {BATCH}\n{KERNEL}")
+}
+
+/// Converts the 32 byte big-endian field element into little-endian 32-bit limbs of the kernel
+fn limbs(bytes: &[u8]) -> impl Iterator<Item = u32> + '_ {
+    bytes
+        .rchunks(4)
+        .map(|limb| u32::from_be_bytes(limb.try_into().expect("limb is 4 bytes long")))
+}
+
+/// Converts the OpenCL error
+fn gpu_error(error: ocl::Error) -> Error {
+    Error::Gpu(error.to_string())
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_mask() {
+        let pattern = AddressPattern::new()
+            .with_leading_zero_bytes(1)
+            .unwrap()
+            .with_prefix("00a")
+            .unwrap()
+            .with_suffix("bC1")
+            .unwrap();
+        let (mask, value) = GpuVanitySearch::new(pattern).mask().unwrap();
+
+        let mut expected_mask = [0u8; 20];
+        expected_mask[..2].copy_from_slice(&[0xff, 0xf0]);
+        expected_mask[18..].copy_from_slice(&[0x0f, 0xff]);
+        let mut expected_value = [0u8; 20];
+        expected_value[..2].copy_from_slice(&[0x00, 0xa0]);
+        expected_value[18..].copy_from_slice(&[0x0b, 0xc1]);
+        assert_eq!((expected_mask, expected_value), (mask, value));
+
+        let conflict = AddressPattern::new()
+            .with_leading_zero_bytes(1)
+            .unwrap()
+            .with_prefix("1")
+            .unwrap();
+        assert!(GpuVanitySearch::new(conflict).mask().is_err());
+    }
+
+    #[test]
+    #[ignore = "needs an OpenCL device, e.g. pocl"]
+    fn test_kernel_addresses() {
+        let search = GpuVanitySearch::new(AddressPattern::new())
+            .with_work_items(3)
+            .with_steps(5);
+        let base = PrivateKey::random();
+        let keys: Vec<PrivateKey> = (0..BATCH * 3 * 5)
+            .map(|index| PrivateKey {
+                private_key: backend::add_tweak(&base.private_key, &uint256(index as u128))
+                    .unwrap(),
+            })
+            .collect();
+
+        let pro_que = ProQue::builder().src(source()).dims(3).build().unwrap();
+        let points = pro_que
+            .buffer_builder::<u32>()
+            .len(16 * BATCH * 3)
+            .copy_host_slice(&search.start_points(&base).unwrap())
+            .build()
+            .unwrap();
+        let addresses = pro_que
+            .buffer_builder::<u8>()
+            .len(20 * keys.len())
+            .build()
+            .unwrap();
+        let kernel = pro_que
+            .kernel_builder("addresses")
+            .arg(&points)
+            .arg(5u32)
+            .arg(&addresses)
+            .build()
+            .unwrap();
+        unsafe { kernel.enq().unwrap() };
+        let mut gpu = vec![0u8; 20 * keys.len()];
+        addresses.read(&mut gpu[..]).enq().unwrap();
+
+        for (index, address) in crate::derive_addresses(&keys).iter().enumerate() {
+            assert_eq!(
+                address.as_bytes()[..],
+                gpu[20 * index..20 * (index + 1)],
+                "key {index}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_limbs() {
+        let bytes: Vec<u8> = (1..=32).collect();
+        let limbs: Vec<u32> = limbs(&bytes).collect();
+        assert_eq!(0x1d1e1f20, limbs[0]);
+        assert_eq!(0x01020304, limbs[7]);
+    }
+}
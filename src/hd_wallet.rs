@@ -0,0 +1,174 @@
+use crate::{
+    Address, DerivationPath, ExtendedPrivateKey, IntoDerivationPath, Mnemonic, PrivateKey, Result,
+};
+use std::str::FromStr;
+
+/// Hierarchical deterministic wallet that derives accounts from one mnemonic or xprv.
+///
+/// Accounts are derived as children of the base path, by default `m/44'/60'/0'/0`, so index `n`
+/// is the same account as the `n`-th account in MetaMask or Ledger Live.
+/// ```
+/// use ethereum_private_key_to_address::{HdWallet, Mnemonic};
+///
+/// let mnemonic = Mnemonic::parse("test test test test test test test test test test test junk").unwrap();
+/// let wallet = HdWallet::from_mnemonic(&mnemonic, "").unwrap();
+///
+/// for account in wallet.addresses(0..3) {
+///     let (index, address) = account.unwrap();
+///     println!("{index}: {address}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HdWallet {
+    /// Key at the base path, accounts are its children
+    base: ExtendedPrivateKey,
+    /// Base path relative to the root key
+    base_path: DerivationPath,
+}
+
+impl HdWallet {
+    /// Base path of Ethereum accounts used by most wallets
+    pub const DEFAULT_BASE_PATH: &'static str = "m/44'/60'/0'/0";
+
+    /// Creates the wallet from the mnemonic and passphrase with the default base path. Use empty
+    /// passphrase if the mnemonic doesn't have one.
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Result<Self> {
+        Self::from_mnemonic_with_base_path(mnemonic, passphrase, Self::DEFAULT_BASE_PATH)
+    }
+
+    /// Creates the wallet from the mnemonic and passphrase with a custom base path
+    pub fn from_mnemonic_with_base_path(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        base_path: impl IntoDerivationPath,
+    ) -> Result<Self> {
+        Self::from_root_with_base_path(mnemonic.to_master_key(passphrase)?, base_path)
+    }
+
+    /// Creates the wallet from the master `xprv...` key with the default base path
+    pub fn from_xprv(xprv: &str) -> Result<Self> {
+        Self::from_xprv_with_base_path(xprv, Self::DEFAULT_BASE_PATH)
+    }
+
+    /// Creates the wallet from the `xprv...` key with a custom base path. The path is relative to
+    /// the given key, so if the xprv is already the account key use `m` as the base path.
+    pub fn from_xprv_with_base_path(
+        xprv: &str,
+        base_path: impl IntoDerivationPath,
+    ) -> Result<Self> {
+        Self::from_root_with_base_path(ExtendedPrivateKey::from_str(xprv)?, base_path)
+    }
+
+    /// Creates the wallet from the extended key with a custom base path
+    pub fn from_root_with_base_path(
+        root: ExtendedPrivateKey,
+        base_path: impl IntoDerivationPath,
+    ) -> Result<Self> {
+        let base_path = base_path.into_derivation_path()?;
+        Ok(Self {
+            base: root.derive_path(&base_path)?,
+            base_path,
+        })
+    }
+
+    /// Returns the base path of the accounts
+    pub fn base_path(&self) -> &DerivationPath {
+        &self.base_path
+    }
+
+    /// Derives the private key of the account at the given index
+    pub fn key(&self, index: u32) -> Result<PrivateKey> {
+        Ok(self.base.derive_child(index)?.into_private_key())
+    }
+
+    /// Derives the address of the account at the given index
+    pub fn address(&self, index: u32) -> Result<Address> {
+        Ok(self.key(index)?.to_address())
+    }
+
+    /// Returns lazy iterator over the accounts at the given indexes, yielding the index, private
+    /// key and address. Nothing is derived until the iterator is advanced.
+    pub fn keys<'a, I>(
+        &'a self,
+        indexes: I,
+    ) -> impl Iterator<Item = Result<(u32, PrivateKey, Address)>> + 'a
+    where
+        I: IntoIterator<Item = u32>,
+        I::IntoIter: 'a,
+    {
+        indexes.into_iter().map(move |index| {
+            let private_key = self.key(index)?;
+            let address = private_key.to_address();
+            Ok((index, private_key, address))
+        })
+    }
+
+    /// Returns lazy iterator over the addresses at the given indexes, yielding the index and
+    /// address
+    pub fn addresses<'a, I>(
+        &'a self,
+        indexes: I,
+    ) -> impl Iterator<Item = Result<(u32, Address)>> + 'a
+    where
+        I: IntoIterator<Item = u32>,
+        I::IntoIter: 'a,
+    {
+        self.keys(indexes)
+            .map(|account| account.map(|(index, _, address)| (index, address)))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{DerivationPath, HdWallet, Mnemonic, PrivateKey};
+
+    const PHRASE: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_addresses() {
+        let wallet = HdWallet::from_mnemonic(&Mnemonic::parse(PHRASE).unwrap(), "").unwrap();
+        let addresses: Vec<_> = wallet
+            .addresses(0..3)
+            .map(|account| account.unwrap())
+            .map(|(index, address)| (index, address.to_string()))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string()),
+                (1, "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".to_string()),
+                (2, "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc".to_string()),
+            ],
+            addresses
+        );
+    }
+
+    #[test]
+    fn test_keys() {
+        let wallet = HdWallet::from_mnemonic(&Mnemonic::parse(PHRASE).unwrap(), "").unwrap();
+        let (index, private_key, address) = wallet.keys(5..).next().unwrap().unwrap();
+        assert_eq!(5, index);
+        assert_eq!(
+            private_key,
+            PrivateKey::from_mnemonic(PHRASE, DerivationPath::ethereum(5)).unwrap()
+        );
+        assert_eq!(private_key.to_address(), address);
+    }
+
+    #[test]
+    fn test_from_xprv() {
+        let master = Mnemonic::parse(PHRASE).unwrap().to_master_key("").unwrap();
+        let wallet = HdWallet::from_xprv(&master.to_xprv()).unwrap();
+        assert_eq!(
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            wallet.address(0).unwrap().to_string()
+        );
+
+        let account = master.derive_path(HdWallet::DEFAULT_BASE_PATH).unwrap();
+        let wallet = HdWallet::from_xprv_with_base_path(&account.to_xprv(), "m").unwrap();
+        assert_eq!(
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+            wallet.address(1).unwrap().to_string()
+        );
+        assert!(HdWallet::from_root_with_base_path(account, "44'").is_err());
+    }
+}
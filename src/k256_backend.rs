@@ -0,0 +1,171 @@
+use crate::{Error, Result};
+use alloc::string::ToString;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::subtle::ConstantTimeEq;
+use k256::elliptic_curve::PrimeField;
+use k256::{FieldBytes, NonZeroScalar, Scalar};
+
+/// Private key of the pure-Rust backend, compared in constant time
+#[derive(Clone, Copy)]
+pub(crate) struct SecretKey(NonZeroScalar);
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(<redacted>)")
+    }
+}
+
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        (*self.0).ct_eq(&*other.0).into()
+    }
+}
+
+impl Eq for SecretKey {}
+
+/// Public key of the pure-Rust backend. It's ordered and hashed by the compressed form, the same
+/// as the public key of the secp256k1 library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PublicKey(k256::PublicKey);
+
+impl Hash for PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        serialize_compressed(self).hash(state);
+    }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PublicKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        serialize_compressed(self).cmp(&serialize_compressed(other))
+    }
+}
+
+/// Returns the key with the value one, its public key is the generator point
+pub(crate) fn one() -> SecretKey {
+    SecretKey(NonZeroScalar::ONE)
+}
+
+/// Parses the 32 byte big-endian key, fails if it's zero or not below the curve order
+pub(crate) fn secret_key_from_slice(slice: &[u8]) -> Result<SecretKey> {
+    if slice.len() != 32 {
+        return Err(Error::OutOfRange);
+    }
+    Option::from(NonZeroScalar::from_repr(FieldBytes::clone_from_slice(
+        slice,
+    )))
+    .map(SecretKey)
+    .ok_or(Error::OutOfRange)
+}
+
+/// Returns the 32 byte big-endian key
+pub(crate) fn secret_bytes(secret_key: &SecretKey) -> [u8; 32] {
+    secret_key.0.to_repr().into()
+}
+
+/// Adds the 32 byte big-endian tweak to the key modulo the curve order, fails if the tweak isn't
+/// below the curve order or the sum is zero
+pub(crate) fn add_tweak(secret_key: &SecretKey, tweak: &[u8; 32]) -> Result<SecretKey> {
+    let tweak: Scalar =
+        Option::from(Scalar::from_repr((*tweak).into())).ok_or(Error::OutOfRange)?;
+    Option::from(NonZeroScalar::new(*secret_key.0 + tweak))
+        .map(SecretKey)
+        .ok_or(Error::OutOfRange)
+}
+
+/// Calculates the public key of the key
+pub(crate) fn public_key(secret_key: &SecretKey) -> PublicKey {
+    PublicKey(k256::PublicKey::from_secret_scalar(&secret_key.0))
+}
+
+/// Parses the 33 byte compressed or the 65 byte uncompressed public key
+pub(crate) fn public_key_from_slice(slice: &[u8]) -> Result<PublicKey> {
+    k256::PublicKey::from_sec1_bytes(slice)
+        .map(PublicKey)
+        .map_err(|_| Error::InvalidPublicKey)
+}
+
+/// Returns the 65 byte uncompressed public key with the 0x04 prefix
+pub(crate) fn serialize_uncompressed(public_key: &PublicKey) -> [u8; 65] {
+    public_key
+        .0
+        .to_encoded_point(false)
+        .as_bytes()
+        .try_into()
+        .expect("uncompressed public key is 65 bytes long")
+}
+
+/// Returns the 33 byte compressed public key
+pub(crate) fn serialize_compressed(public_key: &PublicKey) -> [u8; 33] {
+    public_key
+        .0
+        .to_encoded_point(true)
+        .as_bytes()
+        .try_into()
+        .expect("compressed public key is 33 bytes long")
+}
+
+/// Adds the points of the public keys, fails if the sum is the point at infinity
+pub(crate) fn combine(public_key: &PublicKey, other: &PublicKey) -> Result<PublicKey> {
+    let sum = public_key.0.to_projective() + other.0.to_projective();
+    k256::PublicKey::from_affine(sum.to_affine())
+        .map(PublicKey)
+        .map_err(|_| Error::InvalidPublicKey)
+}
+
+/// Signs the 32 byte digest with RFC 6979 nonce, returns the low-s `r || s` and the y parity
+pub(crate) fn sign_recoverable(secret_key: &SecretKey, digest: &[u8; 32]) -> ([u8; 64], bool) {
+    let (signature, recovery_id) = SigningKey::from(secret_key.0)
+        .sign_prehash_recoverable(digest)
+        .expect("32 byte digest can be signed");
+    let mut compact = [0u8; 64];
+    compact.copy_from_slice(&signature.to_bytes());
+    (compact, recovery_id.is_y_odd())
+}
+
+/// Recovers the public key that signed the 32 byte digest from `r || s` and the recovery id
+pub(crate) fn recover(
+    digest: &[u8; 32],
+    signature: &[u8; 64],
+    recovery_id: u8,
+) -> Result<PublicKey> {
+    let recovery_id =
+        RecoveryId::from_byte(recovery_id).ok_or(Error::InvalidRecoveryId(recovery_id.into()))?;
+    let signature = Signature::from_slice(signature).map_err(k256_error)?;
+    VerifyingKey::recover_from_prehash(digest, &signature, recovery_id)
+        .map(|verifying_key| PublicKey(verifying_key.into()))
+        .map_err(k256_error)
+}
+
+/// Verifies `r || s` of the 32 byte digest against the public key
+pub(crate) fn verify(digest: &[u8; 32], signature: &[u8; 64], public_key: &PublicKey) -> bool {
+    let Ok(signature) = Signature::from_slice(signature) else {
+        return false;
+    };
+    VerifyingKey::from(public_key.0)
+        .verify_prehash(digest, &signature)
+        .is_ok()
+}
+
+/// Parses the DER encoded signature into `r || s`
+pub(crate) fn signature_from_der(der: &[u8]) -> Result<[u8; 64]> {
+    let signature = Signature::from_der(der).map_err(k256_error)?;
+    let mut compact = [0u8; 64];
+    compact.copy_from_slice(&signature.to_bytes());
+    Ok(compact)
+}
+
+/// Converts the k256 error
+fn k256_error(error: k256::ecdsa::Error) -> Error {
+    Error::Secp256k1(error.to_string())
+}
@@ -0,0 +1,86 @@
+use alloc::vec::Vec;
+#[cfg(eth_key_simd)]
+use keccak::simd::u64x4;
+
+/// Number of lanes of the Keccak state
+const STATE_LANES: usize = 25;
+
+/// Hashes the 64 byte public keys without the `0x04` prefix by Keccak256, in the same order. A
+/// public key fits into one Keccak block, so it's absorbed directly into the state without the
+/// buffering of the streaming hasher. Built with `--cfg eth_key_simd`, four keys are permuted at
+/// once.
+pub(crate) fn keccak256_public_keys(public_keys: &[[u8; 64]]) -> Vec<[u8; 32]> {
+    let mut hashes = Vec::with_capacity(public_keys.len());
+
+    #[cfg(eth_key_simd)]
+    let public_keys = {
+        let mut chunks = public_keys.chunks_exact(4);
+        for chunk in &mut chunks {
+            let states: Vec<[u64; STATE_LANES]> = chunk.iter().map(absorb).collect();
+            let mut state = [u64x4::splat(0); STATE_LANES];
+            for (lane, simd_lane) in state.iter_mut().enumerate() {
+                *simd_lane = u64x4::from_array([
+                    states[0][lane],
+                    states[1][lane],
+                    states[2][lane],
+                    states[3][lane],
+                ]);
+            }
+            keccak::simd::f1600x4(&mut state);
+            for key in 0..4 {
+                hashes.push(squeeze(&core::array::from_fn(|lane| {
+                    state[lane].to_array()[key]
+                })));
+            }
+        }
+        chunks.remainder()
+    };
+
+    for public_key in public_keys {
+        let mut state = absorb(public_key);
+        keccak::f1600(&mut state);
+        hashes.push(squeeze(&state));
+    }
+    hashes
+}
+
+/// Returns the state with the padded public key absorbed
+fn absorb(public_key: &[u8; 64]) -> [u64; STATE_LANES] {
+    let mut state = [0u64; STATE_LANES];
+    for (lane, bytes) in state.iter_mut().zip(public_key.chunks_exact(8)) {
+        *lane = u64::from_le_bytes(bytes.try_into().expect("lane is 8 bytes long"));
+    }
+    // Keccak padding of the 136 byte block, 0x01 after the data and 0x80 at the end
+    state[8] ^= 0x01;
+    state[16] ^= 0x80 << 56;
+    state
+}
+
+/// Returns the 32 byte hash from the permuted state
+fn squeeze(state: &[u64; STATE_LANES]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    for (bytes, lane) in hash.chunks_exact_mut(8).zip(state) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+    hash
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::utils::keccak256;
+
+    #[test]
+    fn test_keccak256_public_keys() {
+        let public_keys: Vec<[u8; 64]> = (0..9u8)
+            .map(|i| std::array::from_fn(|j| i.wrapping_mul(31).wrapping_add(j as u8)))
+            .collect();
+        for count in 0..=public_keys.len() {
+            let expected: Vec<[u8; 32]> = public_keys[..count]
+                .iter()
+                .map(|public_key| keccak256(public_key))
+                .collect();
+            assert_eq!(expected, keccak256_public_keys(&public_keys[..count]));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::utils::uint256;
+use crate::{backend, Address, PrivateKey, PublicKey, Result};
+use core::iter::FusedIterator;
+
+/// Iterator over consecutive private keys and their addresses, starting from the key and counting
+/// up until the end of the curve order. The next public key is the previous one plus the generator
+/// point, which is much cheaper than deriving every key from scratch. Limit the range with
+/// `take()`.
+///
+/// Meant for puzzles, test fixtures and audits of weak key spaces. Never use keys from a range as
+/// real wallets, anyone can walk the same range.
+/// ```
+/// use ethereum_private_key_to_address::KeyRange;
+///
+/// let addresses: Vec<String> = KeyRange::from_index(1)
+///     .unwrap()
+///     .take(2)
+///     .map(|(_, address)| address.to_checksum())
+///     .collect();
+///
+/// assert_eq!(
+///     vec![
+///         "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+///         "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF",
+///     ],
+///     addresses
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyRange {
+    /// Next key and its public key, `None` once the end of the curve order is reached
+    next: Option<(backend::SecretKey, backend::PublicKey)>,
+    /// Generator point added to the public key in every step
+    generator: backend::PublicKey,
+}
+
+impl KeyRange {
+    /// Creates the range starting at the key
+    pub fn new(start: &PrivateKey) -> Self {
+        Self {
+            next: Some((start.private_key, backend::public_key(&start.private_key))),
+            generator: backend::public_key(&backend::one()),
+        }
+    }
+
+    /// Creates the range starting at the key with the numeric value, fails if it's zero
+    pub fn from_index(start: u128) -> Result<Self> {
+        Ok(Self::new(&PrivateKey::from_slice(&uint256(start))?))
+    }
+}
+
+impl Iterator for KeyRange {
+    type Item = (PrivateKey, Address);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (secret_key, public_key) = self.next.take()?;
+
+        // Both fail only after the last key of the curve order
+        self.next = backend::add_tweak(&secret_key, &uint256(1))
+            .ok()
+            .zip(backend::combine(&public_key, &self.generator).ok());
+        Some((
+            PrivateKey {
+                private_key: secret_key,
+            },
+            PublicKey::from_backend(public_key).address(),
+        ))
+    }
+}
+
+impl FusedIterator for KeyRange {}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_key_range() {
+        let mut range = KeyRange::from_index(1).unwrap();
+        for expected in [
+            "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf",
+            "0x2b5ad5c4795c026514f8317c7a215e218dccd6cf",
+            "0x6813eb9362372eef6200f3b1dbc3f819671cba69",
+        ] {
+            let (private_key, address) = range.next().unwrap();
+            assert_eq!(expected, address.to_string());
+            assert_eq!(private_key.to_address(), address);
+        }
+        assert!(KeyRange::from_index(0).is_err());
+
+        let last = PrivateKey::from_str(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        )
+        .unwrap();
+        let mut range = KeyRange::new(&last);
+        assert_eq!(last.to_address(), range.next().unwrap().1);
+        assert!(range.next().is_none());
+    }
+}
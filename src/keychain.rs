@@ -0,0 +1,118 @@
+use crate::{Address, Error, PrivateKey, Result};
+use keyring::Entry;
+use std::str::FromStr;
+use zeroize::Zeroizing;
+
+/// Private keys stored in the platform keychain, the macOS Keychain, the Windows Credential
+/// Manager or the Secret Service on Linux. The keys are stored under the service name with the
+/// checksummed address as the account, so they can be loaded by the address without ever typing
+/// the private key into a shell.
+/// ```no_run
+/// use std::str::FromStr;
+/// use ethereum_private_key_to_address::{Keychain, PrivateKey};
+///
+/// let keychain = Keychain::new();
+///
+/// let private_key = PrivateKey::random();
+/// let address = keychain.store(&private_key).unwrap();
+///
+/// let loaded = keychain.load(&address).unwrap();
+/// assert_eq!(loaded, private_key);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Keychain {
+    /// Service name of the keychain entries
+    service: String,
+}
+
+impl Keychain {
+    /// Service name used by `new()`
+    pub const DEFAULT_SERVICE: &'static str = "ethereum-private-key-to-address";
+
+    /// Creates the keychain with the default service name
+    pub fn new() -> Self {
+        Self::with_service(Self::DEFAULT_SERVICE)
+    }
+
+    /// Creates the keychain with the service name, e.g. the name of the application, so its keys
+    /// are kept apart
+    pub fn with_service(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    /// Returns the service name of the keychain entries
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Stores the private key under its address and returns the address. A key already stored
+    /// under the address is overwritten, it's the same key.
+    pub fn store(&self, private_key: &PrivateKey) -> Result<Address> {
+        let address = private_key.to_address();
+        self.entry(&address)?
+            .set_password(&private_key.expose_secret_hex())
+            .map_err(keychain_error)?;
+        Ok(address)
+    }
+
+    /// Loads the private key of the address, fails if there's no key stored under the address or
+    /// the stored key belongs to a different address
+    pub fn load(&self, address: &Address) -> Result<PrivateKey> {
+        let secret = Zeroizing::new(self.entry(address)?.get_password().map_err(
+            |error| match error {
+                keyring::Error::NoEntry => {
+                    Error::Keychain(format!("no key stored for address {address}"))
+                }
+                error => keychain_error(error),
+            },
+        )?);
+
+        let private_key = PrivateKey::from_str(&secret)?;
+        if private_key.to_address() != *address {
+            return Err(Error::Keychain(format!(
+                "key stored for address {address} belongs to {}",
+                private_key.to_address()
+            )));
+        }
+        Ok(private_key)
+    }
+
+    /// Returns true if a key is stored under the address
+    pub fn contains(&self, address: &Address) -> Result<bool> {
+        match self.entry(address)?.get_password() {
+            Ok(secret) => {
+                drop(Zeroizing::new(secret));
+                Ok(true)
+            }
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(error) => Err(keychain_error(error)),
+        }
+    }
+
+    /// Deletes the key of the address, returns false if no key was stored under the address
+    pub fn delete(&self, address: &Address) -> Result<bool> {
+        match self.entry(address)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(error) => Err(keychain_error(error)),
+        }
+    }
+
+    /// Returns the keychain entry of the address
+    fn entry(&self, address: &Address) -> Result<Entry> {
+        Entry::new(&self.service, &address.to_checksum()).map_err(keychain_error)
+    }
+}
+
+impl Default for Keychain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts the error of the platform keychain
+fn keychain_error(error: keyring::Error) -> Error {
+    Error::Keychain(error.to_string())
+}
@@ -0,0 +1,889 @@
+use crate::utils::keccak256;
+use crate::{Address, Error, Language, Mnemonic, PrivateKey, Result};
+use aes::cipher::{KeyIvInit, StreamCipher};
+use rand_core::CryptoRngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Marks keystores encrypted with Argon2id. They aren't part of the V3 standard, so other wallets
+/// can't decrypt them.
+#[cfg(feature = "argon2")]
+const ARGON2ID_EXTENSION: &str = "argon2id-v1";
+
+// Limits of the KDF parameters, the keystore can come from an untrusted source and mustn't make
+// the decryption run out of memory or take forever. They are well above the parameters used by
+// wallets, `KdfPreset::Paranoid` uses half of the scrypt memory and an eighth of the work.
+
+/// Longest derived key, only the first 32 bytes are used
+const MAX_DKLEN: usize = 64;
+/// Memory of scrypt, `128 * n * r` bytes
+const MAX_SCRYPT_MEMORY: u64 = 2 << 30;
+/// Work of scrypt, `n * r * p`
+const MAX_SCRYPT_WORK: u64 = 1 << 26;
+/// Iterations of PBKDF2, geth uses 262144
+const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+/// Memory of Argon2id in KiB
+#[cfg(feature = "argon2")]
+const MAX_ARGON2_MEMORY_KIB: u32 = 2 << 20;
+/// Passes of Argon2id over the memory
+#[cfg(feature = "argon2")]
+const MAX_ARGON2_ITERATIONS: u32 = 100;
+
+/// KDF used to derive the encryption key of an exported keystore from the password
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeystoreKdf {
+    /// scrypt with CPU/memory cost `n` (power of 2), block size `r` and parallelization `p`
+    Scrypt {
+        /// CPU/memory cost, has to be a power of 2
+        n: u64,
+        /// Block size
+        r: u32,
+        /// Parallelization
+        p: u32,
+    },
+    /// PBKDF2-HMAC-SHA256 with the given number of iterations
+    Pbkdf2 {
+        /// Number of iterations
+        iterations: u32,
+    },
+    /// Memory-hard Argon2id. This is a non-standard extension of the V3 keystore that only this
+    /// library can decrypt, the keystore is marked with `"x-extension": "argon2id-v1"`.
+    #[cfg(feature = "argon2")]
+    Argon2id {
+        /// Memory cost in KiB
+        memory_kib: u32,
+        /// Number of passes over the memory
+        iterations: u32,
+        /// Number of lanes
+        parallelism: u32,
+    },
+}
+
+/// Same parameters as geth uses by default
+impl Default for KeystoreKdf {
+    fn default() -> Self {
+        KdfPreset::Standard.into()
+    }
+}
+
+/// Named scrypt parameters of the keystore KDF
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum KdfPreset {
+    /// `n = 4096, r = 8, p = 6`, the light parameters of geth. Uses 4 MiB of memory and unlocks
+    /// in tens of milliseconds, meant for devices with little memory or for tests.
+    Light,
+    /// `n = 262144, r = 8, p = 1`, the default parameters of geth. Uses 256 MiB of memory and
+    /// unlocks in about a second.
+    #[default]
+    Standard,
+    /// `n = 1048576, r = 8, p = 1`. Uses 1 GiB of memory and unlocks in several seconds, meant
+    /// for keystores stored long-term.
+    Paranoid,
+}
+
+impl From<KdfPreset> for KeystoreKdf {
+    fn from(preset: KdfPreset) -> Self {
+        let (n, p) = match preset {
+            KdfPreset::Light => (1 << 12, 6),
+            KdfPreset::Standard => (1 << 18, 1),
+            KdfPreset::Paranoid => (1 << 20, 1),
+        };
+        KeystoreKdf::Scrypt { n, r: 8, p }
+    }
+}
+
+/// Options of the keystore created by `PrivateKey::to_keystore()`
+/// ```
+/// use ethereum_private_key_to_address::{KeystoreOptions, PrivateKey};
+///
+/// let private_key = PrivateKey::random();
+/// let options = KeystoreOptions::scrypt(8192, 8, 1);
+/// let json = private_key.to_keystore("password", &options).unwrap();
+///
+/// assert_eq!(private_key, PrivateKey::from_keystore(&json, "password").unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeystoreOptions {
+    /// KDF and its parameters
+    kdf: KeystoreKdf,
+}
+
+impl KeystoreOptions {
+    /// Creates the options with the given KDF
+    pub fn new(kdf: KeystoreKdf) -> Self {
+        Self { kdf }
+    }
+
+    /// Uses scrypt with the given parameters
+    pub fn scrypt(n: u64, r: u32, p: u32) -> Self {
+        Self::new(KeystoreKdf::Scrypt { n, r, p })
+    }
+
+    /// Uses PBKDF2-HMAC-SHA256 with the given number of iterations
+    pub fn pbkdf2(iterations: u32) -> Self {
+        Self::new(KeystoreKdf::Pbkdf2 { iterations })
+    }
+
+    /// Uses scrypt with the parameters of the preset
+    pub fn preset(preset: KdfPreset) -> Self {
+        Self::new(preset.into())
+    }
+
+    /// Benchmarks scrypt on this machine and picks the highest `n` (with `r = 8, p = 1`) that
+    /// unlocks the keystore within the target time. `n` is between 2^12 and 2^20, so the result
+    /// is never weaker than `KdfPreset::Light`. Running the benchmark takes up to twice the
+    /// target time.
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use ethereum_private_key_to_address::{KeystoreOptions, PrivateKey};
+    ///
+    /// let options = KeystoreOptions::auto_tune(Duration::from_secs(1));
+    /// let json = PrivateKey::random().to_keystore("password", &options).unwrap();
+    /// ```
+    pub fn auto_tune(target: Duration) -> Self {
+        const MIN_LOG_N: u8 = 12;
+        const MAX_LOG_N: u8 = 20;
+
+        let mut log_n = MIN_LOG_N;
+        while log_n < MAX_LOG_N {
+            let params = scrypt::Params::new(log_n, 8, 1, 32).expect("parameters are valid");
+            let mut key = [0u8; 32];
+            let start = Instant::now();
+            scrypt::scrypt(b"password", &[0u8; 32], &params, &mut key)
+                .expect("output length is valid");
+            // Doubling n doubles the time
+            if start.elapsed() * 2 > target {
+                break;
+            }
+            log_n += 1;
+        }
+        Self::scrypt(1 << log_n, 8, 1)
+    }
+
+    /// Uses Argon2id with the given parameters, RFC 9106 recommends 64 MiB of memory (65536),
+    /// 3 iterations and 4 lanes. The keystore can't be imported into other wallets.
+    #[cfg(feature = "argon2")]
+    pub fn argon2id(memory_kib: u32, iterations: u32, parallelism: u32) -> Self {
+        Self::new(KeystoreKdf::Argon2id {
+            memory_kib,
+            iterations,
+            parallelism,
+        })
+    }
+
+    /// Returns the KDF
+    pub fn kdf(&self) -> KeystoreKdf {
+        self.kdf
+    }
+}
+
+/// Web3 Secret Storage (keystore V3) JSON file
+#[derive(Serialize, Deserialize)]
+struct KeystoreJson {
+    /// Address of the key without the `0x` prefix, it's optional
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    /// Some wallets, for example MyEtherWallet, capitalize the field
+    #[serde(alias = "Crypto")]
+    crypto: CryptoJson,
+    /// Random UUID of the keystore
+    #[serde(default)]
+    id: String,
+    /// Version of the non-standard extension used by the keystore
+    #[serde(
+        rename = "x-extension",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    extension: Option<String>,
+    /// Metadata of the mnemonic if the keystore contains a mnemonic instead of a private key
+    #[serde(
+        rename = "x-mnemonic",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    mnemonic: Option<MnemonicMetadata>,
+    /// Always 3
+    version: u8,
+}
+
+/// Encrypted secret with the parameters of the KDF and the cipher
+#[derive(Serialize, Deserialize)]
+struct CryptoJson {
+    cipher: String,
+    cipherparams: CipherParams,
+    #[serde(with = "hex")]
+    ciphertext: Vec<u8>,
+    #[serde(flatten)]
+    kdf: KdfParams,
+    #[serde(with = "hex")]
+    mac: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    #[serde(with = "hex")]
+    iv: Vec<u8>,
+}
+
+/// KDF used to derive the encryption key from the password
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+enum KdfParams {
+    Scrypt {
+        dklen: usize,
+        n: u64,
+        r: u32,
+        p: u32,
+        #[serde(with = "hex")]
+        salt: Vec<u8>,
+    },
+    Pbkdf2 {
+        c: u32,
+        dklen: usize,
+        prf: String,
+        #[serde(with = "hex")]
+        salt: Vec<u8>,
+    },
+    #[cfg(feature = "argon2")]
+    Argon2id {
+        dklen: usize,
+        m: u32,
+        t: u32,
+        p: u32,
+        #[serde(with = "hex")]
+        salt: Vec<u8>,
+    },
+}
+
+impl KdfParams {
+    /// Creates the parameters of the KDF with a random salt
+    fn generate<R: CryptoRngCore + ?Sized>(rng: &mut R, kdf: KeystoreKdf) -> Self {
+        let mut salt = vec![0u8; 32];
+        rng.fill_bytes(&mut salt);
+        match kdf {
+            KeystoreKdf::Scrypt { n, r, p } => KdfParams::Scrypt {
+                dklen: 32,
+                n,
+                r,
+                p,
+                salt,
+            },
+            KeystoreKdf::Pbkdf2 { iterations } => KdfParams::Pbkdf2 {
+                c: iterations,
+                dklen: 32,
+                prf: "hmac-sha256".to_string(),
+                salt,
+            },
+            #[cfg(feature = "argon2")]
+            KeystoreKdf::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+            } => KdfParams::Argon2id {
+                dklen: 32,
+                m: memory_kib,
+                t: iterations,
+                p: parallelism,
+                salt,
+            },
+        }
+    }
+
+    /// Returns the extension the keystore has to be marked with when it uses this KDF
+    fn extension(&self) -> Option<&'static str> {
+        match self {
+            KdfParams::Scrypt { .. } | KdfParams::Pbkdf2 { .. } => None,
+            #[cfg(feature = "argon2")]
+            KdfParams::Argon2id { .. } => Some(ARGON2ID_EXTENSION),
+        }
+    }
+
+    /// Derives the key from the password, fails if the parameters exceed the limits
+    fn derive_key(&self, password: &str) -> Result<Zeroizing<Vec<u8>>> {
+        let invalid = |reason: &str| Error::InvalidKeystore(reason.to_string());
+
+        let dklen = match self {
+            KdfParams::Scrypt { dklen, .. } | KdfParams::Pbkdf2 { dklen, .. } => *dklen,
+            #[cfg(feature = "argon2")]
+            KdfParams::Argon2id { dklen, .. } => *dklen,
+        };
+        if dklen < 32 {
+            return Err(invalid("derived key has to be at least 32 bytes long"));
+        }
+        if dklen > MAX_DKLEN {
+            return Err(invalid("derived key is too long"));
+        }
+
+        match self {
+            KdfParams::Scrypt {
+                dklen,
+                n,
+                r,
+                p,
+                salt,
+            } => {
+                if *n < 2 || !n.is_power_of_two() {
+                    return Err(invalid("scrypt n has to be a power of 2"));
+                }
+                if *r == 0 || *p == 0 {
+                    return Err(invalid("scrypt r and p can't be 0"));
+                }
+                if n.saturating_mul(u64::from(*r)).saturating_mul(128) > MAX_SCRYPT_MEMORY
+                    || n.saturating_mul(u64::from(*r))
+                        .saturating_mul(u64::from(*p))
+                        > MAX_SCRYPT_WORK
+                {
+                    return Err(invalid("scrypt parameters are too high"));
+                }
+                let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p, *dklen)
+                    .map_err(|_| invalid("invalid scrypt parameters"))?;
+                let mut key = Zeroizing::new(vec![0u8; *dklen]);
+                scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+                    .map_err(|_| invalid("invalid scrypt parameters"))?;
+                Ok(key)
+            }
+            KdfParams::Pbkdf2 {
+                c,
+                dklen,
+                prf,
+                salt,
+            } => {
+                if *c == 0 || *c > MAX_PBKDF2_ITERATIONS {
+                    return Err(invalid("invalid pbkdf2 iteration count"));
+                }
+                if prf != "hmac-sha256" {
+                    return Err(invalid("only hmac-sha256 PRF is supported"));
+                }
+                let mut key = Zeroizing::new(vec![0u8; *dklen]);
+                pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, *c, &mut key);
+                Ok(key)
+            }
+            #[cfg(feature = "argon2")]
+            KdfParams::Argon2id {
+                dklen,
+                m,
+                t,
+                p,
+                salt,
+            } => {
+                if *m > MAX_ARGON2_MEMORY_KIB || *t > MAX_ARGON2_ITERATIONS {
+                    return Err(invalid("argon2 parameters are too high"));
+                }
+                let params = argon2::Params::new(*m, *t, *p, Some(*dklen))
+                    .map_err(|error| invalid(&format!("invalid argon2 parameters: {error}")))?;
+                let mut key = Zeroizing::new(vec![0u8; *dklen]);
+                argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+                    .hash_password_into(password.as_bytes(), salt, &mut key)
+                    .map_err(|error| invalid(&format!("invalid argon2 parameters: {error}")))?;
+                Ok(key)
+            }
+        }
+    }
+}
+
+impl KeystoreJson {
+    /// Parses the keystore and validates its version and extension, without decrypting it
+    fn parse(json: &str) -> Result<Self> {
+        let invalid = |reason: &str| Error::InvalidKeystore(reason.to_string());
+
+        let keystore: Self =
+            serde_json::from_str(json).map_err(|error| invalid(&error.to_string()))?;
+        if keystore.version != 3 {
+            return Err(invalid("only version 3 is supported"));
+        }
+        if keystore.extension.as_deref() != keystore.crypto.kdf.extension() {
+            return Err(invalid("unsupported keystore extension"));
+        }
+        Ok(keystore)
+    }
+
+    /// Encrypts the secret with the password into a keystore without the address and the mnemonic
+    /// metadata
+    fn encrypt<R: CryptoRngCore + ?Sized>(
+        rng: &mut R,
+        secret: &[u8],
+        password: &str,
+        options: &KeystoreOptions,
+    ) -> Result<Self> {
+        let kdf = KdfParams::generate(rng, options.kdf);
+        let key = kdf.derive_key(password)?;
+
+        let mut iv = vec![0u8; 16];
+        rng.fill_bytes(&mut iv);
+        let mut ciphertext = secret.to_vec();
+        Aes128Ctr::new_from_slices(&key[..16], &iv)
+            .expect("key and IV are 16 bytes long")
+            .apply_keystream(&mut ciphertext);
+
+        let mut mac_input = Zeroizing::new(key[16..32].to_vec());
+        mac_input.extend_from_slice(&ciphertext);
+        let mut uuid = [0u8; 16];
+        rng.fill_bytes(&mut uuid);
+
+        Ok(Self {
+            address: None,
+            extension: kdf.extension().map(str::to_string),
+            crypto: CryptoJson {
+                cipher: "aes-128-ctr".to_string(),
+                cipherparams: CipherParams { iv },
+                ciphertext,
+                kdf,
+                mac: keccak256(&mac_input).to_vec(),
+            },
+            id: uuid::Builder::from_random_bytes(uuid)
+                .into_uuid()
+                .to_string(),
+            mnemonic: None,
+            version: 3,
+        })
+    }
+
+    /// Verifies the MAC and decrypts the secret with the password
+    fn decrypt(self, password: &str) -> Result<Zeroizing<Vec<u8>>> {
+        let crypto = self.crypto;
+        if crypto.cipher != "aes-128-ctr" {
+            return Err(Error::InvalidKeystore(
+                "only aes-128-ctr cipher is supported".to_string(),
+            ));
+        }
+
+        let key = crypto.kdf.derive_key(password)?;
+        let mut mac_input = Zeroizing::new(key[16..32].to_vec());
+        mac_input.extend_from_slice(&crypto.ciphertext);
+        // The MAC depends on the password, compare it in constant time
+        if !bool::from(keccak256(&mac_input)[..].ct_eq(&crypto.mac[..])) {
+            return Err(Error::InvalidPassword);
+        }
+
+        let mut secret = Zeroizing::new(crypto.ciphertext);
+        Aes128Ctr::new_from_slices(&key[..16], &crypto.cipherparams.iv)
+            .map_err(|_| Error::InvalidKeystore("IV has to be 16 bytes long".to_string()))?
+            .apply_keystream(&mut secret);
+        Ok(secret)
+    }
+
+    /// Fails if the keystore contains a mnemonic instead of a private key
+    fn expect_private_key(self) -> Result<Self> {
+        match self.mnemonic {
+            Some(_) => Err(Error::InvalidKeystore(
+                "keystore contains a mnemonic, use Mnemonic::from_keystore()".to_string(),
+            )),
+            None => Ok(self),
+        }
+    }
+
+    /// Serializes the keystore into JSON
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("keystore is always serializable")
+    }
+}
+
+/// Parses the private key keystore without decrypting it and returns the address stored in it,
+/// if any, and the id
+pub(crate) fn parse_metadata(json: &str) -> Result<(Option<Address>, String)> {
+    let keystore = KeystoreJson::parse(json)?.expect_private_key()?;
+    let address = keystore
+        .address
+        .map(|address| Address::from_str(&address))
+        .transpose()?;
+    Ok((address, keystore.id))
+}
+
+impl PrivateKey {
+    /// Decrypts the private key from the Web3 Secret Storage (keystore V3) JSON created by geth,
+    /// MetaMask, MyCrypto and other wallets. Both scrypt and pbkdf2 KDFs are supported.
+    /// ```no_run
+    /// use ethereum_private_key_to_address::PrivateKey;
+    ///
+    /// let json = std::fs::read_to_string("UTC--2023-01-01T00-00-00.000Z--f39fd6e5...").unwrap();
+    /// let private_key = PrivateKey::from_keystore(&json, "password").unwrap();
+    ///
+    /// println!("{}", private_key.address());
+    /// ```
+    pub fn from_keystore(json: &str, password: &str) -> Result<Self> {
+        let private_key = KeystoreJson::parse(json)?
+            .expect_private_key()?
+            .decrypt(password)?;
+        Self::from_slice(&private_key)
+    }
+
+    /// Encrypts the private key with the password into Web3 Secret Storage (keystore V3) JSON
+    /// that can be imported into geth, MetaMask and other wallets
+    pub fn to_keystore(&self, password: &str, options: &KeystoreOptions) -> Result<String> {
+        self.to_keystore_with(&mut rand_core::OsRng, password, options)
+    }
+
+    /// Same as `to_keystore()` but generates the salt, IV and UUID with the given random number
+    /// generator
+    pub fn to_keystore_with<R: CryptoRngCore + ?Sized>(
+        &self,
+        rng: &mut R,
+        password: &str,
+        options: &KeystoreOptions,
+    ) -> Result<String> {
+        let mut keystore = KeystoreJson::encrypt(rng, &self.secret_bytes()[..], password, options)?;
+        keystore.address = Some(self.address()[2..].to_string());
+        Ok(keystore.to_json())
+    }
+}
+
+/// Metadata of a keystore that contains a mnemonic instead of a private key
+#[derive(Serialize, Deserialize)]
+struct MnemonicMetadata {
+    /// Language of the mnemonic
+    language: String,
+    /// Hint of the BIP-39 passphrase, it's stored in plain text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    passphrase_hint: Option<String>,
+}
+
+/// Names of the languages stored in the mnemonic metadata
+const LANGUAGES: [(Language, &str); 10] = [
+    (Language::English, "english"),
+    (Language::SimplifiedChinese, "simplified-chinese"),
+    (Language::TraditionalChinese, "traditional-chinese"),
+    (Language::Czech, "czech"),
+    (Language::French, "french"),
+    (Language::Italian, "italian"),
+    (Language::Japanese, "japanese"),
+    (Language::Korean, "korean"),
+    (Language::Portuguese, "portuguese"),
+    (Language::Spanish, "spanish"),
+];
+
+impl Mnemonic {
+    /// Encrypts the mnemonic with the password into a keystore container. It uses the same
+    /// encryption as the V3 keystore, but other wallets can't import it. The optional hint of the
+    /// BIP-39 passphrase is stored unencrypted, so it must not contain the passphrase itself.
+    /// ```
+    /// use ethereum_private_key_to_address::{KdfPreset, KeystoreOptions, Mnemonic};
+    ///
+    /// let mnemonic = Mnemonic::generate(24).unwrap();
+    /// let options = KeystoreOptions::preset(KdfPreset::Light);
+    /// let json = mnemonic.to_keystore("password", &options, Some("name of the first pet")).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some("name of the first pet".to_string()),
+    ///     Mnemonic::keystore_passphrase_hint(&json).unwrap()
+    /// );
+    /// assert_eq!(mnemonic, Mnemonic::from_keystore(&json, "password").unwrap());
+    /// ```
+    pub fn to_keystore(
+        &self,
+        password: &str,
+        options: &KeystoreOptions,
+        passphrase_hint: Option<&str>,
+    ) -> Result<String> {
+        self.to_keystore_with(&mut rand_core::OsRng, password, options, passphrase_hint)
+    }
+
+    /// Same as `to_keystore()` but generates the salt, IV and UUID with the given random number
+    /// generator
+    pub fn to_keystore_with<R: CryptoRngCore + ?Sized>(
+        &self,
+        rng: &mut R,
+        password: &str,
+        options: &KeystoreOptions,
+        passphrase_hint: Option<&str>,
+    ) -> Result<String> {
+        let language = LANGUAGES
+            .iter()
+            .find(|(language, _)| *language == self.language())
+            .map(|(_, name)| name.to_string())
+            .expect("all languages have a name");
+
+        let mut keystore = KeystoreJson::encrypt(rng, self.phrase().as_bytes(), password, options)?;
+        keystore.mnemonic = Some(MnemonicMetadata {
+            language,
+            passphrase_hint: passphrase_hint.map(str::to_string),
+        });
+        Ok(keystore.to_json())
+    }
+
+    /// Decrypts the mnemonic from the keystore container created by `to_keystore()`
+    pub fn from_keystore(json: &str, password: &str) -> Result<Self> {
+        let keystore = KeystoreJson::parse(json)?;
+        let language = match &keystore.mnemonic {
+            Some(metadata) => LANGUAGES
+                .iter()
+                .find(|(_, name)| *name == metadata.language)
+                .map(|(language, _)| *language)
+                .ok_or_else(|| Error::InvalidKeystore("unknown mnemonic language".to_string()))?,
+            None => {
+                return Err(Error::InvalidKeystore(
+                    "keystore doesn't contain a mnemonic".to_string(),
+                ))
+            }
+        };
+
+        let phrase = keystore.decrypt(password)?;
+        let phrase = std::str::from_utf8(&phrase)
+            .map_err(|_| Error::InvalidKeystore("mnemonic isn't valid UTF-8".to_string()))?;
+        Self::parse_in(language, phrase)
+    }
+
+    /// Returns the hint of the BIP-39 passphrase stored in the keystore container, without
+    /// decrypting it
+    pub fn keystore_passphrase_hint(json: &str) -> Result<Option<String>> {
+        match KeystoreJson::parse(json)?.mnemonic {
+            Some(metadata) => Ok(metadata.passphrase_hint),
+            None => Err(Error::InvalidKeystore(
+                "keystore doesn't contain a mnemonic".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{Error, KdfPreset, KeystoreKdf, KeystoreOptions, Language, Mnemonic, PrivateKey};
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    const PRIVATE_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+    const PASSWORD: &str = "testpassword";
+
+    // Same key and password as in the Web3 Secret Storage test vectors, with lower KDF costs so
+    // the tests run fast
+    const SCRYPT: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {"iv": "83dbcc02d8ccb40e466191a123791e0e"},
+            "ciphertext": "01a05c7f05b697274227d8bd0825a6caa89967e24643426c0fcfa2fb663052d7",
+            "kdf": "scrypt",
+            "kdfparams": {
+                "dklen": 32,
+                "n": 1024,
+                "p": 1,
+                "r": 8,
+                "salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+            },
+            "mac": "d60a6540bbdeaa746e4c7b4359c74e4bb0b679bedce5b4d129ad96150d200274"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    const PBKDF2: &str = r#"{
+        "Crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {"iv": "83dbcc02d8ccb40e466191a123791e0e"},
+            "ciphertext": "cd0049568e41620bb294a26bdf1e3088e3070f2f784293fe4a4d9a6b5a415ad1",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 1024,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+            },
+            "mac": "f8438cbefa2ca738411030fea72aab77ea85c77fd7d07219d362df3804de1f6a"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    #[test]
+    fn test_from_keystore() {
+        for json in [SCRYPT, PBKDF2] {
+            let private_key = PrivateKey::from_keystore(json, PASSWORD).unwrap();
+            assert_eq!(PRIVATE_KEY, private_key.expose_secret_hex().as_str());
+        }
+    }
+
+    #[test]
+    fn test_invalid_keystore() {
+        assert_eq!(
+            PrivateKey::from_keystore(SCRYPT, "wrongpassword"),
+            Err(Error::InvalidPassword)
+        );
+        assert!(matches!(
+            PrivateKey::from_keystore(&SCRYPT.replace("aes-128-ctr", "aes-128-cbc"), PASSWORD),
+            Err(Error::InvalidKeystore(_))
+        ));
+        assert!(matches!(
+            PrivateKey::from_keystore(&SCRYPT.replace("\"n\": 1024", "\"n\": 1000"), PASSWORD),
+            Err(Error::InvalidKeystore(_))
+        ));
+        assert!(matches!(
+            PrivateKey::from_keystore("{}", PASSWORD),
+            Err(Error::InvalidKeystore(_))
+        ));
+    }
+
+    #[test]
+    fn test_kdf_limits() {
+        for (json, from, to) in [
+            (SCRYPT, "\"n\": 1024", "\"n\": 4294967296"),
+            (SCRYPT, "\"p\": 1", "\"p\": 4294967295"),
+            (SCRYPT, "\"p\": 1", "\"p\": 0"),
+            (SCRYPT, "\"dklen\": 32", "\"dklen\": 1073741824"),
+            (PBKDF2, "\"c\": 1024", "\"c\": 0"),
+            (PBKDF2, "\"c\": 1024", "\"c\": 4294967295"),
+        ] {
+            assert!(matches!(
+                PrivateKey::from_keystore(&json.replace(from, to), PASSWORD),
+                Err(Error::InvalidKeystore(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_to_keystore() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        for options in [
+            KeystoreOptions::scrypt(1024, 8, 1),
+            KeystoreOptions::pbkdf2(1024),
+        ] {
+            let json = private_key.to_keystore(PASSWORD, &options).unwrap();
+            assert_eq!(
+                private_key,
+                PrivateKey::from_keystore(&json, PASSWORD).unwrap()
+            );
+
+            let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(3, value["version"]);
+            assert_eq!("008aeeda4d805471df9b2a5b0f38a0c3bcba786b", value["address"]);
+            assert_eq!("aes-128-ctr", value["crypto"]["cipher"]);
+            assert_eq!(36, value["id"].as_str().unwrap().len());
+        }
+
+        let first = private_key
+            .to_keystore(PASSWORD, &KeystoreOptions::pbkdf2(1024))
+            .unwrap();
+        let second = private_key
+            .to_keystore(PASSWORD, &KeystoreOptions::pbkdf2(1024))
+            .unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        assert!(matches!(
+            private_key.to_keystore(PASSWORD, &KeystoreOptions::scrypt(1000, 8, 1)),
+            Err(Error::InvalidKeystore(_))
+        ));
+        assert!(matches!(
+            private_key.to_keystore(PASSWORD, &KeystoreOptions::scrypt(1024, 0, 1)),
+            Err(Error::InvalidKeystore(_))
+        ));
+    }
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(
+            KeystoreKdf::Scrypt {
+                n: 262144,
+                r: 8,
+                p: 1
+            },
+            KeystoreOptions::default().kdf()
+        );
+        assert_eq!(
+            KeystoreOptions::scrypt(4096, 8, 6),
+            KeystoreOptions::preset(KdfPreset::Light)
+        );
+
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let json = private_key
+            .to_keystore(PASSWORD, &KeystoreOptions::preset(KdfPreset::Light))
+            .unwrap();
+        assert_eq!(
+            private_key,
+            PrivateKey::from_keystore(&json, PASSWORD).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_auto_tune() {
+        assert_eq!(
+            KeystoreOptions::scrypt(4096, 8, 1),
+            KeystoreOptions::auto_tune(Duration::ZERO)
+        );
+        match KeystoreOptions::auto_tune(Duration::from_millis(100)).kdf() {
+            KeystoreKdf::Scrypt { n, r: 8, p: 1 } => {
+                assert!(n.is_power_of_two() && (4096..=1 << 20).contains(&n))
+            }
+            kdf => panic!("unexpected KDF {kdf:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_extension() {
+        let json = SCRYPT.replace("\"version\"", "\"x-extension\": \"unknown\", \"version\"");
+        assert_eq!(
+            PrivateKey::from_keystore(&json, PASSWORD),
+            Err(Error::InvalidKeystore(
+                "unsupported keystore extension".to_string()
+            ))
+        );
+    }
+
+    #[cfg(feature = "argon2")]
+    #[test]
+    fn test_argon2id_keystore() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let json = private_key
+            .to_keystore(PASSWORD, &KeystoreOptions::argon2id(1024, 2, 1))
+            .unwrap();
+        assert_eq!(
+            private_key,
+            PrivateKey::from_keystore(&json, PASSWORD).unwrap()
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!("argon2id-v1", value["x-extension"]);
+        assert_eq!("argon2id", value["crypto"]["kdf"]);
+        assert_eq!(1024, value["crypto"]["kdfparams"]["m"]);
+
+        let unmarked = json.replace("\"x-extension\":\"argon2id-v1\",", "");
+        assert!(matches!(
+            PrivateKey::from_keystore(&unmarked, PASSWORD),
+            Err(Error::InvalidKeystore(_))
+        ));
+        assert_eq!(
+            PrivateKey::from_keystore(&json, "wrongpassword"),
+            Err(Error::InvalidPassword)
+        );
+    }
+
+    #[test]
+    fn test_mnemonic_keystore() {
+        let options = KeystoreOptions::pbkdf2(1024);
+        let mnemonic = Mnemonic::generate_in(Language::Japanese, 12).unwrap();
+        let json = mnemonic
+            .to_keystore(PASSWORD, &options, Some("hint"))
+            .unwrap();
+        assert!(!json.contains(mnemonic.phrase().as_str()));
+        assert_eq!(mnemonic, Mnemonic::from_keystore(&json, PASSWORD).unwrap());
+        assert_eq!(
+            Some("hint".to_string()),
+            Mnemonic::keystore_passphrase_hint(&json).unwrap()
+        );
+        assert_eq!(
+            Mnemonic::from_keystore(&json, "wrongpassword"),
+            Err(Error::InvalidPassword)
+        );
+        assert!(matches!(
+            PrivateKey::from_keystore(&json, PASSWORD),
+            Err(Error::InvalidKeystore(_))
+        ));
+
+        let json = Mnemonic::generate(24)
+            .unwrap()
+            .to_keystore(PASSWORD, &options, None)
+            .unwrap();
+        assert_eq!(None, Mnemonic::keystore_passphrase_hint(&json).unwrap());
+        assert!(matches!(
+            Mnemonic::from_keystore(PBKDF2, PASSWORD),
+            Err(Error::InvalidKeystore(_))
+        ));
+    }
+}
@@ -0,0 +1,336 @@
+use crate::keystore::parse_metadata;
+use crate::{Address, Error, PrivateKey, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use zeroize::Zeroizing;
+
+/// Keystore file found in a `KeystoreDir`, parsed without decrypting it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeystoreEntry {
+    /// Path of the file
+    path: PathBuf,
+    /// Address from the file, or from the geth style file name if the file doesn't contain it
+    address: Option<Address>,
+    /// UUID of the keystore
+    id: String,
+}
+
+impl KeystoreEntry {
+    /// Returns the path of the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the address of the key, if it's known without decrypting the file
+    pub fn address(&self) -> Option<Address> {
+        self.address
+    }
+
+    /// Returns the UUID of the keystore
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Reads the file and decrypts the private key with the password
+    pub fn decrypt(&self, password: &str) -> Result<PrivateKey> {
+        PrivateKey::from_keystore(&read_file(&self.path)?, password)
+    }
+}
+
+/// Directory with keystore V3 files, like the `keystore` directory of geth.
+///
+/// Files are only parsed when the directory is opened, the private keys are decrypted on demand.
+/// ```no_run
+/// use std::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, KeystoreDir};
+///
+/// let dir = KeystoreDir::open("/home/user/.ethereum/keystore").unwrap();
+/// for entry in dir.entries() {
+///     println!("{:?}: {:?}", entry.path(), entry.address());
+/// }
+///
+/// let address = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+/// let private_key = dir.decrypt(&address, "password").unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeystoreDir {
+    /// Path of the directory
+    path: PathBuf,
+    /// Keystore files sorted by path
+    entries: Vec<KeystoreEntry>,
+}
+
+impl KeystoreDir {
+    /// Scans the directory for keystore files. Subdirectories, hidden files, editor backups and
+    /// files that aren't valid keystores are skipped.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let mut dir = Self {
+            path: path.as_ref().to_path_buf(),
+            entries: Vec::new(),
+        };
+        dir.rescan()?;
+        Ok(dir)
+    }
+
+    /// Scans the directory again, for example after a new keystore was added
+    pub fn rescan(&mut self) -> Result<()> {
+        let io_error = |error: std::io::Error| Error::Io(format!("{:?}: {error}", self.path));
+
+        let mut entries = Vec::new();
+        for file in fs::read_dir(&self.path).map_err(io_error)? {
+            let file = file.map_err(io_error)?;
+            let name = file.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') || name.ends_with('~') || !file.path().is_file() {
+                continue;
+            }
+            let Ok((address, id)) = read_file(&file.path()).and_then(|json| parse_metadata(&json))
+            else {
+                continue;
+            };
+            entries.push(KeystoreEntry {
+                address: address.or_else(|| address_from_file_name(&name)),
+                path: file.path(),
+                id,
+            });
+        }
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        self.entries = entries;
+        Ok(())
+    }
+
+    /// Returns the path of the directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns all keystore files in the directory
+    pub fn entries(&self) -> &[KeystoreEntry] {
+        &self.entries
+    }
+
+    /// Returns the addresses of all keystore files with a known address
+    pub fn addresses(&self) -> impl Iterator<Item = Address> + '_ {
+        self.entries.iter().filter_map(|entry| entry.address)
+    }
+
+    /// Returns the keystore file of the address. If there are more files with the same address
+    /// the first one by path is returned, for geth style file names that's the oldest one.
+    pub fn find(&self, address: &Address) -> Option<&KeystoreEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.address.as_ref() == Some(address))
+    }
+
+    /// Decrypts the private key of the address with the password
+    pub fn decrypt(&self, address: &Address, password: &str) -> Result<PrivateKey> {
+        self.find(address)
+            .ok_or(Error::KeystoreNotFound(*address))?
+            .decrypt(password)
+    }
+
+    /// Decrypts all keystores in the directory with the same password in parallel, see
+    /// `decrypt_keystores()`
+    pub fn decrypt_all(&self, password: &str) -> Vec<(PathBuf, Result<PrivateKey>)> {
+        let paths = self
+            .entries
+            .iter()
+            .map(|entry| entry.path.as_path())
+            .collect::<Vec<_>>();
+        decrypt_keystores(&paths, password)
+    }
+}
+
+/// Decrypts the keystore files with the same password in parallel on all CPU cores. Returns the
+/// path and the result of every file in the same order as the given paths.
+/// ```no_run
+/// use ethereum_private_key_to_address::decrypt_keystores;
+///
+/// let paths = ["keystore/UTC--first", "keystore/UTC--second"];
+/// for (path, private_key) in decrypt_keystores(&paths, "password") {
+///     match private_key {
+///         Ok(private_key) => println!("{path:?}: {}", private_key.address()),
+///         Err(error) => println!("{path:?}: {error}"),
+///     }
+/// }
+/// ```
+pub fn decrypt_keystores<P: AsRef<Path> + Sync>(
+    paths: &[P],
+    password: &str,
+) -> Vec<(PathBuf, Result<PrivateKey>)> {
+    decrypt_keystores_with(paths, |_| Zeroizing::new(password.to_string()))
+}
+
+/// Same as `decrypt_keystores()` but every file has its own password returned by the function
+pub fn decrypt_keystores_with<P, F>(paths: &[P], password: F) -> Vec<(PathBuf, Result<PrivateKey>)>
+where
+    P: AsRef<Path> + Sync,
+    F: Fn(&Path) -> Zeroizing<String> + Sync,
+{
+    let threads = thread::available_parallelism()
+        .map_or(1, |threads| threads.get())
+        .min(paths.len());
+    // Workers take files one by one, so one slow file doesn't hold a whole chunk
+    let next = AtomicUsize::new(0);
+
+    let mut results = thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(index) else {
+                            return results;
+                        };
+                        let path = path.as_ref();
+                        let private_key = read_file(path)
+                            .and_then(|json| PrivateKey::from_keystore(&json, &password(path)));
+                        results.push((index, path.to_path_buf(), private_key));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("keystore worker panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_by_key(|(index, _, _)| *index);
+    results
+        .into_iter()
+        .map(|(_, path, private_key)| (path, private_key))
+        .collect()
+}
+
+/// Reads the file into a string
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|error| Error::Io(format!("{path:?}: {error}")))
+}
+
+/// Parses the address from geth style file name `UTC--<created at>--<address>`
+fn address_from_file_name(name: &str) -> Option<Address> {
+    let (_, address) = name.rsplit_once("--")?;
+    Address::from_str(address).ok()
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::KeystoreOptions;
+
+    /// Creates empty directory in the system temp directory
+    fn temp_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "ethereum-private-key-to-address-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_keystore_dir() {
+        let path = temp_dir("keystore-dir");
+        let options = KeystoreOptions::pbkdf2(1024);
+
+        let first = PrivateKey::insecure_random_from_seed(1);
+        fs::write(
+            path.join("UTC--2023-01-01T00-00-00.000000000Z--first"),
+            first.to_keystore("first", &options).unwrap(),
+        )
+        .unwrap();
+
+        // Keystore without the address field, the address is read from the file name
+        let second = PrivateKey::insecure_random_from_seed(2);
+        let json = second.to_keystore("second", &options).unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("address");
+        let name = format!(
+            "UTC--2023-01-02T00-00-00.000000000Z--{}",
+            &second.address()[2..]
+        );
+        fs::write(path.join(name), value.to_string()).unwrap();
+
+        fs::write(path.join("notes.txt"), "not a keystore").unwrap();
+        fs::write(
+            path.join(".hidden"),
+            first.to_keystore("first", &options).unwrap(),
+        )
+        .unwrap();
+        fs::create_dir(path.join("subdir")).unwrap();
+
+        let dir = KeystoreDir::open(&path).unwrap();
+        assert_eq!(2, dir.entries().len());
+        assert_eq!(
+            vec![first.to_address(), second.to_address()],
+            dir.addresses().collect::<Vec<_>>()
+        );
+
+        assert_eq!(first, dir.decrypt(&first.to_address(), "first").unwrap());
+        assert_eq!(second, dir.decrypt(&second.to_address(), "second").unwrap());
+        assert_eq!(
+            dir.decrypt(&second.to_address(), "first"),
+            Err(Error::InvalidPassword)
+        );
+
+        let unknown = PrivateKey::insecure_random_from_seed(3).to_address();
+        assert!(dir.find(&unknown).is_none());
+        assert_eq!(
+            dir.decrypt(&unknown, "first"),
+            Err(Error::KeystoreNotFound(unknown))
+        );
+
+        fs::remove_dir_all(&path).unwrap();
+        assert!(matches!(KeystoreDir::open(&path), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn test_decrypt_keystores() {
+        let path = temp_dir("decrypt-keystores");
+        let options = KeystoreOptions::pbkdf2(1024);
+
+        let keys = (0..10)
+            .map(PrivateKey::insecure_random_from_seed)
+            .collect::<Vec<_>>();
+        let mut paths = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let file = path.join(format!("key-{i}"));
+                let password = format!("password-{i}");
+                fs::write(&file, key.to_keystore(&password, &options).unwrap()).unwrap();
+                file
+            })
+            .collect::<Vec<_>>();
+        paths.push(path.join("missing"));
+
+        let results = decrypt_keystores_with(&paths, |path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            Zeroizing::new(name.replace("key-", "password-"))
+        });
+        assert_eq!(11, results.len());
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(paths[i], results[i].0);
+            assert_eq!(key, results[i].1.as_ref().unwrap());
+        }
+        assert!(matches!(results[10].1, Err(Error::Io(_))));
+
+        let results = decrypt_keystores(&paths[..2], "password-1");
+        assert_eq!(Err(Error::InvalidPassword), results[0].1);
+        assert_eq!(Ok(keys[1].clone()), results[1].1);
+
+        let dir = KeystoreDir::open(&path).unwrap();
+        let results = dir.decrypt_all("password-0");
+        assert_eq!(10, results.len());
+        assert_eq!(1, results.iter().filter(|(_, key)| key.is_ok()).count());
+
+        assert!(decrypt_keystores::<&Path>(&[], "password").is_empty());
+        fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,332 @@
+use crate::eip712::hash_with_separator;
+use crate::{
+    hash_message, Address, DerivationPath, Eip712, Eip712Domain, Error, PublicKey, Result,
+    Signature, Signer, Transaction,
+};
+use hidapi::{HidApi, HidDevice, HidError};
+use std::fmt;
+use std::sync::Mutex;
+
+/// USB vendor id of Ledger devices
+const VENDOR_ID: u16 = 0x2c97;
+
+/// HID usage page of the APDU interface
+const USAGE_PAGE: u16 = 0xffa0;
+
+/// Size of the HID report
+const PACKET_SIZE: usize = 64;
+
+/// Channel of the HID transport
+const CHANNEL: u16 = 0x0101;
+
+/// Tag of the HID packets carrying APDUs
+const TAG_APDU: u8 = 0x05;
+
+/// Class of the Ethereum app commands
+const CLA: u8 = 0xe0;
+
+/// Returns the public key and the address at the path
+const INS_GET_ADDRESS: u8 = 0x02;
+
+/// Signs the unsigned transaction
+const INS_SIGN_TRANSACTION: u8 = 0x04;
+
+/// Signs the personal message
+const INS_SIGN_PERSONAL_MESSAGE: u8 = 0x08;
+
+/// Signs the EIP-712 domain separator and struct hash
+const INS_SIGN_EIP712_HASHED: u8 = 0x0c;
+
+/// `P1` of the first chunk of the data
+const P1_FIRST: u8 = 0x00;
+
+/// `P1` of the following chunks of the data
+const P1_MORE: u8 = 0x80;
+
+/// Maximum data length of one APDU
+const MAX_CHUNK: usize = 255;
+
+/// Status word of success
+const SW_OK: u16 = 0x9000;
+
+/// Signer backed by the Ethereum app of a Ledger device connected over USB. The key at the
+/// derivation path never leaves the device, every signature is confirmed on the device.
+///
+/// Ledger signs messages, typed data and transactions but not raw digests, so `sign_digest()`
+/// returns an error. The Ethereum app must be open.
+/// ```no_run
+/// use ethereum_private_key_to_address::{DerivationPath, LedgerSigner, Signer};
+///
+/// let signer = LedgerSigner::new(DerivationPath::ethereum(0)).unwrap();
+/// println!("{}", signer.address());
+///
+/// let signature = signer.sign_message(b"hello world").unwrap();
+/// ```
+pub struct LedgerSigner {
+    /// Open HID device, exchanges must not interleave
+    device: Mutex<HidDevice>,
+    /// Derivation path of the key
+    path: DerivationPath,
+    /// Public key at the path
+    public_key: PublicKey,
+}
+
+impl LedgerSigner {
+    /// Opens the first connected Ledger device and fetches the public key at the path
+    pub fn new(path: DerivationPath) -> Result<Self> {
+        let api = HidApi::new().map_err(hid_error)?;
+        let info = api
+            .device_list()
+            .find(|info| {
+                info.vendor_id() == VENDOR_ID
+                    && (info.usage_page() == USAGE_PAGE || info.interface_number() == 0)
+            })
+            .ok_or_else(|| Error::Signer("no Ledger device found".to_string()))?;
+        let device = info.open_device(&api).map_err(hid_error)?;
+
+        let response = exchange(&device, INS_GET_ADDRESS, 0x00, &encode_path(&path))?;
+        let public_key = response
+            .get(1..1 + usize::from(*response.first().unwrap_or(&0)))
+            .ok_or_else(|| invalid_response("public key"))?;
+        let public_key = PublicKey::from_slice(public_key)?;
+
+        Ok(Self {
+            device: Mutex::new(device),
+            path,
+            public_key,
+        })
+    }
+
+    /// Returns the derivation path of the key
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    /// Returns the public key at the path
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// Shows the address on the device and waits for the user to confirm it, so the user can
+    /// check the address isn't replaced by malware on the host
+    pub fn confirm_address(&self) -> Result<()> {
+        self.exchange(INS_GET_ADDRESS, 0x01, &encode_path(&self.path))?;
+        Ok(())
+    }
+
+    /// Signs the EIP-712 typed data. The device shows only the domain separator and the struct
+    /// hash, the Ethereum app must allow blind signing.
+    pub fn sign_typed_data<T: Eip712>(
+        &self,
+        domain: &Eip712Domain,
+        value: &T,
+    ) -> Result<Signature> {
+        self.sign_typed_data_hashes(&domain.separator(), &value.struct_hash())
+    }
+
+    /// Signs the EIP-712 typed data given by the domain separator and the struct hash of the
+    /// message
+    pub fn sign_typed_data_hashes(
+        &self,
+        domain_separator: &[u8; 32],
+        struct_hash: &[u8; 32],
+    ) -> Result<Signature> {
+        let mut data = encode_path(&self.path);
+        data.extend_from_slice(domain_separator);
+        data.extend_from_slice(struct_hash);
+        let response = self.exchange(INS_SIGN_EIP712_HASHED, P1_FIRST, &data)?;
+        self.signature(
+            &hash_with_separator(domain_separator, struct_hash),
+            &response,
+        )
+    }
+
+    /// Sends the data in chunks and returns the response of the last one
+    fn exchange_chunked(&self, ins: u8, data: &[u8]) -> Result<Vec<u8>> {
+        let device = self.device.lock().expect("lock isn't poisoned");
+        let mut response = Vec::new();
+        for (i, chunk) in data.chunks(MAX_CHUNK).enumerate() {
+            let p1 = if i == 0 { P1_FIRST } else { P1_MORE };
+            response = exchange(&device, ins, p1, chunk)?;
+        }
+        Ok(response)
+    }
+
+    /// Sends the APDU to the device and returns the response data
+    fn exchange(&self, ins: u8, p1: u8, data: &[u8]) -> Result<Vec<u8>> {
+        let device = self.device.lock().expect("lock isn't poisoned");
+        exchange(&device, ins, p1, data)
+    }
+
+    /// Parses the `v || r || s` response. The recovery id is found from the public key, as the
+    /// app encodes `v` differently for messages and each transaction type.
+    fn signature(&self, digest: &[u8; 32], response: &[u8]) -> Result<Signature> {
+        if response.len() != 65 {
+            return Err(invalid_response("signature"));
+        }
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&response[1..33]);
+        s.copy_from_slice(&response[33..]);
+        Signature::new(r, s, false)?.recover_parity(digest, &self.public_key)
+    }
+}
+
+impl fmt::Debug for LedgerSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LedgerSigner")
+            .field("path", &format_args!("{}", self.path))
+            .field("address", &format_args!("{}", self.public_key.address()))
+            .finish_non_exhaustive()
+    }
+}
+
+impl Signer for LedgerSigner {
+    fn address(&self) -> Address {
+        self.public_key.address()
+    }
+
+    fn sign_digest(&self, _digest: &[u8; 32]) -> Result<Signature> {
+        Err(Error::Signer(
+            "Ledger can't sign raw digests, only messages, typed data and transactions".to_string(),
+        ))
+    }
+
+    fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        let mut data = encode_path(&self.path);
+        data.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        data.extend_from_slice(message);
+        let response = self.exchange_chunked(INS_SIGN_PERSONAL_MESSAGE, &data)?;
+        self.signature(&hash_message(message), &response)
+    }
+
+    fn sign_transaction(&self, transaction: &dyn Transaction) -> Result<Vec<u8>> {
+        let mut data = encode_path(&self.path);
+        data.extend_from_slice(&transaction.encode_unsigned());
+        let response = self.exchange_chunked(INS_SIGN_TRANSACTION, &data)?;
+        let signature = self.signature(&transaction.signing_hash(), &response)?;
+        Ok(transaction.encode_signed(&signature))
+    }
+}
+
+/// Encodes the path as the number of indexes followed by the big-endian indexes
+fn encode_path(path: &DerivationPath) -> Vec<u8> {
+    let mut encoded = vec![path.len() as u8];
+    for index in path.indexes() {
+        encoded.extend_from_slice(&index.to_be_bytes());
+    }
+    encoded
+}
+
+/// Sends the APDU to the device and returns the response data without the status word
+fn exchange(device: &HidDevice, ins: u8, p1: u8, data: &[u8]) -> Result<Vec<u8>> {
+    let mut apdu = vec![CLA, ins, p1, 0x00, data.len() as u8];
+    apdu.extend_from_slice(data);
+    for packet in encode_packets(&apdu) {
+        // The report id is prepended
+        let mut report = vec![0x00];
+        report.extend_from_slice(&packet);
+        device.write(&report).map_err(hid_error)?;
+    }
+
+    let mut response = read_response(device)?;
+    let status = match response.len() {
+        len if len >= 2 => u16::from_be_bytes([response[len - 2], response[len - 1]]),
+        _ => return Err(invalid_response("status word")),
+    };
+    response.truncate(response.len() - 2);
+    match status {
+        SW_OK => Ok(response),
+        0x6985 => Err(Error::Signer("rejected on the Ledger device".to_string())),
+        0x6a80 => Err(Error::Signer(
+            "Ledger rejected the data, blind signing may have to be enabled".to_string(),
+        )),
+        0x6d00 | 0x6e00 | 0x6e01 => Err(Error::Signer(
+            "Ethereum app isn't open on the Ledger device".to_string(),
+        )),
+        status => Err(Error::Signer(format!(
+            "Ledger returned status {status:04x}"
+        ))),
+    }
+}
+
+/// Splits the APDU into HID packets, the first one starts with the length of the APDU
+fn encode_packets(apdu: &[u8]) -> Vec<[u8; PACKET_SIZE]> {
+    let mut data = (apdu.len() as u16).to_be_bytes().to_vec();
+    data.extend_from_slice(apdu);
+    data.chunks(PACKET_SIZE - 5)
+        .enumerate()
+        .map(|(sequence, chunk)| {
+            let mut packet = [0u8; PACKET_SIZE];
+            packet[..2].copy_from_slice(&CHANNEL.to_be_bytes());
+            packet[2] = TAG_APDU;
+            packet[3..5].copy_from_slice(&(sequence as u16).to_be_bytes());
+            packet[5..5 + chunk.len()].copy_from_slice(chunk);
+            packet
+        })
+        .collect()
+}
+
+/// Reads the HID packets of the response and joins them
+fn read_response(device: &HidDevice) -> Result<Vec<u8>> {
+    let mut response = Vec::new();
+    let mut length = 0;
+    for sequence in 0u16.. {
+        let mut packet = [0u8; PACKET_SIZE];
+        // Blocks until the user confirms or rejects on the device
+        let read = device.read(&mut packet).map_err(hid_error)?;
+        if read < 7
+            || packet[..2] != CHANNEL.to_be_bytes()
+            || packet[2] != TAG_APDU
+            || packet[3..5] != sequence.to_be_bytes()
+        {
+            return Err(invalid_response("HID packet"));
+        }
+        let mut payload = &packet[5..read];
+        if sequence == 0 {
+            length = usize::from(u16::from_be_bytes([payload[0], payload[1]]));
+            payload = &payload[2..];
+        }
+        response.extend_from_slice(payload);
+        if response.len() >= length {
+            break;
+        }
+    }
+    response.truncate(length);
+    Ok(response)
+}
+
+/// Creates the error of the malformed response
+fn invalid_response(part: &str) -> Error {
+    Error::Signer(format!("Ledger returned invalid {part}"))
+}
+
+/// Converts the error of the HID transport
+fn hid_error(error: HidError) -> Error {
+    Error::Signer(format!("Ledger HID error: {error}"))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode_path() {
+        assert_eq!(
+            "058000002c8000003c800000000000000000000003",
+            hex::encode(encode_path(&DerivationPath::ethereum(3)))
+        );
+    }
+
+    #[test]
+    fn test_encode_packets() {
+        let apdu = [0xab; 100];
+        let packets = encode_packets(&apdu);
+        assert_eq!(2, packets.len());
+        assert_eq!([0x01, 0x01, 0x05, 0x00, 0x00, 0x00, 100], packets[0][..7]);
+        assert_eq!([0x01, 0x01, 0x05, 0x00, 0x01], packets[1][..5]);
+        // 57 bytes of the APDU fit into the first packet
+        assert_eq!([0xab; 43], packets[1][5..48]);
+        assert_eq!([0u8; 16], packets[1][48..]);
+    }
+}
@@ -1,7 +1,217 @@
-use anyhow::{Context, Result};
-use secp256k1::{PublicKey, Secp256k1, SecretKey};
-use sha3::{Digest, Keccak256};
-use std::str::FromStr;
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(eth_key_simd, feature(portable_simd))]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use rand_core::CryptoRngCore;
+#[cfg(feature = "std")]
+use rand_core::OsRng;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+
+mod address;
+#[cfg(feature = "alloy-primitives")]
+mod alloy_interop;
+#[cfg(feature = "alloy-signer")]
+mod alloy_signer_interop;
+#[cfg(feature = "async")]
+mod async_signer;
+#[cfg(feature = "aws-kms")]
+mod aws_kms;
+#[cfg(feature = "azure-key-vault")]
+mod azure_key_vault;
+mod batch;
+mod bip32;
+mod derivation_path;
+mod deterministic_deployer;
+mod eip191;
+mod eip712;
+mod eip7702;
+mod entropy;
+mod erc4337;
+mod error;
+#[cfg(feature = "ethers")]
+mod ethers_interop;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "gcp-kms")]
+mod gcp_kms;
+#[cfg(feature = "opencl")]
+mod gpu_vanity;
+#[cfg(feature = "std")]
+mod hd_wallet;
+#[cfg(feature = "k256")]
+mod k256_backend;
+mod keccak_batch;
+mod key_range;
+#[cfg(feature = "keychain")]
+mod keychain;
+#[cfg(feature = "std")]
+mod keystore;
+#[cfg(feature = "std")]
+mod keystore_dir;
+#[cfg(feature = "ledger")]
+mod ledger;
+mod minimal_proxy;
+#[cfg(feature = "std")]
+mod mnemonic;
+#[cfg(feature = "uniffi")]
+mod mobile;
+#[cfg(feature = "openssl")]
+mod openssl_interop;
+#[cfg(feature = "aho-corasick")]
+mod pattern_set;
+mod permit;
+mod permit2;
+#[cfg(feature = "pkcs11")]
+mod pkcs11;
+mod pkcs8;
+#[cfg(feature = "std")]
+mod presale;
+#[cfg(feature = "primitive-types")]
+mod primitive_types_interop;
+mod public_key;
+#[cfg(feature = "python")]
+mod python;
+mod rlp;
+mod safe;
+#[cfg(feature = "std")]
+mod salt_miner;
+#[cfg(feature = "std")]
+mod search;
+#[cfg(not(feature = "k256"))]
+mod secp256k1_backend;
+#[cfg(feature = "serde")]
+mod serde_hex;
+mod signature;
+mod signer;
+#[cfg(feature = "std")]
+mod siwe;
+#[cfg(feature = "std")]
+mod slip39;
+#[cfg(feature = "std")]
+mod slip39_wordlist;
+mod transaction;
+#[cfg(feature = "trezor")]
+mod trezor;
+mod typed_data;
+#[cfg(feature = "std")]
+mod ur;
+mod utils;
+#[cfg(feature = "std")]
+mod vanity;
+#[cfg(feature = "std")]
+mod wallet;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(not(any(feature = "secp256k1", feature = "k256")))]
+compile_error!("enable the `secp256k1` or the `k256` feature to select the curve backend");
+#[cfg(not(any(feature = "std", feature = "k256")))]
+compile_error!("`no_std` builds need the `k256` backend, the `secp256k1` backend requires `std`");
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+#[cfg(feature = "k256")]
+use k256_backend as backend;
+#[cfg(not(feature = "k256"))]
+use secp256k1_backend as backend;
+
+pub use address::{address_from_pubkey_hex, Address};
+#[cfg(feature = "alloy-signer")]
+pub use alloy_signer_interop::AlloySigner;
+#[cfg(feature = "async")]
+pub use async_signer::{AsyncSigner, BlockingSigner};
+#[cfg(feature = "aws-kms")]
+pub use aws_kms::AwsKmsSigner;
+#[cfg(feature = "azure-key-vault")]
+pub use azure_key_vault::{AzureCredential, AzureKeyVaultSigner};
+pub use batch::derive_addresses;
+#[cfg(feature = "rayon")]
+pub use batch::par_derive_addresses;
+pub use bip32::ExtendedPrivateKey;
+pub use derivation_path::{
+    AccountBuilder, ChangeBuilder, CoinTypeBuilder, DerivationPath, IndexBuilder,
+    IntoDerivationPath, PurposeBuilder,
+};
+pub use deterministic_deployer::{
+    deterministic_deployment_address, deterministic_deployment_calldata, DETERMINISTIC_DEPLOYER,
+};
+pub use eip191::{hash_message, hash_with_validator};
+pub use eip712::{hash_typed_data, hash_typed_data_struct, Eip712, Eip712Domain, Eip712Encoder};
+pub use eip7702::{Authorization, SignedAuthorization};
+pub use entropy::{UserEntropy, MIN_USER_ENTROPY_BITS};
+pub use erc4337::{InitCode, SimpleAccountFactory};
+pub use error::{Error, Result};
+#[cfg(feature = "ffi")]
+pub use ffi::{
+    eth_private_key_to_address, eth_private_key_to_checksum_address, eth_private_key_to_public_key,
+    eth_sign_message, ETH_ERROR_INVALID_PRIVATE_KEY, ETH_ERROR_NULL_POINTER, ETH_OK,
+};
+#[cfg(feature = "gcp-kms")]
+pub use gcp_kms::GcpKmsSigner;
+#[cfg(feature = "opencl")]
+pub use gpu_vanity::GpuVanitySearch;
+#[cfg(feature = "std")]
+pub use hd_wallet::HdWallet;
+pub use key_range::KeyRange;
+#[cfg(feature = "keychain")]
+pub use keychain::Keychain;
+#[cfg(feature = "std")]
+pub use keystore::{KdfPreset, KeystoreKdf, KeystoreOptions};
+#[cfg(feature = "std")]
+pub use keystore_dir::{decrypt_keystores, decrypt_keystores_with, KeystoreDir, KeystoreEntry};
+#[cfg(feature = "ledger")]
+pub use ledger::LedgerSigner;
+pub use minimal_proxy::MinimalProxy;
+#[cfg(feature = "std")]
+pub use mnemonic::{Language, Mnemonic, DEFAULT_DERIVATION_PATH};
+#[cfg(feature = "uniffi")]
+pub use mobile::EthereumKey;
+#[cfg(feature = "aho-corasick")]
+pub use pattern_set::PatternSet;
+pub use permit::Permit;
+pub use permit2::{
+    permit2_domain, PermitBatch, PermitDetails, PermitSingle, PermitTransferFrom, PERMIT2_ADDRESS,
+};
+#[cfg(feature = "pkcs11")]
+pub use pkcs11::Pkcs11Signer;
+pub use public_key::PublicKey;
+pub use rlp::RlpItem;
+pub use safe::{SafeProxyFactory, SafeSetup};
+#[cfg(feature = "std")]
+pub use salt_miner::Create2SaltMiner;
+#[cfg(feature = "std")]
+pub use search::{AddressPattern, SearchProgress};
+#[cfg(feature = "serde")]
+pub use serde_hex::{address_lowercase, public_key_compressed};
+pub use signature::{
+    recover_address, recover_address_prehashed, verify, verify_prehashed, Signature, VEncoding,
+};
+pub use signer::Signer;
+#[cfg(feature = "std")]
+pub use siwe::SiweMessage;
+#[cfg(feature = "std")]
+pub use slip39::Slip39;
+pub use transaction::{AccessListItem, Eip1559Transaction, Eip2930Transaction, Transaction};
+#[cfg(feature = "trezor")]
+pub use trezor::{PinCallback, TrezorSigner};
+pub use typed_data::TypedData;
+#[cfg(feature = "std")]
+pub use ur::{CryptoHdKey, EthDataType, EthSignRequest, EthSignature, Ur, UrDecoder};
+#[cfg(feature = "std")]
+pub use vanity::{VanityCheckpoint, VanitySearch, VanitySearchHandle};
+#[cfg(feature = "std")]
+pub use wallet::{Wallet, WalletFormat};
+#[cfg(feature = "wasm")]
+pub use wasm::{
+    is_checksum_address, private_key_to_address, public_key, public_key_to_address,
+    to_checksum_address,
+};
 
 /// PrivateKey struct that contains method that will convert your private key to an ethereum
 /// address
@@ -17,59 +227,131 @@ use std::str::FromStr;
 /// // 2.) Call the `address()` method on  your private key
 /// let address = private_key.address();
 /// ```
-#[derive(Debug, PartialEq, Eq, Clone)]
+///
+/// The key is wiped from memory on drop. The `alloy-primitives` and `primitive-types` features
+/// only parse it from `B256` and `H256`, there is no conversion back, so the key isn't copied into
+/// a type that isn't wiped on drop. Use `secret_bytes()` to get the raw key.
+#[derive(PartialEq, Eq, Clone)]
 pub struct PrivateKey {
     /// Private Key
-    private_key: SecretKey,
+    private_key: backend::SecretKey,
 }
 
 impl FromStr for PrivateKey {
-    type Err = anyhow::Error;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let private_key = s.replace("0x", "");
-        let private_key = SecretKey::from_str(&private_key)
-            .context("Problem parsing private key, check if your private key is correct")?;
+        let private_key = Zeroizing::new(hex::decode(utils::strip_hex_prefix(s))?);
+        Self::from_slice(&private_key)
+    }
+}
 
-        Ok(Self { private_key })
+/// Prints only the address derived from the private key, the private key itself is redacted so it
+/// doesn't end up in logs. If you really need the raw value call `expose_secret_hex()`.
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("address", &format_args!("{}", self.to_address()))
+            .field("private_key", &"<redacted>")
+            .finish()
     }
 }
 
+/// Overwrites the private key when the struct goes out of scope, so the secret doesn't stay in
+/// memory after it's no longer used. This also applies to every clone of the private key.
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        // SecretKey can only hold a valid scalar, so instead of zeroes it's overwritten with one.
+        // The volatile write makes sure the compiler doesn't optimize the write away.
+        let erased = backend::one();
+        unsafe { core::ptr::write_volatile(&mut self.private_key, erased) };
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl ZeroizeOnDrop for PrivateKey {}
+
+#[cfg(not(feature = "k256"))]
 impl From<secp256k1::SecretKey> for PrivateKey {
     fn from(value: secp256k1::SecretKey) -> Self {
         Self { private_key: value }
     }
 }
 
-impl From<&[u8]> for PrivateKey {
-    fn from(value: &[u8]) -> Self {
-        let private_key = SecretKey::from_slice(value).expect("Failed to parse the private key. Check if your encoding to &[u8] is correct and try again. Or you can try the from_str() method");
-        Self { private_key }
+impl TryFrom<&[u8]> for PrivateKey {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
     }
 }
 
-impl From<&[u8; 32]> for PrivateKey {
-    fn from(value: &[u8; 32]) -> Self {
-        let private_key = SecretKey::from_slice(value).expect("Failed to parse the private key. Check if your encoding to &[u8] is correct and try again. Or you can try the from_str() method");
-        Self { private_key }
+impl TryFrom<&[u8; 32]> for PrivateKey {
+    type Error = Error;
+
+    fn try_from(value: &[u8; 32]) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
     }
 }
 
-impl From<[u8; 32]> for PrivateKey {
-    fn from(value: [u8; 32]) -> Self {
-        let private_key = SecretKey::from_slice(&value).expect("Failed to parse the private key. Check if your encoding to &[u8] is correct and try again. Or you can try the from_str() method");
-        Self { private_key }
+impl TryFrom<[u8; 32]> for PrivateKey {
+    type Error = Error;
+
+    fn try_from(value: [u8; 32]) -> Result<Self, Self::Error> {
+        let value = Zeroizing::new(value);
+        Self::from_slice(&value[..])
     }
 }
 
-impl From<Vec<u8>> for PrivateKey {
-    fn from(value: Vec<u8>) -> Self {
-        let private_key = SecretKey::from_slice(&value.to_vec()).expect("Failed to parse the private key. Check if your encoding to &[u8] is correct and try again. Or you can try the from_str() method");
-        Self { private_key }
+impl TryFrom<Vec<u8>> for PrivateKey {
+    type Error = Error;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        let value = Zeroizing::new(value);
+        Self::from_slice(&value)
     }
 }
 
 impl PrivateKey {
+    /// Generates a new random private key using the operating system's random number generator
+    /// ```
+    /// use ethereum_private_key_to_address::PrivateKey;
+    ///
+    /// let private_key = PrivateKey::random();
+    ///
+    /// println!("{}", private_key.address());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn random() -> Self {
+        Self::random_with(&mut OsRng)
+    }
+
+    /// Generates a new random private key using the given cryptographically secure random number
+    /// generator. Use this if you want to seed the key generation from your own entropy source,
+    /// for example an HSM.
+    pub fn random_with<R: CryptoRngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = Zeroizing::new([0u8; 32]);
+        loop {
+            rng.fill_bytes(&mut bytes[..]);
+            // The chance of hitting zero or a value above the curve order is negligible, but
+            // retry instead of failing just in case
+            if let Ok(private_key) = Self::from_slice(&bytes[..]) {
+                return private_key;
+            }
+        }
+    }
+
+    /// Generates a private key deterministically from the given seed.
+    ///
+    /// **Only for tests.** Anyone who knows or guesses the seed can recreate the private key, never
+    /// use keys generated by this method to hold real funds. Requires the `test-utils` feature.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn insecure_random_from_seed(seed: u64) -> Self {
+        use rand_core::SeedableRng;
+
+        Self::random_with(&mut rand_chacha::ChaCha20Rng::seed_from_u64(seed))
+    }
+
     /// Calculates the address from the private key
     /// ```
     /// use ethereum_private_key_to_address::PrivateKey;
@@ -80,20 +362,86 @@ impl PrivateKey {
     /// println!("{}", pk.address());
     /// ```
     pub fn address(&self) -> String {
-        let secp = Secp256k1::new();
-        let public_key = self.private_key.public_key(&secp);
-        let public_key = public_key.serialize_uncompressed()[1..].to_vec();
-        let mut hasher = Keccak256::new();
-        hasher.update(public_key);
-        let address = hasher.finalize();
-        let mut addr = hex::encode(&address[12..32]);
-        addr.insert_str(0, "0x");
-        addr
+        self.to_address().to_string()
+    }
+
+    /// Calculates the address from the private key and returns it as the `Address` struct, which
+    /// gives you access to the raw bytes and the checksummed format
+    /// ```
+    /// use ethereum_private_key_to_address::PrivateKey;
+    /// use std::str::FromStr;
+    ///
+    /// let pk = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+    /// let address = pk.to_address();
+    ///
+    /// println!("{}", address.to_checksum());
+    /// println!("{:?}", address.as_bytes());
+    /// ```
+    pub fn to_address(&self) -> Address {
+        self.to_public_key().address()
+    }
+
+    /// Calculates the address of the contract deployed from this key's address by the transaction
+    /// with the nonce
+    /// ```
+    /// use ethereum_private_key_to_address::PrivateKey;
+    /// use std::str::FromStr;
+    ///
+    /// let pk = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+    ///
+    /// assert_eq!(pk.contract_address(0).to_checksum(), "0x5FbDB2315678afecb367f032d93F642f64180aa3");
+    /// ```
+    pub fn contract_address(&self, nonce: u64) -> Address {
+        Address::create(&self.to_address(), nonce)
+    }
+
+    /// Calculates the public key from the private key and returns it as the `PublicKey` struct
+    /// ```
+    /// use ethereum_private_key_to_address::PrivateKey;
+    /// use std::str::FromStr;
+    ///
+    /// let pk = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+    /// let public_key = pk.to_public_key();
+    ///
+    /// assert_eq!(public_key.address(), pk.to_address());
+    /// ```
+    pub fn to_public_key(&self) -> PublicKey {
+        PublicKey::from_backend(backend::public_key(&self.private_key))
+    }
+
+    /// Returns the raw 32 byte private key. The bytes are wrapped in `Zeroizing` so they are wiped
+    /// from memory once the returned value is dropped.
+    /// ```
+    /// use ethereum_private_key_to_address::PrivateKey;
+    /// use std::str::FromStr;
+    ///
+    /// let pk = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+    /// let secret = pk.secret_bytes();
+    ///
+    /// assert_eq!(PrivateKey::from_slice(&secret[..]).unwrap(), pk);
+    /// ```
+    pub fn secret_bytes(&self) -> Zeroizing<[u8; 32]> {
+        Zeroizing::new(backend::secret_bytes(&self.private_key))
+    }
+
+    /// Returns the private key as a hex string without the `0x` prefix. Be careful where you
+    /// print or store this value, this is the only method that reveals the private key as text.
+    pub fn expose_secret_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(self.secret_bytes()))
     }
 
     /// Converts your private key in the &[u8] format to PrivateKey struct
     pub fn from_slice(slice: &[u8]) -> Result<Self> {
-        let private_key = SecretKey::from_slice(slice).context("Failed to parse given private key. Make sure your encoding is correct or try the from_str() method")?;
+        if slice.len() != 32 {
+            return Err(Error::InvalidLength {
+                expected: "32",
+                actual: slice.len(),
+            });
+        }
+        if slice.iter().all(|byte| *byte == 0) {
+            return Err(Error::ZeroKey);
+        }
+        let private_key = backend::secret_key_from_slice(slice)?;
 
         Ok(Self { private_key })
     }
@@ -101,45 +449,46 @@ impl PrivateKey {
     /// Returns Full 64 byte Public Key from Private Key without 0x04 in the front as a String. 0x04 is used to
     /// specify the type of the public key. 0x04 in front means the public key is uncompressed
     pub fn public_key(&self) -> String {
-        let secp = Secp256k1::new();
-        let public_key = PublicKey::from_secret_key(&secp, &self.private_key);
-        hex::encode(&public_key.serialize_uncompressed()[1..])
+        self.to_public_key().to_hex()
     }
 
     /// Returns Full 65 byte Public Key including the prefix as a String. In this case prefix is 0x04. 0x04 is used
     /// to specify the type of the public key. If you want to get public key without the prefix
     /// call the `public_key()` method.
     pub fn public_key_full(&self) -> String {
-        let secp = Secp256k1::new();
-        let public_key = PublicKey::from_secret_key(&secp, &self.private_key);
-        hex::encode(public_key.serialize_uncompressed())
+        self.to_public_key().to_hex_full()
     }
 
     /// Returns the x-coordiante of the public key as a string.
     pub fn public_key_x(&self) -> String {
-        let secp = Secp256k1::new();
-        let public_key = PublicKey::from_secret_key(&secp, &self.private_key);
-        hex::encode(&public_key.serialize_uncompressed()[1..33])
+        hex::encode(self.to_public_key().x())
     }
 
     /// Returns the y-coordinate of the public key
     pub fn public_key_y(&self) -> String {
-        let secp = Secp256k1::new();
-        let public_key = PublicKey::from_secret_key(&secp, &self.private_key);
-        hex::encode(&public_key.serialize_uncompressed()[33..])
+        hex::encode(self.to_public_key().y())
     }
 
     /// Returns the entire public key in [u8; 65] format
     pub fn public_key_slice(&self) -> [u8; 65] {
-        let secp = Secp256k1::new();
-        let public_key = PublicKey::from_secret_key(&secp, &self.private_key);
-        public_key.serialize_uncompressed()
+        self.to_public_key().serialize_uncompressed()
+    }
+
+    /// Returns the 33 byte compressed public key as a String. The prefix is 0x02 or 0x03
+    /// depending on the parity of the y-coordinate.
+    pub fn public_key_compressed(&self) -> String {
+        self.to_public_key().to_hex_compressed()
+    }
+
+    /// Returns the compressed public key in [u8; 33] format
+    pub fn public_key_compressed_slice(&self) -> [u8; 33] {
+        self.to_public_key().serialize_compressed()
     }
 }
 
 #[cfg(test)]
 pub mod test {
-    use crate::PrivateKey;
+    use crate::{Error, PrivateKey};
     use hex::FromHex;
     use std::str::FromStr;
 
@@ -176,7 +525,7 @@ pub mod test {
         let private_key =
             Vec::from_hex("8b3a350cf5c34c9194ca85829a2df0ec3153be0318b5e2d3348e872092edffba")
                 .unwrap();
-        let private_key = PrivateKey::from(private_key);
+        let private_key = PrivateKey::try_from(private_key).unwrap();
         assert_eq!(
             "0x9965507d1a55bcc2695c58ba16fb37d819b0a4dc",
             private_key.address()
@@ -188,7 +537,7 @@ pub mod test {
         let private_key =
             hex::decode("7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6")
                 .unwrap();
-        let private_key = PrivateKey::from(&private_key[..]);
+        let private_key = PrivateKey::try_from(&private_key[..]).unwrap();
         assert_eq!(
             "0x90f79bf6eb2c4f870365e785982e1f101e93b906",
             private_key.address()
@@ -197,10 +546,11 @@ pub mod test {
 
     #[test]
     fn test_account_from_vec() {
-        let private_key = PrivateKey::from(
+        let private_key = PrivateKey::try_from(
             hex::decode("47e179ec197488593b187f80a00eb0da91f1b9d0b13f8733639f19c30a34926a")
                 .unwrap(),
-        );
+        )
+        .unwrap();
         assert_eq!(
             "0x15d34aaf54267db7d7c367839aaf71a00a2c6a65",
             private_key.address()
@@ -212,13 +562,99 @@ pub mod test {
         let private_key =
             hex::decode("47e179ec197488593b187f80a00eb0da91f1b9d0b13f8733639f19c30a34926a")
                 .unwrap();
-        let private_key = PrivateKey::from(TryInto::<[u8; 32]>::try_into(private_key).unwrap());
+        let private_key =
+            PrivateKey::try_from(TryInto::<[u8; 32]>::try_into(private_key).unwrap()).unwrap();
         assert_eq!(
             "0x15d34aaf54267db7d7c367839aaf71a00a2c6a65",
             private_key.address()
         );
     }
 
+    #[test]
+    fn test_try_from_invalid() {
+        assert!(PrivateKey::try_from(&[0u8; 31][..]).is_err());
+        assert!(PrivateKey::try_from(vec![0u8; 33]).is_err());
+        assert!(PrivateKey::try_from([0u8; 32]).is_err());
+        assert!(PrivateKey::try_from(&[0xffu8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            PrivateKey::from_str("0xzz"),
+            Err(Error::InvalidHex(_))
+        ));
+        assert_eq!(
+            PrivateKey::from_str("0x1234"),
+            Err(Error::InvalidLength {
+                expected: "32",
+                actual: 2
+            })
+        );
+        assert_eq!(PrivateKey::from_slice(&[0u8; 32]), Err(Error::ZeroKey));
+        assert_eq!(
+            PrivateKey::from_slice(&[0xffu8; 32]),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_secret_bytes() {
+        let private_key =
+            hex::decode("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap();
+        let secret = PrivateKey::from_slice(&private_key).unwrap().secret_bytes();
+        assert_eq!(private_key, secret.to_vec());
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let secret = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let private_key = PrivateKey::from_str(secret).unwrap();
+        let debug = format!("{:?}", private_key);
+        assert!(!debug.contains(secret));
+        assert!(debug.contains("<redacted>"));
+        assert!(debug.contains("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"));
+        assert_eq!(secret, private_key.expose_secret_hex().as_str());
+    }
+
+    #[test]
+    fn test_random() {
+        let first = PrivateKey::random();
+        let second = PrivateKey::random();
+        assert_ne!(first, second);
+        assert_eq!(
+            first,
+            PrivateKey::from_slice(&first.secret_bytes()[..]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_random_seeded() {
+        assert_eq!(
+            PrivateKey::insecure_random_from_seed(42),
+            PrivateKey::insecure_random_from_seed(42)
+        );
+        assert_ne!(
+            PrivateKey::insecure_random_from_seed(42),
+            PrivateKey::insecure_random_from_seed(43)
+        );
+    }
+
+    #[test]
+    fn test_to_address() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let address = private_key.to_address();
+        assert_eq!(private_key.address(), address.to_string());
+        assert_eq!(
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            address.to_checksum()
+        );
+    }
+
     #[test]
     fn test_account_from_slice_custom() {
         let private_key =
@@ -0,0 +1,126 @@
+use crate::utils::keccak256;
+use crate::Address;
+
+/// Init code of the EIP-1167 proxy before the implementation address
+const PREFIX: [u8; 20] = [
+    0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3, 0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d,
+    0x3d, 0x36, 0x3d, 0x73,
+];
+
+/// Init code of the EIP-1167 proxy after the implementation address
+const SUFFIX: [u8; 15] = [
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
+
+/// EIP-1167 minimal proxy (clone) of the implementation contract, as deployed by OpenZeppelin
+/// `Clones` and most clone factories. Predicts the address of the clone deployed by `CREATE` or
+/// `CREATE2`.
+/// ```
+/// use std::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, MinimalProxy};
+///
+/// let implementation = Address::from_str("0xbebebebebebebebebebebebebebebebebebebebe").unwrap();
+/// let factory = Address::from_str("0x5fbdb2315678afecb367f032d93f642f64180aa3").unwrap();
+///
+/// let proxy = MinimalProxy::new(implementation);
+/// let clone = proxy.create2_address(&factory, &[0u8; 32]);
+///
+/// assert_eq!(clone, Address::create2_from_code(&factory, &[0u8; 32], &proxy.init_code()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MinimalProxy {
+    /// Contract the proxy delegates all calls to
+    implementation: Address,
+}
+
+impl MinimalProxy {
+    /// Creates the proxy of the implementation contract
+    pub fn new(implementation: Address) -> Self {
+        Self { implementation }
+    }
+
+    /// Returns the implementation contract
+    pub fn implementation(&self) -> &Address {
+        &self.implementation
+    }
+
+    /// Returns the 55 byte init code of the proxy
+    pub fn init_code(&self) -> [u8; 55] {
+        let mut code = [0u8; 55];
+        code[..20].copy_from_slice(&PREFIX);
+        code[20..40].copy_from_slice(self.implementation.as_bytes());
+        code[40..].copy_from_slice(&SUFFIX);
+        code
+    }
+
+    /// Returns the Keccak256 hash of the init code
+    pub fn init_code_hash(&self) -> [u8; 32] {
+        keccak256(&self.init_code())
+    }
+
+    /// Calculates the address of the clone deployed by the factory with `CREATE`, e.g.
+    /// `Clones.clone()`, where the nonce is the nonce of the factory contract.
+    ///
+    /// The address doesn't depend on the implementation, it is `Address::create` of the factory
+    /// and the nonce.
+    pub fn create_address(&self, factory: &Address, nonce: u64) -> Address {
+        Address::create(factory, nonce)
+    }
+
+    /// Calculates the address of the clone deployed by the factory with `CREATE2`, e.g.
+    /// `Clones.cloneDeterministic()` and `Clones.predictDeterministicAddress()`
+    pub fn create2_address(&self, factory: &Address, salt: &[u8; 32]) -> Address {
+        Address::create2(factory, salt, &self.init_code_hash())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_init_code() {
+        let implementation =
+            Address::from_str("0xbebebebebebebebebebebebebebebebebebebebe").unwrap();
+        assert_eq!(
+            "3d602d80600a3d3981f3363d3d373d3d3d363d73bebebebebebebebebebebebebebebebebebebebe5af43d82803e903d91602b57fd5bf3",
+            hex::encode(MinimalProxy::new(implementation).init_code())
+        );
+    }
+
+    #[test]
+    fn test_create2_address() {
+        // `Clones.predictDeterministicAddress(implementation, salt, factory)`
+        let proxy = MinimalProxy::new(
+            Address::from_str("0xbebebebebebebebebebebebebebebebebebebebe").unwrap(),
+        );
+        let factory = Address::from_str("0x5fbdb2315678afecb367f032d93f642f64180aa3").unwrap();
+        let mut salt = [0u8; 32];
+        salt[31] = 1;
+        assert_eq!(
+            "60a146579875aca032c9566ded6fc12bea362d66b7f2aa0f440db7bd8a90a4fe",
+            hex::encode(proxy.init_code_hash())
+        );
+        assert_eq!(
+            Address::from_str("0xe7f08455c6e0f72819a122837fbe5962abac51a0").unwrap(),
+            proxy.create2_address(&factory, &salt)
+        );
+    }
+
+    #[test]
+    fn test_create_address() {
+        let proxy = MinimalProxy::new(
+            Address::from_str("0xbebebebebebebebebebebebebebebebebebebebe").unwrap(),
+        );
+        let factory = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+        assert_eq!(
+            Address::from_str("0x5FbDB2315678afecb367f032d93F642f64180aa3").unwrap(),
+            proxy.create_address(&factory, 0)
+        );
+        assert_eq!(
+            Address::from_str("0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512").unwrap(),
+            proxy.create_address(&factory, 1)
+        );
+    }
+}
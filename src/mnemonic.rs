@@ -0,0 +1,285 @@
+use crate::{Error, ExtendedPrivateKey, IntoDerivationPath, PrivateKey, Result};
+use rand_core::CryptoRngCore;
+use std::fmt;
+use std::str::FromStr;
+use zeroize::Zeroizing;
+
+pub use bip39::Language;
+
+/// Derivation path of the first account used by most Ethereum wallets (MetaMask, Ledger Live, ...)
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// BIP-39 mnemonic phrase that can be generated from fresh entropy or parsed from a string, and
+/// used to derive private keys.
+///
+/// ```
+/// use ethereum_private_key_to_address::{Language, Mnemonic, DEFAULT_DERIVATION_PATH};
+///
+/// let mnemonic = Mnemonic::generate_in(Language::Spanish, 24).unwrap();
+/// let private_key = mnemonic.derive_key(DEFAULT_DERIVATION_PATH).unwrap();
+///
+/// println!("{}", private_key.address());
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    /// Mnemonic
+    mnemonic: bip39::Mnemonic,
+}
+
+impl Mnemonic {
+    /// Generates a new English mnemonic with the given number of words. Supported word counts are
+    /// 12, 15, 18, 21 and 24.
+    pub fn generate(word_count: usize) -> Result<Self> {
+        Self::generate_in(Language::English, word_count)
+    }
+
+    /// Generates a new mnemonic with the given number of words using the wordlist of the given
+    /// language
+    pub fn generate_in(language: Language, word_count: usize) -> Result<Self> {
+        Self::generate_in_with(&mut rand_core::OsRng, language, word_count)
+    }
+
+    /// Generates a new mnemonic with the given number of words and language using the given
+    /// random number generator
+    pub fn generate_in_with<R: CryptoRngCore + ?Sized>(
+        rng: &mut R,
+        language: Language,
+        word_count: usize,
+    ) -> Result<Self> {
+        if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+            return Err(Error::InvalidWordCount(word_count));
+        }
+        // Every 3 words encode 32 bits of entropy
+        let mut entropy = Zeroizing::new(vec![0u8; word_count / 3 * 4]);
+        rng.fill_bytes(&mut entropy);
+        Self::from_entropy_in(language, &entropy)
+    }
+
+    /// Creates the mnemonic from raw entropy. The entropy has to be 16, 20, 24, 28 or 32 bytes
+    /// long.
+    pub fn from_entropy_in(language: Language, entropy: &[u8]) -> Result<Self> {
+        let mnemonic = bip39::Mnemonic::from_entropy_in(language, entropy)
+            .map_err(|error| Error::InvalidMnemonic(error.to_string()))?;
+        Ok(Self { mnemonic })
+    }
+
+    /// Parses the mnemonic from a phrase. The language is detected automatically.
+    pub fn parse(phrase: &str) -> Result<Self> {
+        let mnemonic = bip39::Mnemonic::parse(phrase)
+            .map_err(|error| Error::InvalidMnemonic(error.to_string()))?;
+        Ok(Self { mnemonic })
+    }
+
+    /// Parses the mnemonic from a phrase in the given language
+    pub fn parse_in(language: Language, phrase: &str) -> Result<Self> {
+        let mnemonic = bip39::Mnemonic::parse_in(language, phrase)
+            .map_err(|error| Error::InvalidMnemonic(error.to_string()))?;
+        Ok(Self { mnemonic })
+    }
+
+    /// Returns the mnemonic phrase with words separated by spaces
+    pub fn phrase(&self) -> Zeroizing<String> {
+        Zeroizing::new(self.mnemonic.to_string())
+    }
+
+    /// Returns the language of the mnemonic
+    pub fn language(&self) -> Language {
+        self.mnemonic.language()
+    }
+
+    /// Returns the number of words in the mnemonic
+    pub fn word_count(&self) -> usize {
+        self.mnemonic.word_count()
+    }
+
+    /// Returns the 64 byte BIP-39 seed
+    pub fn to_seed(&self) -> Zeroizing<[u8; 64]> {
+        self.to_seed_with_passphrase("")
+    }
+
+    /// Returns the 64 byte BIP-39 seed protected by the passphrase (sometimes called the 25th
+    /// word). The same mnemonic with a different passphrase produces a completely different seed.
+    pub fn to_seed_with_passphrase(&self, passphrase: &str) -> Zeroizing<[u8; 64]> {
+        Zeroizing::new(self.mnemonic.to_seed(passphrase))
+    }
+
+    /// Derives the private key at the given BIP-32 derivation path
+    pub fn derive_key(&self, path: impl IntoDerivationPath) -> Result<PrivateKey> {
+        self.derive_key_with_passphrase(path, "")
+    }
+
+    /// Derives the private key at the given BIP-32 derivation path from the seed protected by the
+    /// passphrase. This is compatible with the passphrase feature of Trezor and Ledger.
+    pub fn derive_key_with_passphrase(
+        &self,
+        path: impl IntoDerivationPath,
+        passphrase: &str,
+    ) -> Result<PrivateKey> {
+        Ok(self
+            .to_master_key(passphrase)?
+            .derive_path(path)?
+            .into_private_key())
+    }
+
+    /// Returns the BIP-32 master key of the seed protected by the passphrase. Use empty
+    /// passphrase if the mnemonic doesn't have one.
+    pub fn to_master_key(&self, passphrase: &str) -> Result<ExtendedPrivateKey> {
+        ExtendedPrivateKey::from_seed(&self.to_seed_with_passphrase(passphrase)[..])
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// The phrase is redacted, call `phrase()` to get it
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mnemonic")
+            .field("language", &self.language())
+            .field("word_count", &self.word_count())
+            .field("phrase", &"<redacted>")
+            .finish()
+    }
+}
+
+impl PrivateKey {
+    /// Derives the private key from a BIP-39 mnemonic phrase at the given BIP-32 derivation path.
+    /// The checksum of the mnemonic is validated.
+    /// ```
+    /// use ethereum_private_key_to_address::{PrivateKey, DEFAULT_DERIVATION_PATH};
+    ///
+    /// let phrase = "test test test test test test test test test test test junk";
+    /// let private_key = PrivateKey::from_mnemonic(phrase, DEFAULT_DERIVATION_PATH).unwrap();
+    ///
+    /// assert_eq!(private_key.address(), "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+    /// ```
+    pub fn from_mnemonic(phrase: &str, path: impl IntoDerivationPath) -> Result<Self> {
+        Mnemonic::parse(phrase)?.derive_key(path)
+    }
+
+    /// Derives the private key from a BIP-39 mnemonic phrase protected by the passphrase at the
+    /// given BIP-32 derivation path
+    /// ```
+    /// use ethereum_private_key_to_address::{PrivateKey, DEFAULT_DERIVATION_PATH};
+    ///
+    /// let phrase = "test test test test test test test test test test test junk";
+    /// let private_key =
+    ///     PrivateKey::from_mnemonic_with_passphrase(phrase, "TREZOR", DEFAULT_DERIVATION_PATH)
+    ///         .unwrap();
+    ///
+    /// assert_eq!(private_key.address(), "0x9313778b3753108128b9c476ebdd42fbd566f4ed");
+    /// ```
+    pub fn from_mnemonic_with_passphrase(
+        phrase: &str,
+        passphrase: &str,
+        path: impl IntoDerivationPath,
+    ) -> Result<Self> {
+        Mnemonic::parse(phrase)?.derive_key_with_passphrase(path, passphrase)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{DerivationPath, Error, Language, Mnemonic, PrivateKey, DEFAULT_DERIVATION_PATH};
+
+    const PHRASE: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_from_mnemonic() {
+        let private_key = PrivateKey::from_mnemonic(PHRASE, DEFAULT_DERIVATION_PATH).unwrap();
+        assert_eq!(
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            private_key.address()
+        );
+
+        let private_key = PrivateKey::from_mnemonic(PHRASE, "m/44'/60'/0'/0/1").unwrap();
+        assert_eq!(
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+            private_key.address()
+        );
+        assert_eq!(
+            private_key,
+            PrivateKey::from_mnemonic(PHRASE, DerivationPath::ethereum(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generate_mnemonic() {
+        for word_count in [12, 15, 18, 21, 24] {
+            let mnemonic = Mnemonic::generate(word_count).unwrap();
+            assert_eq!(word_count, mnemonic.word_count());
+            assert_eq!(mnemonic, Mnemonic::parse(&mnemonic.phrase()).unwrap());
+        }
+        assert_eq!(Mnemonic::generate(13), Err(Error::InvalidWordCount(13)));
+    }
+
+    #[test]
+    fn test_generate_mnemonic_languages() {
+        for language in [
+            Language::Japanese,
+            Language::Spanish,
+            Language::SimplifiedChinese,
+            Language::French,
+        ] {
+            let mnemonic = Mnemonic::generate_in(language, 12).unwrap();
+            assert_eq!(language, mnemonic.language());
+            assert_eq!(
+                mnemonic,
+                Mnemonic::parse_in(language, &mnemonic.phrase()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_from_entropy() {
+        let mnemonic = Mnemonic::from_entropy_in(Language::English, &[0u8; 16]).unwrap();
+        assert_eq!(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            mnemonic.phrase().as_str()
+        );
+        assert!(!format!("{:?}", mnemonic).contains("abandon"));
+    }
+
+    #[test]
+    fn test_passphrase() {
+        let mnemonic = Mnemonic::from_entropy_in(Language::English, &[0u8; 16]).unwrap();
+        assert_eq!(
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            hex::encode(mnemonic.to_seed_with_passphrase("TREZOR"))
+        );
+
+        let private_key =
+            PrivateKey::from_mnemonic_with_passphrase(PHRASE, "TREZOR", DEFAULT_DERIVATION_PATH)
+                .unwrap();
+        assert_eq!(
+            "0x9313778b3753108128b9c476ebdd42fbd566f4ed",
+            private_key.address()
+        );
+        assert_eq!(
+            PrivateKey::from_mnemonic(PHRASE, DEFAULT_DERIVATION_PATH).unwrap(),
+            PrivateKey::from_mnemonic_with_passphrase(PHRASE, "", DEFAULT_DERIVATION_PATH).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_mnemonic() {
+        let unknown_word = "test test test test test test test test test test test notaword";
+        assert!(matches!(
+            PrivateKey::from_mnemonic(unknown_word, DEFAULT_DERIVATION_PATH),
+            Err(Error::InvalidMnemonic(_))
+        ));
+        assert!(matches!(
+            PrivateKey::from_mnemonic("test test test", DEFAULT_DERIVATION_PATH),
+            Err(Error::InvalidMnemonic(_))
+        ));
+        assert!(matches!(
+            PrivateKey::from_mnemonic(PHRASE, "44'/60'"),
+            Err(Error::InvalidDerivationPath(_))
+        ));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{Address, Error, PrivateKey, PublicKey, Result, Signature, TypedData};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Private key exported to Kotlin and Swift. The key stays in Rust memory and is wiped when the
+/// object is destroyed, the apps only ever get the address, the public key and signatures.
+#[derive(Debug, uniffi::Object)]
+pub struct EthereumKey {
+    /// Private key
+    private_key: PrivateKey,
+}
+
+#[uniffi::export]
+impl EthereumKey {
+    /// Parses the hex private key, the `0x` prefix is optional
+    #[uniffi::constructor]
+    pub fn new(private_key: String) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            private_key: PrivateKey::from_str(&private_key)?,
+        }))
+    }
+
+    /// Generates a new random private key using the operating system's random number generator
+    #[uniffi::constructor]
+    pub fn random() -> Arc<Self> {
+        Arc::new(Self {
+            private_key: PrivateKey::random(),
+        })
+    }
+
+    /// Returns the EIP-55 checksummed address
+    pub fn address(&self) -> String {
+        self.private_key.to_address().to_checksum()
+    }
+
+    /// Returns the 64 byte public key as hex, without the 0x04 prefix
+    pub fn public_key(&self) -> String {
+        self.private_key.public_key()
+    }
+
+    /// Signs the message the same way as `personal_sign`, returns the 65 byte signature as hex
+    pub fn sign_message(&self, message: Vec<u8>) -> String {
+        self.private_key.sign_message(message).to_string()
+    }
+
+    /// Signs the 32 byte hash as is, returns the 65 byte signature as hex
+    pub fn sign_hash(&self, hash: Vec<u8>) -> Result<String> {
+        let hash: [u8; 32] = hash
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::InvalidLength {
+                expected: "32",
+                actual: hash.len(),
+            })?;
+        Ok(self.private_key.sign_hash(&hash).to_string())
+    }
+
+    /// Signs the EIP-712 typed data JSON of `eth_signTypedData_v4`, returns the 65 byte signature
+    /// as hex
+    pub fn sign_typed_data(&self, typed_data: String) -> Result<String> {
+        let typed_data = TypedData::from_str(&typed_data)?;
+        Ok(self
+            .private_key
+            .sign_typed_data_json(&typed_data)?
+            .to_string())
+    }
+}
+
+/// Calculates the checksummed address of the hex public key. Accepts the uncompressed key with or
+/// without the 0x04 prefix and the 33 byte compressed key.
+#[uniffi::export]
+pub fn public_key_to_address(public_key: String) -> Result<String> {
+    Ok(PublicKey::from_str(&public_key)?.address().to_checksum())
+}
+
+/// Converts the address into the EIP-55 checksummed form
+#[uniffi::export]
+pub fn to_checksum_address(address: String) -> Result<String> {
+    Ok(Address::from_str(&address)?.to_checksum())
+}
+
+/// Recovers the checksummed address that signed the message by `personal_sign`
+#[uniffi::export]
+pub fn recover_message_signer(message: Vec<u8>, signature: String) -> Result<String> {
+    let signature = Signature::from_str(&signature)?;
+    Ok(crate::recover_address(message, &signature)?.to_checksum())
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn test_ethereum_key() {
+        let key = EthereumKey::new(PRIVATE_KEY.to_string()).unwrap();
+        assert_eq!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266", key.address());
+        assert_eq!(
+            key.address(),
+            public_key_to_address(key.public_key()).unwrap()
+        );
+
+        let signature = key.sign_message(b"hello world".to_vec());
+        assert_eq!(
+            key.address(),
+            recover_message_signer(b"hello world".to_vec(), signature).unwrap()
+        );
+        assert!(key.sign_hash(vec![0; 31]).is_err());
+        assert!(EthereumKey::new("00".repeat(32)).is_err());
+        assert_eq!(
+            key.address(),
+            to_checksum_address("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string()).unwrap()
+        );
+    }
+}
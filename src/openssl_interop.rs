@@ -0,0 +1,104 @@
+use crate::{Error, PrivateKey, PublicKey};
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::ec::{EcGroup, EcKey, EcPoint, PointConversionForm};
+use openssl::error::ErrorStack;
+use openssl::nid::Nid;
+use openssl::pkey::{HasPublic, Private};
+use zeroize::Zeroizing;
+
+/// Reads the private key of the OpenSSL key, fails if the curve isn't secp256k1
+impl TryFrom<&EcKey<Private>> for PrivateKey {
+    type Error = Error;
+
+    fn try_from(value: &EcKey<Private>) -> Result<Self, Self::Error> {
+        if value.group().curve_name() != Some(Nid::SECP256K1) {
+            return Err(Error::InvalidPrivateKey("curve isn't secp256k1".into()));
+        }
+        let secret = Zeroizing::new(
+            value
+                .private_key()
+                .to_vec_padded(32)
+                .map_err(openssl_error)?,
+        );
+        Self::from_slice(&secret)
+    }
+}
+
+/// Creates the OpenSSL key on the secp256k1 curve with the private and the public key
+impl TryFrom<&PrivateKey> for EcKey<Private> {
+    type Error = Error;
+
+    fn try_from(value: &PrivateKey) -> Result<Self, Self::Error> {
+        let group = EcGroup::from_curve_name(Nid::SECP256K1).map_err(openssl_error)?;
+        let mut context = BigNumContext::new().map_err(openssl_error)?;
+        let secret = BigNum::from_slice(value.secret_bytes().as_slice()).map_err(openssl_error)?;
+        let point = EcPoint::from_bytes(&group, &value.public_key_slice(), &mut context)
+            .map_err(openssl_error)?;
+        EcKey::from_private_components(&group, &secret, &point).map_err(openssl_error)
+    }
+}
+
+/// Reads the public key of the OpenSSL key, private or public only, fails if the curve isn't
+/// secp256k1
+impl<T: HasPublic> TryFrom<&EcKey<T>> for PublicKey {
+    type Error = Error;
+
+    fn try_from(value: &EcKey<T>) -> Result<Self, Self::Error> {
+        if value.group().curve_name() != Some(Nid::SECP256K1) {
+            return Err(Error::InvalidPublicKey);
+        }
+        let mut context = BigNumContext::new().map_err(openssl_error)?;
+        let point = value
+            .public_key()
+            .to_bytes(
+                value.group(),
+                PointConversionForm::UNCOMPRESSED,
+                &mut context,
+            )
+            .map_err(|_| Error::InvalidPublicKey)?;
+        Self::from_slice(&point)
+    }
+}
+
+/// Converts the OpenSSL error stack
+fn openssl_error(error: ErrorStack) -> Error {
+    Error::InvalidPrivateKey(error.to_string())
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use openssl::pkey::Public;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_openssl_conversions() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+
+        let ec_key = EcKey::try_from(&private_key).unwrap();
+        ec_key.check_key().unwrap();
+        assert_eq!(private_key, PrivateKey::try_from(&ec_key).unwrap());
+        assert_eq!(
+            private_key.to_public_key(),
+            PublicKey::try_from(&ec_key).unwrap()
+        );
+
+        let public_only: EcKey<Public> =
+            EcKey::from_public_key(ec_key.group(), ec_key.public_key()).unwrap();
+        assert_eq!(
+            private_key.to_address(),
+            PublicKey::try_from(&public_only).unwrap().address()
+        );
+
+        let other_curve =
+            EcKey::generate(&EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap()).unwrap();
+        assert!(PrivateKey::try_from(&other_curve).is_err());
+        assert_eq!(
+            PublicKey::try_from(&other_curve),
+            Err(Error::InvalidPublicKey)
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{Address, AddressPattern, Error, Result};
+use aho_corasick::AhoCorasick;
+
+/// Set of address patterns matched against an address at once, e.g. the vanities of the whole
+/// team. The hex of every pattern is searched in the address in one pass by an Aho-Corasick
+/// automaton, so hundreds of patterns cost little more than one. Only the patterns whose hex was
+/// found are checked completely.
+/// ```
+/// use std::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, AddressPattern, PatternSet};
+///
+/// let set = PatternSet::new(vec![
+///     AddressPattern::new().with_prefix("0xdead").unwrap(),
+///     AddressPattern::new().with_prefix("0xf39f").unwrap(),
+///     AddressPattern::new().with_suffix("2266").unwrap(),
+/// ])
+/// .unwrap();
+///
+/// let address = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+/// assert_eq!(Some(1), set.matches(&address));
+/// assert_eq!(vec![1, 2], set.matching(&address));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PatternSet {
+    /// Patterns of the set
+    patterns: Vec<AddressPattern>,
+    /// Automaton searching the longest hex condition of each pattern
+    automaton: AhoCorasick,
+    /// Index of the pattern of each needle of the automaton
+    needle_patterns: Vec<usize>,
+    /// Patterns without a hex condition, checked for every address
+    unconditional: Vec<usize>,
+}
+
+impl PatternSet {
+    /// Creates the set of the patterns, fails if the automaton can't be built
+    pub fn new(patterns: Vec<AddressPattern>) -> Result<Self> {
+        let mut needles = Vec::new();
+        let mut needle_patterns = Vec::new();
+        let mut unconditional = Vec::new();
+        for (index, pattern) in patterns.iter().enumerate() {
+            let needle = [pattern.prefix(), pattern.suffix(), pattern.contains()]
+                .into_iter()
+                .max_by_key(|needle| needle.len())
+                .unwrap_or_default();
+            if needle.is_empty() {
+                unconditional.push(index);
+            } else {
+                needles.push(needle.to_string());
+                needle_patterns.push(index);
+            }
+        }
+
+        let automaton = AhoCorasick::builder()
+            .ascii_case_insensitive(true)
+            .build(&needles)
+            .map_err(|error| Error::InvalidPattern(error.to_string()))?;
+        Ok(Self {
+            patterns,
+            automaton,
+            needle_patterns,
+            unconditional,
+        })
+    }
+
+    /// Returns the patterns of the set
+    pub fn patterns(&self) -> &[AddressPattern] {
+        &self.patterns
+    }
+
+    /// Returns the number of patterns
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Returns true if the set has no patterns
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Returns the index of the first pattern the address matches
+    pub fn matches(&self, address: &Address) -> Option<usize> {
+        self.matching(address).first().copied()
+    }
+
+    /// Returns the indexes of all patterns the address matches, in ascending order
+    pub fn matching(&self, address: &Address) -> Vec<usize> {
+        let hex = hex::encode(address.as_bytes());
+        let mut candidates = self.unconditional.clone();
+        candidates.extend(
+            self.automaton
+                .find_overlapping_iter(&hex)
+                .map(|found| self.needle_patterns[found.pattern().as_usize()]),
+        );
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates.retain(|index| self.patterns[*index].matches(address));
+        candidates
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_pattern_set() {
+        let patterns = vec![
+            AddressPattern::new().with_prefix("dead").unwrap(),
+            AddressPattern::new()
+                .with_prefix("f39f")
+                .unwrap()
+                .with_contains("AAD88")
+                .unwrap(),
+            AddressPattern::new()
+                .with_suffix("2266")
+                .unwrap()
+                .with_checksum_case(true),
+            AddressPattern::new()
+                .with_contains("6aB8")
+                .unwrap()
+                .with_checksum_case(true),
+            AddressPattern::new().with_leading_zero_bytes(1).unwrap(),
+            AddressPattern::new(),
+        ];
+        let set = PatternSet::new(patterns).unwrap();
+        assert_eq!(6, set.len());
+
+        let address = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+        assert_eq!(vec![1, 2, 3, 5], set.matching(&address));
+        assert_eq!(Some(1), set.matches(&address));
+
+        let address = Address::from_str("0x00adbeef00000000000000000000000000000000").unwrap();
+        assert_eq!(vec![4, 5], set.matching(&address));
+
+        assert_eq!(None, PatternSet::new(Vec::new()).unwrap().matches(&address));
+    }
+}
@@ -0,0 +1,198 @@
+use crate::eip712::hash_typed_data;
+use crate::utils::uint256;
+use crate::{Address, Eip712, Eip712Domain, Eip712Encoder, PrivateKey};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// EIP-2612 `permit` of an ERC-20 token, which lets the spender spend the tokens of the owner
+/// without an `approve` transaction from the owner.
+///
+/// The domain must match the `DOMAIN_SEPARATOR()` of the token, read the name and the version
+/// from the token contract. The value and the nonce default to zero and the deadline to the
+/// maximum `uint256`, which never expires.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, Eip712Domain, Permit, PrivateKey};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let token = Address::from_str("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap();
+/// let spender = Address::from_str("0x70997970c51812dc3a010c7d01b50e0d17dc79c8").unwrap();
+///
+/// let domain = Eip712Domain::new()
+///     .with_name("USD Coin")
+///     .with_version("2")
+///     .with_chain_id(1)
+///     .with_verifying_contract(token);
+/// let permit = Permit::new(domain, private_key.to_address(), spender)
+///     .with_value(1_000_000)
+///     .with_nonce(0)
+///     .with_deadline(1_700_000_000);
+///
+/// let (v, r, s) = permit.sign(&private_key);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permit {
+    /// Domain of the token
+    domain: Eip712Domain,
+    /// Owner of the tokens, the signer
+    owner: Address,
+    /// Address that is allowed to spend the tokens
+    spender: Address,
+    /// Allowance as 256 bit big-endian integer
+    value: [u8; 32],
+    /// Current `nonces(owner)` of the token as 256 bit big-endian integer
+    nonce: [u8; 32],
+    /// Unix timestamp after which the permit expires as 256 bit big-endian integer
+    deadline: [u8; 32],
+}
+
+impl Permit {
+    /// Creates the permit of the token for the owner and the spender
+    pub fn new(domain: Eip712Domain, owner: Address, spender: Address) -> Self {
+        Self {
+            domain,
+            owner,
+            spender,
+            value: [0u8; 32],
+            nonce: [0u8; 32],
+            deadline: [0xff; 32],
+        }
+    }
+
+    /// Sets the allowance in the smallest unit of the token
+    pub fn with_value(self, value: u128) -> Self {
+        self.with_value_uint256(uint256(value))
+    }
+
+    /// Sets the allowance given as 256 bit big-endian integer, e.g. `[0xff; 32]` for unlimited
+    /// allowance
+    pub fn with_value_uint256(mut self, value: [u8; 32]) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the nonce, the current value of `nonces(owner)` of the token
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = uint256(nonce.into());
+        self
+    }
+
+    /// Sets the unix timestamp after which the permit can't be used anymore
+    pub fn with_deadline(mut self, deadline: u64) -> Self {
+        self.deadline = uint256(deadline.into());
+        self
+    }
+
+    /// Returns the domain of the token
+    pub fn domain(&self) -> &Eip712Domain {
+        &self.domain
+    }
+
+    /// Returns the owner
+    pub fn owner(&self) -> Address {
+        self.owner
+    }
+
+    /// Returns the spender
+    pub fn spender(&self) -> Address {
+        self.spender
+    }
+
+    /// Returns the hash that is signed by the owner
+    pub fn hash(&self) -> [u8; 32] {
+        hash_typed_data(&self.domain, self)
+    }
+
+    /// Signs the permit and returns `(v, r, s)` in the order of the arguments of `permit()`,
+    /// where `v` is 27 or 28
+    pub fn sign(&self, private_key: &PrivateKey) -> (u8, [u8; 32], [u8; 32]) {
+        let signature = private_key.sign_hash(&self.hash());
+        (signature.v(), signature.r(), signature.s())
+    }
+}
+
+impl Eip712 for Permit {
+    fn encode_type() -> String {
+        "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+            .to_string()
+    }
+
+    fn encode_data(&self) -> Vec<u8> {
+        Eip712Encoder::new()
+            .address(&self.owner)
+            .address(&self.spender)
+            .uint256(self.value)
+            .uint256(self.nonce)
+            .uint256(self.deadline)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn permit() -> (PrivateKey, Permit) {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let domain = Eip712Domain::new()
+            .with_name("USD Coin")
+            .with_version("2")
+            .with_chain_id(1)
+            .with_verifying_contract(
+                Address::from_str("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap(),
+            );
+        let spender = Address::from_str("0x70997970c51812dc3a010c7d01b50e0d17dc79c8").unwrap();
+        let permit = Permit::new(domain, private_key.to_address(), spender)
+            .with_value(1_000_000)
+            .with_nonce(3);
+        (private_key, permit)
+    }
+
+    #[test]
+    fn test_permit() {
+        assert_eq!(
+            "6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9",
+            hex::encode(Permit::type_hash())
+        );
+
+        let (private_key, permit) = permit();
+        let permit = permit.with_deadline(1_700_000_000);
+        assert_eq!(
+            "2b5087450b94360e83156a4d8a7ab3a48e7f7594ad7eb4089fe4618a1c682f94",
+            hex::encode(permit.hash())
+        );
+        let (v, r, s) = permit.sign(&private_key);
+        assert_eq!(27, v);
+        assert_eq!(
+            "a06f8b0cbafc12d2e4de4b83779359ba0ac5863d898dc12b2fff51683c6d3f26",
+            hex::encode(r)
+        );
+        assert_eq!(
+            "394ca688efed46b79432e80662f1fa2f0134d99ba2e287fa477eae36b6c0abf6",
+            hex::encode(s)
+        );
+    }
+
+    #[test]
+    fn test_permit_without_deadline() {
+        let (private_key, permit) = permit();
+        assert_eq!(
+            "f7d7921b0dde213f4c74b0dad6be65e18f3143c2382fb0e7b00e0066abd1dcde",
+            hex::encode(permit.hash())
+        );
+        let (v, r, s) = permit.sign(&private_key);
+        assert_eq!(28, v);
+        assert_eq!(
+            "f61a198d04854be021fe5e8d940e2a2cc139a6dd3fb35475653dd8e4c5ca0f21",
+            hex::encode(r)
+        );
+        assert_eq!(
+            "73383c7b06e4552c5e53a60afc2e9007440a58092a110f2c376442ac284cc97e",
+            hex::encode(s)
+        );
+    }
+}
@@ -0,0 +1,453 @@
+use crate::eip712::hash_typed_data;
+use crate::utils::uint256;
+use crate::{Address, Eip712, Eip712Domain, Eip712Encoder, PrivateKey, Signature};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Address of the Uniswap Permit2 contract, the same on every chain
+pub const PERMIT2_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0xd4, 0x73, 0x03, 0x0f, 0x11, 0x6d, 0xde, 0xe9, 0xf6, 0xb4,
+    0x3a, 0xc7, 0x8b, 0xa3,
+]);
+
+/// Largest value of `uint48`, used by the expiration and the nonce of `PermitDetails`
+const MAX_UINT48: u64 = (1 << 48) - 1;
+
+/// Encoded type of `PermitDetails`, referenced by `PermitSingle` and `PermitBatch`
+const PERMIT_DETAILS_TYPE: &str =
+    "PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)";
+
+/// Encoded type of `TokenPermissions`, referenced by `PermitTransferFrom`
+const TOKEN_PERMISSIONS_TYPE: &str = "TokenPermissions(address token,uint256 amount)";
+
+/// Returns the EIP-712 domain of the Permit2 contract on the chain
+pub fn permit2_domain(chain_id: u64) -> Eip712Domain {
+    Eip712Domain::new()
+        .with_name("Permit2")
+        .with_chain_id(chain_id)
+        .with_verifying_contract(PERMIT2_ADDRESS)
+}
+
+/// Allowance of one token given to the spender by `PermitSingle` or `PermitBatch`
+/// (`AllowanceTransfer` of Permit2).
+///
+/// The amount defaults to zero, the expiration to zero which means the allowance expires at the
+/// end of the block, and the nonce to zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermitDetails {
+    /// ERC-20 token
+    token: Address,
+    /// Allowance as 256 bit big-endian integer, at most `uint160`
+    amount: [u8; 32],
+    /// Unix timestamp when the allowance expires
+    expiration: u64,
+    /// Current nonce of the owner, token and spender in Permit2
+    nonce: u64,
+}
+
+impl PermitDetails {
+    /// Creates the allowance of the token
+    pub fn new(token: Address) -> Self {
+        Self {
+            token,
+            amount: [0u8; 32],
+            expiration: 0,
+            nonce: 0,
+        }
+    }
+
+    /// Sets the allowance in the smallest unit of the token
+    pub fn with_amount(mut self, amount: u128) -> Self {
+        self.amount = uint256(amount);
+        self
+    }
+
+    /// Sets the maximum allowance, `uint160` max, which Permit2 treats as unlimited
+    pub fn with_max_amount(mut self) -> Self {
+        self.amount = [0u8; 32];
+        self.amount[12..].copy_from_slice(&[0xff; 20]);
+        self
+    }
+
+    /// Sets the unix timestamp when the allowance expires
+    ///
+    /// # Panics
+    /// If the expiration doesn't fit into `uint48`
+    pub fn with_expiration(mut self, expiration: u64) -> Self {
+        assert!(expiration <= MAX_UINT48, "expiration is uint48");
+        self.expiration = expiration;
+        self
+    }
+
+    /// Sets the nonce, the current value of `allowance(owner, token, spender)` in Permit2
+    ///
+    /// # Panics
+    /// If the nonce doesn't fit into `uint48`
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        assert!(nonce <= MAX_UINT48, "nonce is uint48");
+        self.nonce = nonce;
+        self
+    }
+
+    /// Returns the token
+    pub fn token(&self) -> Address {
+        self.token
+    }
+}
+
+impl Eip712 for PermitDetails {
+    fn encode_type() -> String {
+        PERMIT_DETAILS_TYPE.to_string()
+    }
+
+    fn encode_data(&self) -> Vec<u8> {
+        Eip712Encoder::new()
+            .address(&self.token)
+            .uint256(self.amount)
+            .uint(self.expiration.into())
+            .uint(self.nonce.into())
+            .finish()
+    }
+}
+
+/// Permit2 `PermitSingle`, allowance of one token for the spender.
+///
+/// The signature deadline defaults to the maximum `uint256`, which never expires.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, PermitDetails, PermitSingle, PrivateKey};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let token = Address::from_str("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap();
+/// let spender = Address::from_str("0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD").unwrap();
+///
+/// let details = PermitDetails::new(token)
+///     .with_amount(1_000_000)
+///     .with_expiration(1_700_000_000);
+/// let permit = PermitSingle::new(details, spender).with_sig_deadline(1_700_000_000);
+///
+/// let signature = permit.sign(&private_key, 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermitSingle {
+    /// Allowance of the token
+    details: PermitDetails,
+    /// Address that is allowed to spend the tokens
+    spender: Address,
+    /// Unix timestamp after which the signature can't be used as 256 bit big-endian integer
+    sig_deadline: [u8; 32],
+}
+
+impl PermitSingle {
+    /// Creates the permit of the allowance for the spender
+    pub fn new(details: PermitDetails, spender: Address) -> Self {
+        Self {
+            details,
+            spender,
+            sig_deadline: [0xff; 32],
+        }
+    }
+
+    /// Sets the unix timestamp after which the signature can't be used
+    pub fn with_sig_deadline(mut self, sig_deadline: u64) -> Self {
+        self.sig_deadline = uint256(sig_deadline.into());
+        self
+    }
+
+    /// Returns the hash that is signed by the owner for Permit2 on the chain
+    pub fn hash(&self, chain_id: u64) -> [u8; 32] {
+        hash_typed_data(&permit2_domain(chain_id), self)
+    }
+
+    /// Signs the permit for Permit2 on the chain
+    pub fn sign(&self, private_key: &PrivateKey, chain_id: u64) -> Signature {
+        private_key.sign_hash(&self.hash(chain_id))
+    }
+}
+
+impl Eip712 for PermitSingle {
+    fn encode_type() -> String {
+        format!(
+            "PermitSingle(PermitDetails details,address spender,uint256 sigDeadline){PERMIT_DETAILS_TYPE}"
+        )
+    }
+
+    fn encode_data(&self) -> Vec<u8> {
+        Eip712Encoder::new()
+            .value(&self.details)
+            .address(&self.spender)
+            .uint256(self.sig_deadline)
+            .finish()
+    }
+}
+
+/// Permit2 `PermitBatch`, allowances of more tokens for the spender in one signature.
+///
+/// The signature deadline defaults to the maximum `uint256`, which never expires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermitBatch {
+    /// Allowances of the tokens
+    details: Vec<PermitDetails>,
+    /// Address that is allowed to spend the tokens
+    spender: Address,
+    /// Unix timestamp after which the signature can't be used as 256 bit big-endian integer
+    sig_deadline: [u8; 32],
+}
+
+impl PermitBatch {
+    /// Creates the permit of the allowances for the spender
+    pub fn new(details: Vec<PermitDetails>, spender: Address) -> Self {
+        Self {
+            details,
+            spender,
+            sig_deadline: [0xff; 32],
+        }
+    }
+
+    /// Sets the unix timestamp after which the signature can't be used
+    pub fn with_sig_deadline(mut self, sig_deadline: u64) -> Self {
+        self.sig_deadline = uint256(sig_deadline.into());
+        self
+    }
+
+    /// Returns the hash that is signed by the owner for Permit2 on the chain
+    pub fn hash(&self, chain_id: u64) -> [u8; 32] {
+        hash_typed_data(&permit2_domain(chain_id), self)
+    }
+
+    /// Signs the permit for Permit2 on the chain
+    pub fn sign(&self, private_key: &PrivateKey, chain_id: u64) -> Signature {
+        private_key.sign_hash(&self.hash(chain_id))
+    }
+}
+
+impl Eip712 for PermitBatch {
+    fn encode_type() -> String {
+        format!(
+            "PermitBatch(PermitDetails[] details,address spender,uint256 sigDeadline){PERMIT_DETAILS_TYPE}"
+        )
+    }
+
+    fn encode_data(&self) -> Vec<u8> {
+        Eip712Encoder::new()
+            .array(self.details.iter().map(|details| details.struct_hash()))
+            .address(&self.spender)
+            .uint256(self.sig_deadline)
+            .finish()
+    }
+}
+
+/// Permit2 `PermitTransferFrom` (`SignatureTransfer` of Permit2), one time transfer of the token
+/// by the spender without a standing allowance.
+///
+/// The spender is the contract that calls `permitTransferFrom()` on Permit2. The amount and the
+/// nonce default to zero and the deadline to the maximum `uint256`, which never expires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermitTransferFrom {
+    /// ERC-20 token
+    token: Address,
+    /// Maximum amount that can be transferred as 256 bit big-endian integer
+    amount: [u8; 32],
+    /// Address that is allowed to transfer the tokens
+    spender: Address,
+    /// Unordered nonce of the owner as 256 bit big-endian integer
+    nonce: [u8; 32],
+    /// Unix timestamp after which the signature can't be used as 256 bit big-endian integer
+    deadline: [u8; 32],
+}
+
+impl PermitTransferFrom {
+    /// Creates the permit of the token transfer for the spender
+    pub fn new(token: Address, spender: Address) -> Self {
+        Self {
+            token,
+            amount: [0u8; 32],
+            spender,
+            nonce: [0u8; 32],
+            deadline: [0xff; 32],
+        }
+    }
+
+    /// Sets the maximum amount in the smallest unit of the token
+    pub fn with_amount(mut self, amount: u128) -> Self {
+        self.amount = uint256(amount);
+        self
+    }
+
+    /// Sets the nonce, any nonce of the owner that wasn't used yet
+    pub fn with_nonce(mut self, nonce: u128) -> Self {
+        self.nonce = uint256(nonce);
+        self
+    }
+
+    /// Sets the unix timestamp after which the signature can't be used
+    pub fn with_deadline(mut self, deadline: u64) -> Self {
+        self.deadline = uint256(deadline.into());
+        self
+    }
+
+    /// Returns the hash that is signed by the owner for Permit2 on the chain
+    pub fn hash(&self, chain_id: u64) -> [u8; 32] {
+        hash_typed_data(&permit2_domain(chain_id), self)
+    }
+
+    /// Signs the permit for Permit2 on the chain
+    pub fn sign(&self, private_key: &PrivateKey, chain_id: u64) -> Signature {
+        private_key.sign_hash(&self.hash(chain_id))
+    }
+}
+
+impl Eip712 for PermitTransferFrom {
+    fn encode_type() -> String {
+        format!(
+            "PermitTransferFrom(TokenPermissions permitted,address spender,uint256 nonce,uint256 deadline){TOKEN_PERMISSIONS_TYPE}"
+        )
+    }
+
+    fn encode_data(&self) -> Vec<u8> {
+        let permitted = TokenPermissions {
+            token: self.token,
+            amount: self.amount,
+        };
+        Eip712Encoder::new()
+            .value(&permitted)
+            .address(&self.spender)
+            .uint256(self.nonce)
+            .uint256(self.deadline)
+            .finish()
+    }
+}
+
+/// Token and amount of `PermitTransferFrom`
+struct TokenPermissions {
+    /// ERC-20 token
+    token: Address,
+    /// Maximum amount as 256 bit big-endian integer
+    amount: [u8; 32],
+}
+
+impl Eip712 for TokenPermissions {
+    fn encode_type() -> String {
+        TOKEN_PERMISSIONS_TYPE.to_string()
+    }
+
+    fn encode_data(&self) -> Vec<u8> {
+        Eip712Encoder::new()
+            .address(&self.token)
+            .uint256(self.amount)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use core::str::FromStr;
+
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+    const SPENDER: &str = "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD";
+
+    fn address(address: &str) -> Address {
+        Address::from_str(address).unwrap()
+    }
+
+    fn private_key() -> PrivateKey {
+        PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+            .unwrap()
+    }
+
+    fn usdc_details() -> PermitDetails {
+        PermitDetails::new(address(USDC))
+            .with_amount(1_000_000)
+            .with_expiration(1_700_000_000)
+    }
+
+    #[test]
+    fn test_type_hashes() {
+        // Constants from the Permit2 contracts
+        for (expected, type_hash) in [
+            (
+                "65626cad6cb96493bf6f5ebea28756c966f023ab9e8a83a7101849d5573b3678",
+                PermitDetails::type_hash(),
+            ),
+            (
+                "f3841cd1ff0085026a6327b620b67997ce40f282c88a8e905a7a5626e310f3d0",
+                PermitSingle::type_hash(),
+            ),
+            (
+                "af1b0d30d2cab0380e68f0689007e3254993c596f2fdd0aaa7f4d04f79440863",
+                PermitBatch::type_hash(),
+            ),
+            (
+                "939c21a48a8dbe3a9a2404a1d46691e4d39f6583d6ec6b35714604c986d80106",
+                PermitTransferFrom::type_hash(),
+            ),
+        ] {
+            assert_eq!(expected, hex::encode(type_hash));
+        }
+        assert_eq!(
+            address("0x000000000022D473030F116dDEE9F6B43aC78BA3"),
+            PERMIT2_ADDRESS
+        );
+        assert_eq!(
+            "866a5aba21966af95d6c7ab78eb2b2fc913915c28be3b9aa07cc04ff903e3f28",
+            hex::encode(permit2_domain(1).separator())
+        );
+    }
+
+    #[test]
+    fn test_permit_single() {
+        let permit =
+            PermitSingle::new(usdc_details(), address(SPENDER)).with_sig_deadline(1_700_000_000);
+        assert_eq!(
+            "0cdcb2ce4e694f2680ab782e6132de6b0f5b8ebd22c687c4709dd10bf44b306e",
+            hex::encode(permit.hash(1))
+        );
+        assert_eq!(
+            "0x18983b0434c0a2b5852c6d6a2f1afbfca102f768b4ceadffd3eb25066869a44f7b7ceb9b75854072133d0c6c11d7a9310642355c88434e4f287484e8844b4a521b",
+            permit.sign(&private_key(), 1).to_string()
+        );
+    }
+
+    #[test]
+    fn test_permit_batch() {
+        let weth = PermitDetails::new(address(WETH))
+            .with_max_amount()
+            .with_expiration(1_700_003_600)
+            .with_nonce(5);
+        let permit = PermitBatch::new(vec![usdc_details(), weth], address(SPENDER))
+            .with_sig_deadline(1_700_000_000);
+        assert_eq!(
+            "4a5dbad9d58c4d56b6df23a2d0d8c3d727085bb5ea8169e45d3baa00ecb3fc17",
+            hex::encode(permit.hash(1))
+        );
+        assert_eq!(
+            "0xcf7ba8c55057ad4e4f65c8ffc2bb9f6291b74efe0e133ccf9eebb589254d37665e97d3c381281dada1b1f392070ce06047e7c2c9900373bd2d821eaafa23ab631c",
+            permit.sign(&private_key(), 1).to_string()
+        );
+    }
+
+    #[test]
+    fn test_permit_transfer_from() {
+        let permit = PermitTransferFrom::new(address(USDC), address(SPENDER))
+            .with_amount(1_000_000)
+            .with_nonce(7)
+            .with_deadline(1_700_000_000);
+        assert_eq!(
+            "6143ef0a17ac57b34947506a59b319062b76f4c7566d57b8216558bb167f9782",
+            hex::encode(permit.hash(1))
+        );
+        assert_eq!(
+            "0xa84d7e61183df6a06af5adf603ab1ffadcf68e93e74abbd15e1871e4ef54b5aa72ce0ba3a6d53e997d505296f2e8e042057a7834f314311417dd6b606725e97a1b",
+            permit.sign(&private_key(), 1).to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "nonce is uint48")]
+    fn test_nonce_overflow() {
+        PermitDetails::new(address(USDC)).with_nonce(1 << 48);
+    }
+}
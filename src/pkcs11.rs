@@ -0,0 +1,200 @@
+use crate::{Address, Error, PublicKey, Result, Signature, Signer};
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+use std::fmt;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// DER encoded OID of the `secp256k1` curve, the `CKA_EC_PARAMS` of the keys
+const SECP256K1_PARAMS: [u8; 7] = [0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+/// Signer backed by a secp256k1 key of a PKCS#11 token, e.g. SoftHSM, Thales Luna, YubiHSM 2 or
+/// any other HSM with a PKCS#11 module. The private key never leaves the token. YubiKey PIV
+/// can't be used, it doesn't support secp256k1.
+///
+/// The private and the public key objects are found by their `CKA_LABEL`. The token signs with
+/// `CKM_ECDSA` and returns only r and s, so the signature is normalized and the recovery id is
+/// found locally from the public key.
+/// ```no_run
+/// use ethereum_private_key_to_address::{Pkcs11Signer, Signer};
+///
+/// let signer = Pkcs11Signer::new(
+///     "/usr/lib/softhsm/libsofthsm2.so",
+///     "ethereum",
+///     "1234",
+///     "signer",
+/// )
+/// .unwrap();
+/// let signature = signer.sign_message(b"hello world").unwrap();
+/// ```
+pub struct Pkcs11Signer {
+    /// Logged in session, PKCS#11 sessions can't be used from multiple threads at once
+    session: Mutex<Session>,
+    /// Handle of the private key
+    private_key: ObjectHandle,
+    /// Label of the key
+    key_label: String,
+    /// Public key of the key
+    public_key: PublicKey,
+}
+
+impl Pkcs11Signer {
+    /// Loads the PKCS#11 module, logs in to the token with the label as the user and finds the
+    /// key pair with the label
+    pub fn new(
+        module: impl AsRef<Path>,
+        token_label: &str,
+        pin: &str,
+        key_label: &str,
+    ) -> Result<Self> {
+        let pkcs11 = Pkcs11::new(module).map_err(pkcs11_error)?;
+        pkcs11
+            .initialize(CInitializeArgs::OsThreads)
+            .map_err(pkcs11_error)?;
+
+        let mut slot = None;
+        for candidate in pkcs11.get_slots_with_token().map_err(pkcs11_error)? {
+            let info = pkcs11.get_token_info(candidate).map_err(pkcs11_error)?;
+            if info.label() == token_label {
+                slot = Some(candidate);
+                break;
+            }
+        }
+        let slot =
+            slot.ok_or_else(|| Error::Signer(format!("PKCS#11 token {token_label} not found")))?;
+
+        let session = pkcs11.open_ro_session(slot).map_err(pkcs11_error)?;
+        session
+            .login(UserType::User, Some(&AuthPin::new(pin.to_string())))
+            .map_err(pkcs11_error)?;
+
+        let private_key = find_key(&session, ObjectClass::PRIVATE_KEY, key_label)?;
+        let public_key = find_key(&session, ObjectClass::PUBLIC_KEY, key_label)?;
+        let attributes = session
+            .get_attributes(
+                public_key,
+                &[AttributeType::EcParams, AttributeType::EcPoint],
+            )
+            .map_err(pkcs11_error)?;
+
+        let mut point = None;
+        for attribute in attributes {
+            match attribute {
+                Attribute::EcParams(params) if params != SECP256K1_PARAMS => {
+                    return Err(Error::Signer(format!(
+                        "PKCS#11 key {key_label} isn't a secp256k1 key"
+                    )))
+                }
+                Attribute::EcPoint(encoded) => point = Some(decode_ec_point(&encoded)?),
+                _ => {}
+            }
+        }
+        let public_key = point
+            .ok_or_else(|| Error::Signer(format!("PKCS#11 key {key_label} has no public point")))?;
+
+        Ok(Self {
+            session: Mutex::new(session),
+            private_key,
+            key_label: key_label.to_string(),
+            public_key,
+        })
+    }
+
+    /// Returns the label of the key
+    pub fn key_label(&self) -> &str {
+        &self.key_label
+    }
+
+    /// Returns the public key of the key
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+}
+
+impl fmt::Debug for Pkcs11Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pkcs11Signer")
+            .field("key_label", &self.key_label)
+            .field("address", &format_args!("{}", self.public_key.address()))
+            .finish_non_exhaustive()
+    }
+}
+
+impl Signer for Pkcs11Signer {
+    fn address(&self) -> Address {
+        self.public_key.address()
+    }
+
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Signature> {
+        let rs = self
+            .session
+            .lock()
+            .expect("lock isn't poisoned")
+            .sign(&Mechanism::Ecdsa, self.private_key, digest)
+            .map_err(pkcs11_error)?;
+        if rs.len() != 64 {
+            return Err(Error::Signer(format!(
+                "PKCS#11 token returned {} byte signature, expected 64",
+                rs.len()
+            )));
+        }
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&rs[..32]);
+        s.copy_from_slice(&rs[32..]);
+        Signature::new(r, s, false)?.recover_parity(digest, &self.public_key)
+    }
+}
+
+/// Finds the only key object of the class with the label
+fn find_key(session: &Session, class: ObjectClass, label: &str) -> Result<ObjectHandle> {
+    let objects = session
+        .find_objects(&[
+            Attribute::Class(class),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])
+        .map_err(pkcs11_error)?;
+    match objects[..] {
+        [object] => Ok(object),
+        [] => Err(Error::Signer(format!(
+            "PKCS#11 key with label {label} not found"
+        ))),
+        _ => Err(Error::Signer(format!(
+            "multiple PKCS#11 keys with label {label}"
+        ))),
+    }
+}
+
+/// Decodes the `CKA_EC_POINT`, the uncompressed point wrapped in DER `OCTET STRING`. Some
+/// modules return the point without the wrapping.
+fn decode_ec_point(encoded: &[u8]) -> Result<PublicKey> {
+    match encoded {
+        [0x04, 0x41, point @ ..] if point.len() == 65 => PublicKey::from_slice(point),
+        point => PublicKey::from_slice(point),
+    }
+}
+
+/// Converts the error of the PKCS#11 module
+fn pkcs11_error(error: cryptoki::error::Error) -> Error {
+    Error::Signer(format!("PKCS#11 error: {error}"))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_ec_point() {
+        let point = hex::decode("048318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed753547f11ca8696646f2f3acb08e31016afac23e630c5d11f59f61fef57b0d2aa5").unwrap();
+        let public_key = PublicKey::from_slice(&point).unwrap();
+
+        let mut wrapped = vec![0x04, 0x41];
+        wrapped.extend_from_slice(&point);
+        assert_eq!(public_key, decode_ec_point(&wrapped).unwrap());
+        assert_eq!(public_key, decode_ec_point(&point).unwrap());
+        assert!(decode_ec_point(&wrapped[..66]).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use crate::utils::keccak256;
+use crate::{Address, Error, PrivateKey, Result};
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, KeyIvInit};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::str::FromStr;
+use zeroize::Zeroizing;
+
+type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
+
+/// Wallet JSON file from the 2014 Ethereum presale
+#[derive(Deserialize)]
+struct PresaleWallet {
+    /// IV followed by the encrypted seed
+    #[serde(with = "hex")]
+    encseed: Vec<u8>,
+    /// Address of the key without the `0x` prefix
+    ethaddr: String,
+}
+
+impl PrivateKey {
+    /// Decrypts the private key from the 2014 Ethereum presale wallet JSON (the file with the
+    /// `encseed` field). Fails with `Error::InvalidPassword` if the decrypted key doesn't match
+    /// the address stored in the wallet.
+    /// ```no_run
+    /// use ethereum_private_key_to_address::PrivateKey;
+    ///
+    /// let json = std::fs::read_to_string("ethereum_wallet_backup.json").unwrap();
+    /// let private_key = PrivateKey::from_presale_wallet(&json, "password").unwrap();
+    ///
+    /// println!("{}", private_key.address());
+    /// ```
+    pub fn from_presale_wallet(json: &str, password: &str) -> Result<Self> {
+        let invalid = |reason: &str| Error::InvalidKeystore(reason.to_string());
+
+        let wallet: PresaleWallet =
+            serde_json::from_str(json).map_err(|error| invalid(&error.to_string()))?;
+        let address = Address::from_str(&wallet.ethaddr)?;
+        if wallet.encseed.len() < 32 || wallet.encseed.len() % 16 != 0 {
+            return Err(invalid("encrypted seed has invalid length"));
+        }
+
+        let mut key = Zeroizing::new([0u8; 16]);
+        pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), password.as_bytes(), 2000, &mut *key);
+
+        let (iv, ciphertext) = wallet.encseed.split_at(16);
+        let mut seed = Zeroizing::new(ciphertext.to_vec());
+        let seed = Aes128CbcDec::new_from_slices(&key[..], iv)
+            .expect("key and IV are 16 bytes long")
+            .decrypt_padded_mut::<Pkcs7>(&mut seed)
+            .map_err(|_| Error::InvalidPassword)?;
+
+        let private_key = Self::from_slice(&Zeroizing::new(keccak256(seed))[..])?;
+        if private_key.to_address() != address {
+            return Err(Error::InvalidPassword);
+        }
+        Ok(private_key)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{Error, PrivateKey};
+
+    // Presale wallet from the geth test suite, the password is "foo"
+    const WALLET: &str = r#"{
+        "encseed": "26d87f5f2bf9835f9a47eefae571bc09f9107bb13d54ff12a4ec095d01f83897494cf34f7bed2ed34126ecba9db7b62de56c9d7cd136520a0427bfb11b8954ba7ac39b90d4650d3448e31185affcd74226a68f1e94b1108e6e0a4a91cdd83eba",
+        "ethaddr": "d4584b5f6229b7be90727b0fc8c6b91bb427821f",
+        "email": "gustav.simonsson@gmail.com",
+        "btcaddr": "1EVknXyFC68kKNLkh6YnKzW41svSRoaAcx"
+    }"#;
+
+    #[test]
+    fn test_from_presale_wallet() {
+        let private_key = PrivateKey::from_presale_wallet(WALLET, "foo").unwrap();
+        assert_eq!(
+            "0xd4584b5f6229b7be90727b0fc8c6b91bb427821f",
+            private_key.address()
+        );
+        assert_eq!(
+            "7e9ffb1a67f4e33c99430b7582cf52eee7708610c92194d658341205444c3bb7",
+            private_key.expose_secret_hex().as_str()
+        );
+    }
+
+    #[test]
+    fn test_invalid_presale_wallet() {
+        assert_eq!(
+            PrivateKey::from_presale_wallet(WALLET, "bar"),
+            Err(Error::InvalidPassword)
+        );
+        let wrong_address = WALLET.replace("d4584b5f", "d4584b5e");
+        assert_eq!(
+            PrivateKey::from_presale_wallet(&wrong_address, "foo"),
+            Err(Error::InvalidPassword)
+        );
+        assert!(matches!(
+            PrivateKey::from_presale_wallet(r#"{"encseed": "00"}"#, "foo"),
+            Err(Error::InvalidKeystore(_))
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{Address, Error, PrivateKey};
+use primitive_types::{H160, H256};
+
+impl From<Address> for H160 {
+    fn from(value: Address) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<H160> for Address {
+    fn from(value: H160) -> Self {
+        Self::from(value.0)
+    }
+}
+
+/// Parses the 32 byte big-endian private key, fails if it's zero or not below the curve order.
+/// There is no conversion back, see `PrivateKey`. Digests are `[u8; 32]` which converts into
+/// `H256` as is.
+impl TryFrom<H256> for PrivateKey {
+    type Error = Error;
+
+    fn try_from(value: H256) -> Result<Self, Self::Error> {
+        Self::from_slice(value.as_bytes())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::hash_message;
+
+    #[test]
+    fn test_primitive_types_conversions() {
+        // `from_str()` of the hashes needs the `rustc-hex` feature, build them from the bytes
+        let private_key = PrivateKey::try_from(H256::from_slice(
+            &hex::decode("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap(),
+        ))
+        .unwrap();
+        assert!(PrivateKey::try_from(H256::zero()).is_err());
+
+        let address = private_key.to_address();
+        let h160 = H160::from(address);
+        assert_eq!(
+            H160::from_slice(&hex::decode("f39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap()),
+            h160
+        );
+        assert_eq!(address, Address::from(h160));
+
+        let digest = hash_message("hello world");
+        assert_eq!(digest, H256::from(digest).0);
+    }
+}
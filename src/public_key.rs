@@ -0,0 +1,355 @@
+use crate::utils::strip_hex_prefix;
+use crate::{backend, Address, Error, Result};
+use alloc::string::String;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use core::fmt;
+use core::str::FromStr;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+/// DER encoded `AlgorithmIdentifier` of `id-ecPublicKey` with the `secp256k1` curve
+pub(crate) const SPKI_ALGORITHM: [u8; 18] = [
+    0x30, 0x10, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x05, 0x2b, 0x81, 0x04,
+    0x00, 0x0a,
+];
+
+/// PublicKey struct that lets you work with the public key and calculate the address without
+/// holding the private key.
+///
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::PublicKey;
+///
+/// let public_key = PublicKey::from_str("8318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed753547f11ca8696646f2f3acb08e31016afac23e630c5d11f59f61fef57b0d2aa5").unwrap();
+///
+/// assert_eq!(public_key.address().to_string(), "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct PublicKey {
+    /// Public Key
+    public_key: backend::PublicKey,
+}
+
+impl PublicKey {
+    /// Wraps the public key of the curve backend
+    pub(crate) fn from_backend(public_key: backend::PublicKey) -> Self {
+        Self { public_key }
+    }
+
+    /// Returns the public key of the curve backend
+    pub(crate) fn as_backend(&self) -> &backend::PublicKey {
+        &self.public_key
+    }
+
+    /// Converts your public key in the &[u8] format to PublicKey struct. Accepts the 65 byte
+    /// uncompressed key with the 0x04 prefix, the 64 byte key without the prefix, or the 33 byte
+    /// compressed key.
+    pub fn from_slice(slice: &[u8]) -> Result<Self> {
+        let public_key = match slice.len() {
+            64 => {
+                let mut full = [0u8; 65];
+                full[0] = 0x04;
+                full[1..].copy_from_slice(slice);
+                backend::public_key_from_slice(&full)
+            }
+            33 | 65 => backend::public_key_from_slice(slice),
+            len => {
+                return Err(Error::InvalidLength {
+                    expected: "33, 64 or 65",
+                    actual: len,
+                })
+            }
+        }?;
+
+        Ok(Self { public_key })
+    }
+
+    /// Parses the DER encoded X.509 `SubjectPublicKeyInfo` of a secp256k1 key, the format
+    /// returned by KMS and HSM APIs. The point can be compressed or uncompressed.
+    pub fn from_spki_der(der: &[u8]) -> Result<Self> {
+        let [0x30, length, info @ ..] = der else {
+            return Err(Error::InvalidPublicKey);
+        };
+        let Some([0x03, point_length, 0x00, point @ ..]) = info.strip_prefix(&SPKI_ALGORITHM)
+        else {
+            return Err(Error::InvalidPublicKey);
+        };
+        // The 64 byte form without the 0x04 prefix isn't valid in DER
+        if *length as usize != info.len()
+            || *point_length as usize != point.len() + 1
+            || point.len() == 64
+        {
+            return Err(Error::InvalidPublicKey);
+        }
+        Self::from_slice(point)
+    }
+
+    /// Parses the PEM encoded `SubjectPublicKeyInfo` of a secp256k1 key (`-----BEGIN PUBLIC
+    /// KEY-----`), the format returned by Cloud KMS APIs
+    pub fn from_spki_pem(pem: &str) -> Result<Self> {
+        let body = pem
+            .trim()
+            .strip_prefix("-----BEGIN PUBLIC KEY-----")
+            .and_then(|body| body.strip_suffix("-----END PUBLIC KEY-----"))
+            .ok_or(Error::InvalidPublicKey)?;
+        let body: String = body.split_whitespace().collect();
+        let der = STANDARD.decode(body).map_err(|_| Error::InvalidPublicKey)?;
+        Self::from_spki_der(&der)
+    }
+
+    /// Recovers the public key that signed the 32 byte digest from the 64 byte `r || s`
+    /// signature and the recovery id (0 or 1, or 2 and 3 in the rare case r overflowed the curve
+    /// order). Useful when the key itself is needed, e.g. to encrypt to the signer.
+    /// ```
+    /// use core::str::FromStr;
+    /// use ethereum_private_key_to_address::{PrivateKey, PublicKey};
+    ///
+    /// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+    /// let digest = [1u8; 32];
+    /// let signature = private_key.sign_hash(&digest);
+    ///
+    /// let public_key = PublicKey::recover(&digest, &signature.to_rs(), signature.recovery_id()).unwrap();
+    ///
+    /// assert_eq!(public_key, private_key.to_public_key());
+    /// ```
+    pub fn recover(digest: &[u8; 32], signature: &[u8; 64], recovery_id: u8) -> Result<Self> {
+        let public_key = backend::recover(digest, signature, recovery_id)?;
+        Ok(Self { public_key })
+    }
+
+    /// Calculates the address from the public key
+    pub fn address(&self) -> Address {
+        Address::from_public_key(self)
+    }
+
+    /// Returns Full 64 byte Public Key without 0x04 in the front as a String
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.serialize_uncompressed()[1..])
+    }
+
+    /// Returns Full 65 byte Public Key including the 0x04 prefix as a String
+    pub fn to_hex_full(&self) -> String {
+        hex::encode(self.serialize_uncompressed())
+    }
+
+    /// Returns the x-coordinate of the public key
+    pub fn x(&self) -> [u8; 32] {
+        let mut x = [0u8; 32];
+        x.copy_from_slice(&self.serialize_uncompressed()[1..33]);
+        x
+    }
+
+    /// Returns the y-coordinate of the public key
+    pub fn y(&self) -> [u8; 32] {
+        let mut y = [0u8; 32];
+        y.copy_from_slice(&self.serialize_uncompressed()[33..]);
+        y
+    }
+
+    /// Returns the entire public key in [u8; 65] format including the 0x04 prefix
+    pub fn serialize_uncompressed(&self) -> [u8; 65] {
+        backend::serialize_uncompressed(&self.public_key)
+    }
+
+    /// Returns the 33 byte compressed public key. The first byte is 0x02 if the y-coordinate is
+    /// even and 0x03 if it is odd, followed by the x-coordinate.
+    pub fn serialize_compressed(&self) -> [u8; 33] {
+        backend::serialize_compressed(&self.public_key)
+    }
+
+    /// Returns the 33 byte compressed public key as a String
+    pub fn to_hex_compressed(&self) -> String {
+        hex::encode(self.serialize_compressed())
+    }
+
+    /// Returns the fingerprint of the key, the first 4 bytes of RIPEMD160(SHA256(compressed
+    /// key)) as in BIP-32. Identifies the key without revealing it.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let hash = Ripemd160::digest(Sha256::digest(self.serialize_compressed()));
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash[..4]);
+        fingerprint
+    }
+
+    /// Converts uncompressed public key (64 or 65 bytes) to the 33 byte compressed form
+    /// ```
+    /// use ethereum_private_key_to_address::PublicKey;
+    ///
+    /// let uncompressed = hex::decode("048318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed753547f11ca8696646f2f3acb08e31016afac23e630c5d11f59f61fef57b0d2aa5").unwrap();
+    /// let compressed = PublicKey::compress(&uncompressed).unwrap();
+    ///
+    /// assert_eq!(PublicKey::decompress(&compressed).unwrap().to_vec(), uncompressed);
+    /// ```
+    pub fn compress(uncompressed: &[u8]) -> Result<[u8; 33]> {
+        Ok(Self::from_slice(uncompressed)?.serialize_compressed())
+    }
+
+    /// Converts 33 byte compressed public key to the 65 byte uncompressed form with the 0x04
+    /// prefix
+    pub fn decompress(compressed: &[u8]) -> Result<[u8; 65]> {
+        if compressed.len() != 33 {
+            return Err(Error::InvalidLength {
+                expected: "33",
+                actual: compressed.len(),
+            });
+        }
+        Ok(Self::from_slice(compressed)?.serialize_uncompressed())
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let public_key = hex::decode(strip_hex_prefix(s))?;
+        Self::from_slice(&public_key)
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+#[cfg(not(feature = "k256"))]
+impl From<secp256k1::PublicKey> for PublicKey {
+    fn from(value: secp256k1::PublicKey) -> Self {
+        Self { public_key: value }
+    }
+}
+
+#[cfg(not(feature = "k256"))]
+impl From<PublicKey> for secp256k1::PublicKey {
+    fn from(value: PublicKey) -> Self {
+        value.public_key
+    }
+}
+
+impl TryFrom<&[u8]> for PublicKey {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{Error, PrivateKey, PublicKey};
+    use std::str::FromStr;
+
+    const PUBLIC_KEY: &str = "8318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed753547f11ca8696646f2f3acb08e31016afac23e630c5d11f59f61fef57b0d2aa5";
+
+    #[test]
+    fn test_public_key_from_private_key() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let public_key = private_key.to_public_key();
+        assert_eq!(PUBLIC_KEY, public_key.to_hex());
+        assert_eq!(private_key.to_address(), public_key.address());
+    }
+
+    #[test]
+    fn test_public_key_from_str() {
+        let without_prefix = PublicKey::from_str(PUBLIC_KEY).unwrap();
+        let with_prefix = PublicKey::from_str(&format!("0x04{PUBLIC_KEY}")).unwrap();
+        assert_eq!(without_prefix, with_prefix);
+        assert_eq!(format!("04{PUBLIC_KEY}"), with_prefix.to_hex_full());
+        assert_eq!(hex::encode(with_prefix.x()), &PUBLIC_KEY[..64]);
+        assert_eq!(hex::encode(with_prefix.y()), &PUBLIC_KEY[64..]);
+    }
+
+    #[test]
+    fn test_compressed_public_key() {
+        let public_key = PublicKey::from_str(PUBLIC_KEY).unwrap();
+        let compressed = public_key.serialize_compressed();
+        assert_eq!(
+            "038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75",
+            public_key.to_hex_compressed()
+        );
+        assert_eq!([0xa5, 0x54, 0x76, 0x01], public_key.fingerprint());
+        assert_eq!(public_key, PublicKey::from_slice(&compressed).unwrap());
+        assert_eq!(
+            PublicKey::decompress(&compressed).unwrap(),
+            public_key.serialize_uncompressed()
+        );
+        assert_eq!(
+            PublicKey::compress(&public_key.serialize_uncompressed()).unwrap(),
+            compressed
+        );
+        assert!(PublicKey::decompress(&public_key.serialize_uncompressed()).is_err());
+    }
+
+    #[test]
+    fn test_recover() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let digest = crate::hash_message("hello world");
+        let signature = private_key.sign_hash(&digest);
+
+        let public_key =
+            PublicKey::recover(&digest, &signature.to_rs(), signature.recovery_id()).unwrap();
+        assert_eq!(PUBLIC_KEY, public_key.to_hex());
+
+        let other = PublicKey::recover(&digest, &signature.to_rs(), signature.recovery_id() ^ 1);
+        assert_ne!(Ok(public_key), other);
+        assert_eq!(
+            PublicKey::recover(&digest, &signature.to_rs(), 4),
+            Err(Error::InvalidRecoveryId(4))
+        );
+    }
+
+    #[test]
+    fn test_from_spki_der() {
+        let prefix = "3056301006072a8648ce3d020106052b8104000a034200";
+        let der = hex::decode(format!("{prefix}04{PUBLIC_KEY}")).unwrap();
+        let public_key = PublicKey::from_spki_der(&der).unwrap();
+        assert_eq!(PUBLIC_KEY, public_key.to_hex());
+
+        let compressed = hex::decode(format!(
+            "3036301006072a8648ce3d020106052b8104000a032200{}",
+            public_key.to_hex_compressed()
+        ))
+        .unwrap();
+        assert_eq!(public_key, PublicKey::from_spki_der(&compressed).unwrap());
+
+        assert!(PublicKey::from_spki_der(&der[..der.len() - 1]).is_err());
+        // Other curve
+        let mut other = der.clone();
+        other[19] = 0x22;
+        assert_eq!(
+            PublicKey::from_spki_der(&other),
+            Err(Error::InvalidPublicKey)
+        );
+    }
+
+    #[test]
+    fn test_from_spki_pem() {
+        let pem = "-----BEGIN PUBLIC KEY-----
+MFYwEAYHKoZIzj0CAQYFK4EEAAoDQgAEgxhTW1QQXUp6rmDAj8RflocYG0/fxiW9
+GnU/pzl/7XU1R/EcqGlmRvLzrLCOMQFq+sI+YwxdEfWfYf71ew0qpQ==
+-----END PUBLIC KEY-----
+";
+        assert_eq!(PUBLIC_KEY, PublicKey::from_spki_pem(pem).unwrap().to_hex());
+        assert_eq!(
+            PublicKey::from_spki_pem(&pem.replace("PUBLIC KEY", "PRIVATE KEY")),
+            Err(Error::InvalidPublicKey)
+        );
+    }
+
+    #[test]
+    fn test_invalid_public_key() {
+        assert_eq!(
+            PublicKey::from_slice(&[0u8; 64]),
+            Err(Error::InvalidPublicKey)
+        );
+        assert!(PublicKey::from_slice(&[4u8; 10]).is_err());
+        assert!(PublicKey::from_str("zz").is_err());
+    }
+}
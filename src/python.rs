@@ -0,0 +1,242 @@
+use crate::{
+    recover_address, Address, AddressPattern, Error, HdWallet, Mnemonic, PrivateKey, Signature,
+    VanitySearch,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::str::FromStr;
+
+impl From<Error> for PyErr {
+    fn from(error: Error) -> Self {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+/// Private key exported to Python as `PrivateKey`
+#[pyclass(name = "PrivateKey", frozen)]
+pub struct PyPrivateKey(PrivateKey);
+
+#[pymethods]
+impl PyPrivateKey {
+    /// Parses the hex private key, the `0x` prefix is optional
+    #[new]
+    fn new(private_key: &str) -> PyResult<Self> {
+        Ok(Self(PrivateKey::from_str(private_key)?))
+    }
+
+    /// Generates a new random private key using the operating system's random number generator
+    #[staticmethod]
+    fn random() -> Self {
+        Self(PrivateKey::random())
+    }
+
+    /// Parses the 32 byte big-endian private key
+    #[staticmethod]
+    fn from_bytes(private_key: &[u8]) -> PyResult<Self> {
+        Ok(Self(PrivateKey::from_slice(private_key)?))
+    }
+
+    /// Returns the address of the key
+    fn address(&self) -> PyAddress {
+        PyAddress(self.0.to_address())
+    }
+
+    /// Returns the 64 byte public key as hex, without the 0x04 prefix
+    fn public_key(&self) -> String {
+        self.0.public_key()
+    }
+
+    /// Returns the 33 byte compressed public key as hex
+    fn public_key_compressed(&self) -> String {
+        self.0.public_key_compressed()
+    }
+
+    /// Returns the 32 byte private key as hex, without the `0x` prefix
+    fn expose_secret_hex(&self) -> String {
+        self.0.expose_secret_hex().to_string()
+    }
+
+    /// Signs the message the same way as `personal_sign`, returns the 65 byte signature as hex
+    fn sign_message(&self, message: &[u8]) -> String {
+        self.0.sign_message(message).to_string()
+    }
+
+    /// Signs the 32 byte hash as is, returns the 65 byte signature as hex
+    fn sign_hash(&self, hash: &[u8]) -> PyResult<String> {
+        let hash: [u8; 32] = hash.try_into().map_err(|_| Error::InvalidLength {
+            expected: "32",
+            actual: hash.len(),
+        })?;
+        Ok(self.0.sign_hash(&hash).to_string())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PrivateKey(address={}, <redacted>)", self.0.to_address())
+    }
+}
+
+/// Address exported to Python as `Address`
+#[pyclass(name = "Address", frozen, eq, hash, ord)]
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PyAddress(Address);
+
+#[pymethods]
+impl PyAddress {
+    /// Parses the hex address, fails if it's mixed-case with an invalid checksum
+    #[new]
+    fn new(address: &str) -> PyResult<Self> {
+        Ok(Self(Address::from_str(address)?))
+    }
+
+    /// Parses the 20 byte address
+    #[staticmethod]
+    fn from_bytes(address: &[u8]) -> PyResult<Self> {
+        Ok(Self(Address::from_slice(address)?))
+    }
+
+    /// Calculates the address of the hex public key. Accepts the uncompressed key with or without
+    /// the 0x04 prefix and the 33 byte compressed key.
+    #[staticmethod]
+    fn from_public_key(public_key: &str) -> PyResult<Self> {
+        Ok(Self(crate::address_from_pubkey_hex(public_key)?))
+    }
+
+    /// Recovers the address that signed the message by `personal_sign`
+    #[staticmethod]
+    fn recover(message: &[u8], signature: &str) -> PyResult<Self> {
+        let signature = Signature::from_str(signature)?;
+        Ok(Self(recover_address(message, &signature)?))
+    }
+
+    /// Returns the EIP-55 checksummed address
+    fn to_checksum(&self) -> String {
+        self.0.to_checksum()
+    }
+
+    /// Returns the 20 byte address
+    fn to_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_checksum()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Address('{}')", self.0.to_checksum())
+    }
+}
+
+/// HD wallet exported to Python as `HdWallet`
+#[pyclass(name = "HdWallet", frozen)]
+pub struct PyHdWallet(HdWallet);
+
+#[pymethods]
+impl PyHdWallet {
+    /// Creates the wallet from the mnemonic phrase and passphrase with the base path
+    /// `m/44'/60'/0'/0` unless another one is given
+    #[staticmethod]
+    #[pyo3(signature = (phrase, passphrase = "", base_path = HdWallet::DEFAULT_BASE_PATH))]
+    fn from_mnemonic(phrase: &str, passphrase: &str, base_path: &str) -> PyResult<Self> {
+        let mnemonic = Mnemonic::parse(phrase)?;
+        Ok(Self(HdWallet::from_mnemonic_with_base_path(
+            &mnemonic, passphrase, base_path,
+        )?))
+    }
+
+    /// Creates the wallet from the master `xprv...` key with the base path `m/44'/60'/0'/0`
+    /// unless another one is given
+    #[staticmethod]
+    #[pyo3(signature = (xprv, base_path = HdWallet::DEFAULT_BASE_PATH))]
+    fn from_xprv(xprv: &str, base_path: &str) -> PyResult<Self> {
+        Ok(Self(HdWallet::from_xprv_with_base_path(xprv, base_path)?))
+    }
+
+    /// Returns the base path
+    fn base_path(&self) -> String {
+        self.0.base_path().to_string()
+    }
+
+    /// Derives the key of the account at the index
+    fn key(&self, index: u32) -> PyResult<PyPrivateKey> {
+        Ok(PyPrivateKey(self.0.key(index)?))
+    }
+
+    /// Derives the address of the account at the index
+    fn address(&self, index: u32) -> PyResult<PyAddress> {
+        Ok(PyAddress(self.0.address(index)?))
+    }
+}
+
+/// Generates a random English mnemonic phrase with 12, 15, 18, 21 or 24 words
+#[pyfunction]
+#[pyo3(signature = (word_count = 12))]
+fn generate_mnemonic(word_count: usize) -> PyResult<String> {
+    Ok(Mnemonic::generate(word_count)?.phrase().to_string())
+}
+
+/// Searches for a key whose address has the hex prefix and suffix, using one thread per core
+/// unless the number of threads is given. The GIL is released during the search.
+#[pyfunction]
+#[pyo3(signature = (prefix = "", suffix = "", threads = None))]
+fn vanity_search(
+    py: Python<'_>,
+    prefix: &str,
+    suffix: &str,
+    threads: Option<usize>,
+) -> PyResult<(PyPrivateKey, PyAddress)> {
+    let pattern = AddressPattern::new()
+        .with_prefix(prefix)?
+        .with_suffix(suffix)?;
+    let mut search = VanitySearch::new(pattern);
+    if let Some(threads) = threads {
+        if threads == 0 {
+            return Err(PyValueError::new_err("number of threads can't be zero"));
+        }
+        search = search.with_threads(threads);
+    }
+    let (private_key, address) = py.allow_threads(|| search.search());
+    Ok((PyPrivateKey(private_key), PyAddress(address)))
+}
+
+/// Python module `ethereum_private_key_to_address`
+#[pymodule]
+#[pyo3(name = "ethereum_private_key_to_address")]
+fn python_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyPrivateKey>()?;
+    module.add_class::<PyAddress>()?;
+    module.add_class::<PyHdWallet>()?;
+    module.add_function(wrap_pyfunction!(generate_mnemonic, module)?)?;
+    module.add_function(wrap_pyfunction!(vanity_search, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_python_classes() {
+        let private_key =
+            PyPrivateKey::new("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap();
+        let address = private_key.address();
+        assert_eq!(
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            address.__str__()
+        );
+        assert!(address == PyAddress::from_public_key(&private_key.public_key()).unwrap());
+
+        let signature = private_key.sign_message(b"hello world");
+        assert!(address == PyAddress::recover(b"hello world", &signature).unwrap());
+        assert!(private_key.sign_hash(&[0; 31]).is_err());
+
+        let wallet = PyHdWallet::from_mnemonic(
+            "test test test test test test test test test test test junk",
+            "",
+            HdWallet::DEFAULT_BASE_PATH,
+        )
+        .unwrap();
+        assert!(address == wallet.address(0).unwrap());
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{Error, Result};
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Maximum nesting of lists accepted by the decoder, so malicious input can't overflow the stack
+const MAX_DEPTH: usize = 64;
+
+/// Item of Recursive Length Prefix encoding used by transactions and contract addresses, either a
+/// byte string or a list of items.
+/// ```
+/// use ethereum_private_key_to_address::RlpItem;
+///
+/// let item = RlpItem::List(vec![RlpItem::from("cat"), RlpItem::from("dog")]);
+/// let encoded = item.encode();
+///
+/// assert_eq!("c88363617483646f67", hex::encode(&encoded));
+/// assert_eq!(item, RlpItem::decode(&encoded).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RlpItem {
+    /// Byte string
+    String(Vec<u8>),
+    /// List of items
+    List(Vec<RlpItem>),
+}
+
+impl RlpItem {
+    /// Creates the string of the integer in the canonical form, big-endian without leading zeroes
+    pub fn uint(value: u128) -> Self {
+        let bytes = value.to_be_bytes();
+        let start = bytes
+            .iter()
+            .position(|byte| *byte != 0)
+            .unwrap_or(bytes.len());
+        Self::String(bytes[start..].to_vec())
+    }
+
+    /// Encodes the item
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::String(bytes) => encode_bytes(bytes),
+            Self::List(items) => encode_list(&items.iter().map(Self::encode).collect::<Vec<_>>()),
+        }
+    }
+
+    /// Decodes one item, the whole input must be consumed. Only the canonical encoding is
+    /// accepted.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let (item, rest) = decode_item(bytes, 0)?;
+        if !rest.is_empty() {
+            return Err(invalid("trailing bytes after the item"));
+        }
+        Ok(item)
+    }
+
+    /// Returns the bytes if the item is a string
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::String(bytes) => Some(bytes),
+            Self::List(_) => None,
+        }
+    }
+
+    /// Returns the items if the item is a list
+    pub fn as_list(&self) -> Option<&[RlpItem]> {
+        match self {
+            Self::String(_) => None,
+            Self::List(items) => Some(items),
+        }
+    }
+
+    /// Returns the integer if the item is a string in the canonical integer form
+    pub fn to_uint(&self) -> Result<u128> {
+        let bytes = self
+            .as_bytes()
+            .ok_or_else(|| invalid("expected integer, found list"))?;
+        if bytes.len() > 16 {
+            return Err(invalid("integer is too big"));
+        }
+        if bytes.first() == Some(&0) {
+            return Err(invalid("integer has leading zeroes"));
+        }
+        Ok(bytes
+            .iter()
+            .fold(0, |value, byte| (value << 8) | *byte as u128))
+    }
+}
+
+impl From<&[u8]> for RlpItem {
+    fn from(bytes: &[u8]) -> Self {
+        Self::String(bytes.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for RlpItem {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::String(bytes)
+    }
+}
+
+impl From<&str> for RlpItem {
+    fn from(string: &str) -> Self {
+        Self::String(string.as_bytes().to_vec())
+    }
+}
+
+impl From<Vec<RlpItem>> for RlpItem {
+    fn from(items: Vec<RlpItem>) -> Self {
+        Self::List(items)
+    }
+}
+
+/// Encodes the bytes as RLP string
+pub(crate) fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut encoded = encode_length(bytes.len(), 0x80);
+    encoded.extend_from_slice(bytes);
+    encoded
+}
+
+/// Encodes the integer as RLP string, zero is the empty string
+pub(crate) fn encode_uint(value: u128) -> Vec<u8> {
+    encode_uint_bytes(&value.to_be_bytes())
+}
+
+/// Encodes the big-endian integer as RLP string without the leading zeroes
+pub(crate) fn encode_uint_bytes(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(bytes.len());
+    encode_bytes(&bytes[start..])
+}
+
+/// Encodes the already encoded items as RLP list
+pub(crate) fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut encoded = encode_length(payload.len(), 0xc0);
+    encoded.extend_from_slice(&payload);
+    encoded
+}
+
+/// Encodes the prefix of string (offset `0x80`) or list (offset `0xc0`) with the payload length
+fn encode_length(length: usize, offset: u8) -> Vec<u8> {
+    if length < 56 {
+        return vec![offset + length as u8];
+    }
+    let length = (length as u64).to_be_bytes();
+    let length = &length[length.iter().position(|byte| *byte != 0).unwrap_or(7)..];
+    let mut encoded = vec![offset + 55 + length.len() as u8];
+    encoded.extend_from_slice(length);
+    encoded
+}
+
+/// Decodes one item from the start of the input, returns the item and the rest of the input
+fn decode_item(bytes: &[u8], depth: usize) -> Result<(RlpItem, &[u8])> {
+    let (&prefix, rest) = bytes
+        .split_first()
+        .ok_or_else(|| invalid("unexpected end of input"))?;
+
+    match prefix {
+        0x00..=0x7f => Ok((RlpItem::String(vec![prefix]), rest)),
+        0x80..=0xbf => {
+            let (payload, rest) = split_payload(prefix - 0x80, rest)?;
+            if payload.len() == 1 && payload[0] < 0x80 {
+                return Err(invalid("single byte below 0x80 must not have a prefix"));
+            }
+            Ok((RlpItem::String(payload.to_vec()), rest))
+        }
+        _ => {
+            if depth >= MAX_DEPTH {
+                return Err(invalid("lists are nested too deep"));
+            }
+            let (mut payload, rest) = split_payload(prefix - 0xc0, rest)?;
+            let mut items = Vec::new();
+            while !payload.is_empty() {
+                let (item, remaining) = decode_item(payload, depth + 1)?;
+                items.push(item);
+                payload = remaining;
+            }
+            Ok((RlpItem::List(items), rest))
+        }
+    }
+}
+
+/// Splits the payload of the length from the prefix (without the string or list offset) from
+/// the rest of the input
+fn split_payload(length: u8, bytes: &[u8]) -> Result<(&[u8], &[u8])> {
+    let (length, rest) = if length <= 55 {
+        (length as usize, bytes)
+    } else {
+        let size = (length - 55) as usize;
+        if bytes.len() < size {
+            return Err(invalid("unexpected end of input"));
+        }
+        let (length, rest) = bytes.split_at(size);
+        if length[0] == 0 {
+            return Err(invalid("length has leading zeroes"));
+        }
+        let length = length
+            .iter()
+            .fold(0u64, |length, byte| (length << 8) | *byte as u64);
+        if length < 56 {
+            return Err(invalid("short length must use the single byte prefix"));
+        }
+        let length = usize::try_from(length).map_err(|_| invalid("length is too big"))?;
+        (length, rest)
+    };
+    if rest.len() < length {
+        return Err(invalid("unexpected end of input"));
+    }
+    Ok(rest.split_at(length))
+}
+
+/// Creates the error of invalid RLP
+fn invalid(reason: &str) -> Error {
+    Error::InvalidRlp(reason.to_string())
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!("83646f67", hex::encode(encode_bytes(b"dog")));
+        assert_eq!("80", hex::encode(encode_bytes(b"")));
+        assert_eq!("0f", hex::encode(encode_bytes(&[0x0f])));
+        assert_eq!("8180", hex::encode(encode_bytes(&[0x80])));
+        assert_eq!("80", hex::encode(encode_uint(0)));
+        assert_eq!("820400", hex::encode(encode_uint(1024)));
+        assert_eq!("820400", hex::encode(encode_uint_bytes(&[0, 0, 4, 0])));
+        assert_eq!("c0", hex::encode(encode_list(&[])));
+        assert_eq!(
+            "c88363617483646f67",
+            hex::encode(encode_list(&[encode_bytes(b"cat"), encode_bytes(b"dog")]))
+        );
+
+        let lorem = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+        let encoded = encode_bytes(lorem);
+        assert_eq!([0xb8, 0x38], encoded[..2]);
+        assert_eq!(lorem, &encoded[2..]);
+    }
+
+    #[test]
+    fn test_decode() {
+        let item = RlpItem::decode(&hex::decode("c88363617483646f67").unwrap()).unwrap();
+        assert_eq!(
+            RlpItem::List(vec![RlpItem::from("cat"), RlpItem::from("dog")]),
+            item
+        );
+        assert_eq!(Some(&b"cat"[..]), item.as_list().unwrap()[0].as_bytes());
+
+        // [ [], [[]], [ [], [[]] ] ]
+        let encoded = hex::decode("c7c0c1c0c3c0c1c0").unwrap();
+        let item = RlpItem::decode(&encoded).unwrap();
+        assert_eq!(3, item.as_list().unwrap().len());
+        assert_eq!(encoded, item.encode());
+
+        let lorem = RlpItem::from("Lorem ipsum dolor sit amet, consectetur adipisicing elit");
+        assert_eq!(lorem, RlpItem::decode(&lorem.encode()).unwrap());
+
+        for value in [0, 1, 0x7f, 0x80, 1024, u128::MAX] {
+            let item = RlpItem::decode(&encode_uint(value)).unwrap();
+            assert_eq!(value, item.to_uint().unwrap());
+            assert_eq!(RlpItem::uint(value), item);
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        for (encoded, reason) in [
+            ("", "unexpected end of input"),
+            ("83646f", "unexpected end of input"),
+            ("8100", "single byte below 0x80 must not have a prefix"),
+            ("b80400", "short length must use the single byte prefix"),
+            ("b9000100", "length has leading zeroes"),
+            ("c883636174", "unexpected end of input"),
+            ("8080", "trailing bytes after the item"),
+        ] {
+            assert_eq!(
+                RlpItem::decode(&hex::decode(encoded).unwrap()),
+                Err(Error::InvalidRlp(reason.to_string())),
+                "{encoded}"
+            );
+        }
+        let mut nested = RlpItem::List(vec![]);
+        for _ in 0..MAX_DEPTH {
+            nested = RlpItem::List(vec![nested]);
+        }
+        assert_eq!(
+            RlpItem::decode(&nested.encode()),
+            Err(Error::InvalidRlp("lists are nested too deep".to_string()))
+        );
+        assert!(RlpItem::from(&[0, 1][..]).to_uint().is_err());
+        assert!(RlpItem::List(vec![]).to_uint().is_err());
+    }
+}
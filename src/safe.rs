@@ -0,0 +1,260 @@
+use crate::utils::{address_word, keccak256, uint256};
+use crate::Address;
+use alloc::vec::Vec;
+
+/// Selector of `setup(address[],uint256,address,bytes,address,address,uint256,address)`
+const SETUP_SELECTOR: [u8; 4] = [0xb6, 0x3e, 0x80, 0x0d];
+
+/// Arguments of `Safe.setup()`, the initializer the proxy factory calls on the new Safe. The
+/// address of the Safe depends on the initializer, so it has to match the one used for the
+/// deployment byte for byte.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, SafeSetup};
+///
+/// let owner = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+/// let handler = Address::from_str("0xfd0732dc9e303f09fcef3a7388ad10a83459ec99").unwrap();
+///
+/// let setup = SafeSetup::new(vec![owner], 1).with_fallback_handler(handler);
+/// let initializer = setup.initializer();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SafeSetup {
+    /// Owners of the Safe
+    owners: Vec<Address>,
+    /// Number of owners required to confirm a transaction
+    threshold: u64,
+    /// Contract delegate called during the setup, zero address for none
+    to: Address,
+    /// Calldata of the delegate call
+    data: Vec<u8>,
+    /// Fallback handler, zero address for none
+    fallback_handler: Address,
+    /// Token of the deployment payment, zero address for ETH
+    payment_token: Address,
+    /// Amount of the deployment payment
+    payment: u128,
+    /// Receiver of the deployment payment, zero address for `tx.origin`
+    payment_receiver: Address,
+}
+
+impl SafeSetup {
+    /// Creates the setup with the owners and the threshold, without a fallback handler, a delegate
+    /// call or a payment
+    pub fn new(owners: Vec<Address>, threshold: u64) -> Self {
+        Self {
+            owners,
+            threshold,
+            to: Address::default(),
+            data: Vec::new(),
+            fallback_handler: Address::default(),
+            payment_token: Address::default(),
+            payment: 0,
+            payment_receiver: Address::default(),
+        }
+    }
+
+    /// Sets the fallback handler, Safe{Wallet} uses the `CompatibilityFallbackHandler`
+    pub fn with_fallback_handler(mut self, fallback_handler: Address) -> Self {
+        self.fallback_handler = fallback_handler;
+        self
+    }
+
+    /// Sets the contract delegate called with the data during the setup, e.g. to enable modules
+    pub fn with_delegate_call(mut self, to: Address, data: Vec<u8>) -> Self {
+        self.to = to;
+        self.data = data;
+        self
+    }
+
+    /// Sets the payment of the deployment to the receiver
+    pub fn with_payment(mut self, token: Address, payment: u128, receiver: Address) -> Self {
+        self.payment_token = token;
+        self.payment = payment;
+        self.payment_receiver = receiver;
+        self
+    }
+
+    /// Returns the owners
+    pub fn owners(&self) -> &[Address] {
+        &self.owners
+    }
+
+    /// Returns the threshold
+    pub fn threshold(&self) -> u64 {
+        self.threshold
+    }
+
+    /// Returns the ABI encoded `setup()` call
+    pub fn initializer(&self) -> Vec<u8> {
+        // The owners follow the 8 head words, the data follows the owners
+        let owners_offset = 8 * 32;
+        let data_offset = owners_offset + 32 * (1 + self.owners.len());
+
+        let mut encoded = SETUP_SELECTOR.to_vec();
+        encoded.extend_from_slice(&uint256(owners_offset as u128));
+        encoded.extend_from_slice(&uint256(self.threshold.into()));
+        encoded.extend_from_slice(&address_word(&self.to));
+        encoded.extend_from_slice(&uint256(data_offset as u128));
+        encoded.extend_from_slice(&address_word(&self.fallback_handler));
+        encoded.extend_from_slice(&address_word(&self.payment_token));
+        encoded.extend_from_slice(&uint256(self.payment));
+        encoded.extend_from_slice(&address_word(&self.payment_receiver));
+
+        encoded.extend_from_slice(&uint256(self.owners.len() as u128));
+        for owner in &self.owners {
+            encoded.extend_from_slice(&address_word(owner));
+        }
+        encoded.extend_from_slice(&uint256(self.data.len() as u128));
+        encoded.extend_from_slice(&self.data);
+        encoded.resize(encoded.len() + (32 - self.data.len() % 32) % 32, 0);
+        encoded
+    }
+}
+
+/// `SafeProxyFactory` of the Safe smart account, predicts the address of the Safe proxy deployed
+/// by `createProxyWithNonce()` before the deployment.
+///
+/// The proxy creation code is different in each Safe version, get it from
+/// `proxyCreationCode()` of the factory the Safe will be deployed by.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, SafeProxyFactory, SafeSetup};
+///
+/// # fn run(proxy_creation_code: Vec<u8>) {
+/// let factory = Address::from_str("0x4e1dcf7ad4e460cfd30791ccc4f9c8a4f820ec67").unwrap();
+/// let singleton = Address::from_str("0x41675c099f32341bf84bfc5382af534df5c7461a").unwrap();
+/// let owner = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+///
+/// let factory = SafeProxyFactory::new(factory, proxy_creation_code);
+/// let safe = factory.predict_address(&singleton, &SafeSetup::new(vec![owner], 1).initializer(), 0);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SafeProxyFactory {
+    /// Address of the factory
+    address: Address,
+    /// Init code of the proxy without the singleton argument
+    proxy_creation_code: Vec<u8>,
+}
+
+impl SafeProxyFactory {
+    /// Creates the factory at the address with its proxy creation code
+    pub fn new(address: Address, proxy_creation_code: Vec<u8>) -> Self {
+        Self {
+            address,
+            proxy_creation_code,
+        }
+    }
+
+    /// Returns the address of the factory
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    /// Returns the proxy creation code
+    pub fn proxy_creation_code(&self) -> &[u8] {
+        &self.proxy_creation_code
+    }
+
+    /// Calculates the address of the Safe proxy of the singleton created with the initializer and
+    /// the salt nonce. The `CREATE2` salt is `keccak256(keccak256(initializer) || saltNonce)`.
+    pub fn predict_address(
+        &self,
+        singleton: &Address,
+        initializer: &[u8],
+        salt_nonce: u128,
+    ) -> Address {
+        let mut salt = keccak256(initializer).to_vec();
+        salt.extend_from_slice(&uint256(salt_nonce));
+
+        let mut init_code = self.proxy_creation_code.clone();
+        init_code.extend_from_slice(&address_word(singleton));
+        Address::create2_from_code(&self.address, &keccak256(&salt), &init_code)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_initializer() {
+        let owners = vec![
+            Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap(),
+            Address::from_str("0x70997970c51812dc3a010c7d01b50e0d17dc79c8").unwrap(),
+        ];
+        let handler = Address::from_str("0xfd0732dc9e303f09fcef3a7388ad10a83459ec99").unwrap();
+        let setup = SafeSetup::new(owners, 2)
+            .with_fallback_handler(handler)
+            .with_delegate_call(handler, vec![0xab; 4]);
+        assert_eq!(
+            concat!(
+                "b63e800d",
+                "0000000000000000000000000000000000000000000000000000000000000100",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "000000000000000000000000fd0732dc9e303f09fcef3a7388ad10a83459ec99",
+                "0000000000000000000000000000000000000000000000000000000000000160",
+                "000000000000000000000000fd0732dc9e303f09fcef3a7388ad10a83459ec99",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8",
+                "0000000000000000000000000000000000000000000000000000000000000004",
+                "abababab00000000000000000000000000000000000000000000000000000000",
+            ),
+            hex::encode(setup.initializer())
+        );
+    }
+
+    #[test]
+    fn test_predict_address() {
+        // SafeProxyFactory 1.3.0 and its proxyCreationCode(), the runtime code in it hashes to the
+        // code hash of the Safe 1.3.0 proxies
+        let factory = Address::from_str("0xa6b71e26c5e0845f74c812102ca7114b6a896ab2").unwrap();
+        let singleton = Address::from_str("0xd9db270c1b5e3bd161e8c8503c55ceabee709552").unwrap();
+        let handler = Address::from_str("0xf48f2b2d2a534e402487b3ee7c18c33aec0fe5e4").unwrap();
+        let creation_code = hex::decode(concat!(
+            "608060405234801561001057600080fd5b506040516101e63803806101e68339",
+            "818101604052602081101561003357600080fd5b810190808051906020019092",
+            "9190505050600073ffffffffffffffffffffffffffffffffffffffff168173ff",
+            "ffffffffffffffffffffffffffffffffffffff1614156100ca576040517f08c3",
+            "79a0000000000000000000000000000000000000000000000000000000008152",
+            "6004018080602001828103825260228152602001806101c46022913960400191",
+            "505060405180910390fd5b806000806101000a81548173ffffffffffffffffff",
+            "ffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffff",
+            "ffffffffff1602179055505060ab806101196000396000f3fe608060405273ff",
+            "ffffffffffffffffffffffffffffffffffffff600054167fa619486e00000000",
+            "0000000000000000000000000000000000000000000000006000351415605057",
+            "8060005260206000f35b3660008037600080366000845af43d6000803e600081",
+            "14156070573d6000fd5b3d6000f3fea2646970667358221220d1429297349653",
+            "a4918076d650332de1a1068c5f3e07c5c82360c277770b955264736f6c634300",
+            "07060033496e76616c69642073696e676c65746f6e2061646472657373207072",
+            "6f7669646564",
+        ))
+        .unwrap();
+        assert_eq!(
+            "b89c1b3bdf2cf8827818646bce9a8f6e372885f8c55e5c07acbd307cb133b000",
+            hex::encode(keccak256(&creation_code[0x119..0x1c4]))
+        );
+
+        let owner = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+        let initializer = SafeSetup::new(vec![owner], 1)
+            .with_fallback_handler(handler)
+            .initializer();
+        let factory = SafeProxyFactory::new(factory, creation_code);
+        assert_eq!(
+            "0xf161506bf7293443bb041212f80ab2f17d42be9c",
+            factory
+                .predict_address(&singleton, &initializer, 0)
+                .to_string()
+        );
+        assert_ne!(
+            factory.predict_address(&singleton, &initializer, 0),
+            factory.predict_address(&singleton, &initializer, 1)
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use crate::search::{default_threads, parallel_maximize, parallel_search};
+use crate::{Address, AddressPattern, SearchProgress};
+use rand_core::{OsRng, RngCore};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Number of salts a thread tries between checking if another thread found a match
+const BATCH_SIZE: u64 = 4096;
+
+/// Searches for the `CREATE2` salt which deploys the contract to an address matching the pattern,
+/// e.g. with leading zeroes. The salts are tried in parallel, each thread starts from a random
+/// salt and counts up in the last 8 bytes.
+///
+/// The expected number of attempts grows 16 times with every hex character of the pattern, the
+/// search doesn't end if no address can match.
+/// ```
+/// use std::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, AddressPattern, Create2SaltMiner};
+///
+/// let deployer = Address::from_str("0x4e59b44847b379578588920ca78fbf26c0b4956c").unwrap();
+/// let pattern = AddressPattern::new().with_prefix("00").unwrap();
+///
+/// let (salt, address) = Create2SaltMiner::new(deployer, [0u8; 32], pattern).mine();
+/// assert_eq!(address, Address::create2(&deployer, &salt, &[0u8; 32]));
+/// assert!(address.to_string().starts_with("0x00"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Create2SaltMiner {
+    /// Deployer of the contract, the `CREATE2` factory
+    deployer: Address,
+    /// Keccak256 hash of the init code
+    init_code_hash: [u8; 32],
+    /// Pattern the address has to match
+    pattern: AddressPattern,
+    /// Fixed start of the salt
+    salt_prefix: Vec<u8>,
+    /// Number of threads
+    threads: usize,
+}
+
+impl Create2SaltMiner {
+    /// Creates the miner of the salt for the deployer and the init code hash, using one thread
+    /// per core
+    pub fn new(deployer: Address, init_code_hash: [u8; 32], pattern: AddressPattern) -> Self {
+        Self {
+            deployer,
+            init_code_hash,
+            pattern,
+            salt_prefix: Vec::new(),
+            threads: default_threads(),
+        }
+    }
+
+    /// Sets the fixed start of the salt, e.g. the caller address required by factories which
+    /// protect the salts from front-running
+    ///
+    /// # Panics
+    ///
+    /// If the prefix is longer than 24 bytes, the last 8 bytes are the counter.
+    pub fn with_salt_prefix(mut self, prefix: &[u8]) -> Self {
+        assert!(
+            prefix.len() <= 24,
+            "salt prefix can't be longer than 24 bytes"
+        );
+        self.salt_prefix = prefix.to_vec();
+        self
+    }
+
+    /// Sets the number of threads
+    ///
+    /// # Panics
+    ///
+    /// If the number of threads is zero.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "number of threads can't be zero");
+        self.threads = threads;
+        self
+    }
+
+    /// Returns the number of threads
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Searches for the salt whose address matches the pattern and starts with the most zero
+    /// bytes, until the cancel flag is set or an address with 20 zero bytes is found. Every salt
+    /// better than the best one so far is passed to `improved`. Returns the best salt, `None` if
+    /// the search was cancelled before any address matched.
+    pub fn maximize_leading_zero_bytes(
+        &self,
+        cancel: &AtomicBool,
+        mut progress: impl FnMut(SearchProgress),
+        mut improved: impl FnMut(&[u8; 32], &Address),
+    ) -> Option<([u8; 32], Address)> {
+        parallel_maximize(
+            self.threads,
+            BATCH_SIZE,
+            20,
+            cancel,
+            &mut progress,
+            &mut |(salt, address): &([u8; 32], Address)| improved(salt, address),
+            || {
+                let mut salts = self.salts();
+                move || {
+                    let salt = salts();
+                    let address = Address::create2(&self.deployer, &salt, &self.init_code_hash);
+                    self.pattern
+                        .matches(&address)
+                        .then(|| (address.leading_zero_bytes(), (salt, address)))
+                }
+            },
+        )
+    }
+
+    /// Searches until a salt is found, returns the salt and the address
+    pub fn mine(&self) -> ([u8; 32], Address) {
+        self.mine_with_progress(|_| {})
+    }
+
+    /// Searches until a salt is found, calling the callback with the progress about twice a
+    /// second
+    pub fn mine_with_progress(
+        &self,
+        mut progress: impl FnMut(SearchProgress),
+    ) -> ([u8; 32], Address) {
+        let never = AtomicBool::new(false);
+        parallel_search(
+            self.threads,
+            SearchProgress::default(),
+            &never,
+            &mut progress,
+            |attempts, stop| {
+                let mut salts = self.salts();
+                loop {
+                    for _ in 0..BATCH_SIZE {
+                        let salt = salts();
+                        let address = Address::create2(&self.deployer, &salt, &self.init_code_hash);
+                        if self.pattern.matches(&address) {
+                            return Some((salt, address));
+                        }
+                    }
+                    attempts.fetch_add(BATCH_SIZE, Ordering::Relaxed);
+                    if stop.load(Ordering::Relaxed) {
+                        return None;
+                    }
+                }
+            },
+        )
+        .expect("search can't be cancelled")
+    }
+
+    /// Returns the generator of the salts of a thread, starting from a random salt with the
+    /// prefix and counting up in the last 8 bytes. The counter starts at a random value too, so
+    /// the threads don't repeat each other's salts when the prefix takes all 24 bytes.
+    fn salts(&self) -> impl FnMut() -> [u8; 32] {
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt[..24]);
+        salt[..self.salt_prefix.len()].copy_from_slice(&self.salt_prefix);
+
+        let mut counter = OsRng.next_u64();
+        move || {
+            counter = counter.wrapping_add(1);
+            salt[24..].copy_from_slice(&counter.to_be_bytes());
+            salt
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_mine() {
+        let deployer = Address::new([0x11; 20]);
+        let pattern = AddressPattern::new()
+            .with_prefix("ab")
+            .unwrap()
+            .with_suffix("c")
+            .unwrap();
+        let (salt, address) = Create2SaltMiner::new(deployer, [0x22; 32], pattern.clone())
+            .with_salt_prefix(deployer.as_bytes())
+            .with_threads(2)
+            .mine();
+
+        assert!(pattern.matches(&address));
+        assert_eq!(deployer.as_bytes()[..], salt[..20]);
+        assert_eq!(address, Address::create2(&deployer, &salt, &[0x22; 32]));
+    }
+
+    #[test]
+    fn test_salts() {
+        // With the full prefix only the counter differs between the threads
+        let miner =
+            Create2SaltMiner::new(Address::new([0x11; 20]), [0x22; 32], AddressPattern::new())
+                .with_salt_prefix(&[0x33; 24]);
+        let (mut first, mut second) = (miner.salts(), miner.salts());
+        let salt = first();
+        assert_eq!([0x33; 24], salt[..24]);
+        assert_ne!(salt, second());
+    }
+
+    #[test]
+    fn test_maximize_leading_zero_bytes() {
+        let deployer = Address::new([0x11; 20]);
+        let cancel = AtomicBool::new(false);
+        let (salt, address) = Create2SaltMiner::new(deployer, [0x22; 32], AddressPattern::new())
+            .with_threads(2)
+            .maximize_leading_zero_bytes(
+                &cancel,
+                |_| {},
+                |_, address| {
+                    if address.leading_zero_bytes() > 0 {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                },
+            )
+            .unwrap();
+
+        assert!(address.leading_zero_bytes() > 0);
+        assert_eq!(address, Address::create2(&deployer, &salt, &[0x22; 32]));
+    }
+}
@@ -0,0 +1,646 @@
+use crate::utils::strip_hex_prefix;
+use crate::{Address, Error, Result};
+#[cfg(feature = "regex")]
+use regex::Regex;
+use std::fmt;
+#[cfg(feature = "regex")]
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the progress is reported
+pub(crate) const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Pattern the searched address has to match. All the set conditions have to match, the hex
+/// patterns are compared case insensitive against the address without the `0x` prefix, or against
+/// its EIP-55 checksummed form with `with_checksum_case()`.
+/// ```
+/// use std::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, AddressPattern};
+///
+/// let pattern = AddressPattern::new().with_prefix("0xf39f").unwrap().with_suffix("2266").unwrap();
+///
+/// assert!(pattern.matches(&Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct AddressPattern {
+    /// Hex the address starts with
+    prefix: String,
+    /// Hex the address ends with
+    suffix: String,
+    /// Hex the address contains anywhere
+    contains: String,
+    /// Regular expression the hex of the address has to match
+    #[cfg(feature = "regex")]
+    regex: Option<HexRegex>,
+    /// Whether the hex is matched against the checksummed address including the case
+    checksum_case: bool,
+    /// Number of zero bytes the address starts with
+    leading_zero_bytes: usize,
+}
+
+impl AddressPattern {
+    /// Creates the pattern matching every address
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the hex the address has to start with, the `0x` prefix is optional
+    pub fn with_prefix(mut self, prefix: &str) -> Result<Self> {
+        self.prefix = parse_hex_pattern(prefix)?;
+        Ok(self)
+    }
+
+    /// Sets the hex the address has to end with
+    pub fn with_suffix(mut self, suffix: &str) -> Result<Self> {
+        self.suffix = parse_hex_pattern(suffix)?;
+        Ok(self)
+    }
+
+    /// Sets the hex the address has to contain anywhere
+    pub fn with_contains(mut self, contains: &str) -> Result<Self> {
+        self.contains = parse_hex_pattern(contains)?;
+        Ok(self)
+    }
+
+    /// Sets the regular expression the address has to match, e.g. `^dead.*beef$`. It's matched
+    /// against the lowercase hex without the `0x` prefix, or the checksummed hex with
+    /// `with_checksum_case()`. A `0x` at the start of the expression is treated as `^`. The
+    /// expression is compiled once.
+    #[cfg(feature = "regex")]
+    pub fn with_regex(mut self, regex: &str) -> Result<Self> {
+        let anchored = regex
+            .strip_prefix("^0x")
+            .or_else(|| regex.strip_prefix("0x"))
+            .map(|rest| format!("^{rest}"));
+        let regex = Regex::new(anchored.as_deref().unwrap_or(regex))
+            .map_err(|error| Error::InvalidPattern(error.to_string()))?;
+        self.regex = Some(HexRegex(regex));
+        Ok(self)
+    }
+
+    /// Matches the hex patterns including their case against the EIP-55 checksummed address, so
+    /// e.g. the prefix `BEEF` only matches addresses rendered as `0xBEEF...`. Every letter of the
+    /// pattern halves the chance of a match, calculating the checksum is done only for the
+    /// addresses matching the pattern case insensitive.
+    /// ```
+    /// use std::str::FromStr;
+    /// use ethereum_private_key_to_address::{Address, AddressPattern};
+    ///
+    /// let address = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+    ///
+    /// let pattern = AddressPattern::new().with_prefix("f39F").unwrap();
+    /// assert!(pattern.clone().with_checksum_case(true).matches(&address));
+    ///
+    /// let pattern = AddressPattern::new().with_prefix("F39f").unwrap();
+    /// assert!(pattern.matches(&address));
+    /// assert!(!pattern.with_checksum_case(true).matches(&address));
+    /// ```
+    pub fn with_checksum_case(mut self, checksum_case: bool) -> Self {
+        self.checksum_case = checksum_case;
+        self
+    }
+
+    /// Sets the number of zero bytes the address has to start with
+    pub fn with_leading_zero_bytes(mut self, count: usize) -> Result<Self> {
+        if count > 20 {
+            return Err(Error::InvalidPattern(format!(
+                "address has only 20 bytes, can't start with {count} zero bytes"
+            )));
+        }
+        self.leading_zero_bytes = count;
+        Ok(self)
+    }
+
+    /// Returns the hex the address has to start with
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the hex the address has to end with
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    /// Returns the hex the address has to contain
+    pub fn contains(&self) -> &str {
+        &self.contains
+    }
+
+    /// Returns the number of zero bytes the address has to start with
+    pub fn leading_zero_bytes(&self) -> usize {
+        self.leading_zero_bytes
+    }
+
+    /// Returns true if the hex is matched against the checksummed address
+    pub fn checksum_case(&self) -> bool {
+        self.checksum_case
+    }
+
+    /// Returns the regular expression
+    #[cfg(feature = "regex")]
+    pub fn regex(&self) -> Option<&Regex> {
+        self.regex.as_ref().map(|regex| &regex.0)
+    }
+
+    /// Estimates the expected number of attempts to find a matching address, infinite if no
+    /// address can match, e.g. a prefix conflicting with the suffix. Every hex character of the
+    /// prefix and the suffix makes the search 16 times harder and every letter matched with
+    /// `with_checksum_case()` twice as hard again. The contained hex is estimated without its
+    /// overlaps with the other conditions, and the regular expression isn't taken into account.
+    /// ```
+    /// use ethereum_private_key_to_address::AddressPattern;
+    ///
+    /// let pattern = AddressPattern::new().with_prefix("0xdead").unwrap();
+    /// assert_eq!(65536.0, pattern.difficulty());
+    /// ```
+    pub fn difficulty(&self) -> f64 {
+        let fixed = match self.fixed_chars() {
+            Some(fixed) => fixed,
+            None => return f64::INFINITY,
+        };
+        let mut probability = fixed
+            .iter()
+            .flatten()
+            .map(|c| self.char_probability(*c))
+            .product::<f64>();
+        if !self.contains.is_empty() {
+            let at_position = self
+                .contains
+                .chars()
+                .map(|c| self.char_probability(c))
+                .product::<f64>();
+            let positions = (41 - self.contains.len()) as f64;
+            probability *= -(positions * (-at_position).ln_1p()).exp_m1();
+        }
+        1.0 / probability
+    }
+
+    /// Estimates the expected time to find a matching address at the rate of attempts per second,
+    /// e.g. `SearchProgress::rate()` of a running search. Returns `None` if no address can match
+    /// or the rate is zero. The search has no memory, the expected time is the same at any point.
+    pub fn expected_time(&self, rate: f64) -> Option<Duration> {
+        Duration::try_from_secs_f64(self.difficulty() / rate).ok()
+    }
+
+    /// Returns the probability that a search finds a matching address within the attempts
+    pub fn probability(&self, attempts: u64) -> f64 {
+        -(attempts as f64 * (-1.0 / self.difficulty()).ln_1p()).exp_m1()
+    }
+
+    /// Returns the characters at the fixed positions of the hex address, set by the leading zero
+    /// bytes, the prefix and the suffix, or `None` if the conditions conflict
+    pub(crate) fn fixed_chars(&self) -> Option<[Option<char>; 40]> {
+        let mut fixed = [None; 40];
+        let zeros = "0".repeat(2 * self.leading_zero_bytes);
+        let conditions = [
+            (0, zeros.as_str()),
+            (0, self.prefix.as_str()),
+            (40 - self.suffix.len(), self.suffix.as_str()),
+        ];
+        for (start, pattern) in conditions {
+            for (position, c) in fixed[start..].iter_mut().zip(pattern.chars()) {
+                match position {
+                    Some(other) if !self.same_char(*other, c) => return None,
+                    _ => *position = Some(c),
+                }
+            }
+        }
+        Some(fixed)
+    }
+
+    /// Returns true if the characters can be at the same position of the address
+    fn same_char(&self, a: char, b: char) -> bool {
+        if self.checksum_case {
+            a == b
+        } else {
+            a.eq_ignore_ascii_case(&b)
+        }
+    }
+
+    /// Returns the probability that the character is at the position of a random address
+    fn char_probability(&self, c: char) -> f64 {
+        if self.checksum_case && c.is_ascii_alphabetic() {
+            1.0 / 32.0
+        } else {
+            1.0 / 16.0
+        }
+    }
+
+    /// Returns true if the address matches all conditions of the pattern
+    pub fn matches(&self, address: &Address) -> bool {
+        if address.as_bytes()[..self.leading_zero_bytes]
+            .iter()
+            .any(|byte| *byte != 0)
+        {
+            return false;
+        }
+        if !self.has_hex_conditions() {
+            return true;
+        }
+        let hex = hex::encode(address.as_bytes());
+        let ignore_case = |start: usize, pattern: &str| {
+            hex.get(start..start + pattern.len())
+                .is_some_and(|part| part.eq_ignore_ascii_case(pattern))
+        };
+        if !ignore_case(0, &self.prefix)
+            || !ignore_case(40 - self.suffix.len(), &self.suffix)
+            || !(0..=40 - self.contains.len()).any(|start| ignore_case(start, &self.contains))
+        {
+            return false;
+        }
+        if !self.checksum_case {
+            return self.matches_regex(&hex);
+        }
+
+        let checksum = address.to_checksum();
+        let checksum = &checksum[2..];
+        checksum.starts_with(&self.prefix)
+            && checksum.ends_with(&self.suffix)
+            && checksum.contains(&self.contains)
+            && self.matches_regex(checksum)
+    }
+
+    /// Returns true if any condition needs the hex of the address
+    fn has_hex_conditions(&self) -> bool {
+        #[cfg(feature = "regex")]
+        if self.regex.is_some() {
+            return true;
+        }
+        !(self.prefix.is_empty() && self.suffix.is_empty() && self.contains.is_empty())
+    }
+
+    /// Returns true if the hex matches the regular expression or there's none
+    fn matches_regex(&self, hex: &str) -> bool {
+        #[cfg(feature = "regex")]
+        if let Some(regex) = &self.regex {
+            return regex.0.is_match(hex);
+        }
+        #[cfg(not(feature = "regex"))]
+        let _ = hex;
+        true
+    }
+}
+
+/// Compiled regular expression, compared by its source so the pattern can be compared and hashed
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+struct HexRegex(Regex);
+
+#[cfg(feature = "regex")]
+impl PartialEq for HexRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Eq for HexRegex {}
+
+#[cfg(feature = "regex")]
+impl Hash for HexRegex {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_str().hash(state);
+    }
+}
+
+/// Validates the hex pattern, keeping its case
+fn parse_hex_pattern(pattern: &str) -> Result<String> {
+    let pattern = strip_hex_prefix(pattern);
+    if pattern.len() > 40 {
+        return Err(Error::InvalidPattern(format!(
+            "{pattern} is longer than an address"
+        )));
+    }
+    if let Some(c) = pattern.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(Error::InvalidPattern(format!(
+            "{c:?} isn't a hex character"
+        )));
+    }
+    Ok(pattern.to_string())
+}
+
+/// Progress of the running search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SearchProgress {
+    /// Number of tried candidates
+    attempts: u64,
+    /// Time since the start of the search
+    elapsed: Duration,
+}
+
+impl SearchProgress {
+    /// Creates the progress of a search which tried the candidates in the time
+    pub(crate) fn new(attempts: u64, elapsed: Duration) -> Self {
+        Self { attempts, elapsed }
+    }
+
+    /// Returns the number of tried candidates
+    pub fn attempts(&self) -> u64 {
+        self.attempts
+    }
+
+    /// Returns the time since the start of the search
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of tried candidates per second
+    pub fn rate(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => self.attempts as f64 / secs,
+            _ => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for SearchProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} attempts in {:.1}s, {:.0}/s",
+            self.attempts,
+            self.elapsed.as_secs_f64(),
+            self.rate()
+        )
+    }
+}
+
+/// Runs the worker on the threads until one of them finds the result or the search is cancelled,
+/// reporting the progress in the meantime. The workers count their attempts and return `None` once
+/// the stop flag is set. The cancel flag is checked together with the progress. The reported
+/// progress continues from the resumed one.
+pub(crate) fn parallel_search<T, W>(
+    threads: usize,
+    resumed: SearchProgress,
+    cancel: &AtomicBool,
+    progress: &mut dyn FnMut(SearchProgress),
+    worker: W,
+) -> Option<T>
+where
+    T: Send,
+    W: Fn(&AtomicU64, &AtomicBool) -> Option<T> + Sync,
+{
+    let start = Instant::now();
+    let attempts = AtomicU64::new(resumed.attempts);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let (worker, attempts, stop) = (&worker, &attempts, &stop);
+            scope.spawn(move || {
+                if let Some(found) = worker(attempts, stop) {
+                    stop.store(true, Ordering::Relaxed);
+                    // Only the first result is received
+                    let _ = sender.send(found);
+                }
+            });
+        }
+        drop(sender);
+
+        loop {
+            match receiver.recv_timeout(PROGRESS_INTERVAL) {
+                Ok(found) => {
+                    stop.store(true, Ordering::Relaxed);
+                    return Some(found);
+                }
+                Err(RecvTimeoutError::Timeout) if cancel.load(Ordering::Relaxed) => {
+                    stop.store(true, Ordering::Relaxed);
+                    // A match found before the workers stopped is still returned
+                    return receiver.recv().ok();
+                }
+                Err(RecvTimeoutError::Timeout) => progress(SearchProgress {
+                    attempts: attempts.load(Ordering::Relaxed),
+                    elapsed: resumed.elapsed + start.elapsed(),
+                }),
+                Err(RecvTimeoutError::Disconnected) => panic!("search worker panicked"),
+            }
+        }
+    })
+}
+
+/// Runs the workers on the threads until the search is cancelled or a candidate reaches the
+/// maximum score, calling `improved` with every candidate scoring higher than the best one so far.
+/// Each thread creates its worker, which returns the next candidate with its score or `None` if
+/// the candidate doesn't match. Returns the best candidate.
+pub(crate) fn parallel_maximize<T, F, C>(
+    threads: usize,
+    batch_size: u64,
+    max_score: usize,
+    cancel: &AtomicBool,
+    progress: &mut dyn FnMut(SearchProgress),
+    improved: &mut dyn FnMut(&T),
+    worker: F,
+) -> Option<T>
+where
+    T: Send,
+    F: Fn() -> C + Sync,
+    C: FnMut() -> Option<(usize, T)>,
+{
+    let start = Instant::now();
+    let attempts = AtomicU64::new(0);
+    let stop = AtomicBool::new(false);
+    // Best score so far plus one, zero before the first candidate
+    let best_score = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let (worker, attempts, stop, best_score) = (&worker, &attempts, &stop, &best_score);
+            scope.spawn(move || {
+                let mut next = worker();
+                while !stop.load(Ordering::Relaxed) {
+                    for _ in 0..batch_size {
+                        let (score, candidate) = match next() {
+                            Some(found) => found,
+                            None => continue,
+                        };
+                        if best_score.fetch_max(score + 1, Ordering::Relaxed) <= score {
+                            let _ = sender.send((score, candidate));
+                        }
+                    }
+                    attempts.fetch_add(batch_size, Ordering::Relaxed);
+                }
+            });
+        }
+        drop(sender);
+
+        let mut best: Option<(usize, T)> = None;
+        loop {
+            match receiver.recv_timeout(PROGRESS_INTERVAL) {
+                // The candidates of different threads can arrive out of order
+                Ok((score, candidate)) if best.as_ref().map_or(true, |best| score > best.0) => {
+                    improved(&candidate);
+                    best = Some((score, candidate));
+                    if score >= max_score {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(RecvTimeoutError::Timeout) => progress(SearchProgress {
+                    attempts: attempts.load(Ordering::Relaxed),
+                    elapsed: start.elapsed(),
+                }),
+                Err(RecvTimeoutError::Disconnected) => panic!("search worker panicked"),
+            }
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+        stop.store(true, Ordering::Relaxed);
+        best.map(|(_, candidate)| candidate)
+    })
+}
+
+/// Returns the number of threads the search uses by default, one per core
+pub(crate) fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_address_pattern() {
+        let address = Address::from_str("0x0000d6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+        let matching = AddressPattern::new()
+            .with_prefix("0x0000D6")
+            .unwrap()
+            .with_suffix("2266")
+            .unwrap()
+            .with_leading_zero_bytes(2)
+            .unwrap();
+        assert!(matching.matches(&address));
+        assert!(AddressPattern::new().matches(&address));
+        assert!(matching
+            .clone()
+            .with_contains("AAD88")
+            .unwrap()
+            .matches(&address));
+        assert!(!matching
+            .clone()
+            .with_contains("dead")
+            .unwrap()
+            .matches(&address));
+        assert!(!matching
+            .clone()
+            .with_suffix("2267")
+            .unwrap()
+            .matches(&address));
+        assert!(!matching
+            .with_leading_zero_bytes(3)
+            .unwrap()
+            .matches(&address));
+
+        assert!(AddressPattern::new().with_prefix("dead_beef").is_err());
+        assert!(AddressPattern::new().with_suffix(&"0".repeat(41)).is_err());
+        assert!(AddressPattern::new().with_leading_zero_bytes(21).is_err());
+    }
+
+    #[test]
+    fn test_checksum_case() {
+        let address = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+        let pattern = AddressPattern::new()
+            .with_prefix("0xf39Fd6")
+            .unwrap()
+            .with_suffix("fFb92266")
+            .unwrap()
+            .with_contains("aB88")
+            .unwrap();
+        assert!(pattern.matches(&address));
+        assert!(pattern.clone().with_checksum_case(true).matches(&address));
+        assert!(!pattern
+            .with_prefix("F39FD6")
+            .unwrap()
+            .with_checksum_case(true)
+            .matches(&address));
+        assert!(!AddressPattern::new()
+            .with_contains("ab88")
+            .unwrap()
+            .with_checksum_case(true)
+            .matches(&address));
+    }
+
+    #[test]
+    fn test_difficulty() {
+        let pattern = AddressPattern::new().with_prefix("dead").unwrap();
+        assert_eq!(65536.0, pattern.difficulty());
+        assert_eq!(
+            Some(Duration::from_millis(65536)),
+            pattern.expected_time(1000.0)
+        );
+        assert_eq!(None, pattern.expected_time(0.0));
+        assert!((pattern.probability(65536) - 0.632).abs() < 0.001);
+
+        let pattern = AddressPattern::new()
+            .with_leading_zero_bytes(2)
+            .unwrap()
+            .with_prefix("0000AB")
+            .unwrap()
+            .with_suffix("1")
+            .unwrap();
+        assert_eq!(16f64.powi(7), pattern.difficulty());
+        assert_eq!(
+            16f64.powi(7) * 4.0,
+            pattern.clone().with_checksum_case(true).difficulty()
+        );
+        assert_eq!(
+            f64::INFINITY,
+            pattern.clone().with_prefix("1").unwrap().difficulty()
+        );
+        assert_eq!(
+            f64::INFINITY,
+            pattern
+                .with_suffix(&"1".repeat(40))
+                .unwrap()
+                .with_prefix("")
+                .unwrap()
+                .difficulty()
+        );
+        assert_eq!(
+            0.0,
+            AddressPattern::new()
+                .with_prefix("1")
+                .unwrap()
+                .probability(0)
+        );
+        assert_eq!(1.0, AddressPattern::new().difficulty());
+
+        let contains = AddressPattern::new()
+            .with_contains("ab")
+            .unwrap()
+            .difficulty();
+        assert!((contains - 1.0 / (1.0 - (255.0f64 / 256.0).powi(39))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_search_progress() {
+        let progress = SearchProgress {
+            attempts: 3000,
+            elapsed: Duration::from_millis(1500),
+        };
+        assert_eq!(2000.0, progress.rate());
+        assert_eq!("3000 attempts in 1.5s, 2000/s", progress.to_string());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex() {
+        let address = Address::from_str("0xdead0000000000000000000000000000000abeef").unwrap();
+        for regex in ["^dead.*beef$", "0xdead", "^0xdead", "0{10}"] {
+            let pattern = AddressPattern::new().with_regex(regex).unwrap();
+            assert!(pattern.matches(&address), "{regex}");
+        }
+        let pattern = AddressPattern::new().with_regex("^beef").unwrap();
+        assert!(!pattern.matches(&address));
+        assert!(AddressPattern::new().with_regex("(").is_err());
+    }
+}
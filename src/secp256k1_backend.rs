@@ -0,0 +1,106 @@
+use crate::{Error, Result};
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::{All, Message, Scalar, Secp256k1};
+#[cfg(not(feature = "global-context"))]
+use std::sync::OnceLock;
+
+pub(crate) use secp256k1::{PublicKey, SecretKey};
+
+/// Returns the secp256k1 context shared by all operations. Creating a context precomputes large
+/// tables, so it's created once on the first use instead of in every call.
+#[cfg(not(feature = "global-context"))]
+fn secp256k1_context() -> &'static Secp256k1<All> {
+    static CONTEXT: OnceLock<Secp256k1<All>> = OnceLock::new();
+    CONTEXT.get_or_init(Secp256k1::new)
+}
+
+/// Returns the static global context of the secp256k1 library, which is never allocated
+#[cfg(feature = "global-context")]
+fn secp256k1_context() -> &'static Secp256k1<All> {
+    secp256k1::SECP256K1
+}
+
+/// Returns the key with the value one, its public key is the generator point
+pub(crate) fn one() -> SecretKey {
+    SecretKey::from_slice(&secp256k1::constants::ONE).expect("one is a valid key")
+}
+
+/// Parses the 32 byte big-endian key, fails if it's zero or not below the curve order
+pub(crate) fn secret_key_from_slice(slice: &[u8]) -> Result<SecretKey> {
+    SecretKey::from_slice(slice).map_err(|_| Error::OutOfRange)
+}
+
+/// Returns the 32 byte big-endian key
+pub(crate) fn secret_bytes(secret_key: &SecretKey) -> [u8; 32] {
+    secret_key.secret_bytes()
+}
+
+/// Adds the 32 byte big-endian tweak to the key modulo the curve order, fails if the tweak isn't
+/// below the curve order or the sum is zero
+pub(crate) fn add_tweak(secret_key: &SecretKey, tweak: &[u8; 32]) -> Result<SecretKey> {
+    let tweak = Scalar::from_be_bytes(*tweak).map_err(|_| Error::OutOfRange)?;
+    Ok(secret_key.add_tweak(&tweak)?)
+}
+
+/// Calculates the public key of the key
+pub(crate) fn public_key(secret_key: &SecretKey) -> PublicKey {
+    secret_key.public_key(secp256k1_context())
+}
+
+/// Parses the 33 byte compressed or the 65 byte uncompressed public key
+pub(crate) fn public_key_from_slice(slice: &[u8]) -> Result<PublicKey> {
+    PublicKey::from_slice(slice).map_err(|_| Error::InvalidPublicKey)
+}
+
+/// Returns the 65 byte uncompressed public key with the 0x04 prefix
+pub(crate) fn serialize_uncompressed(public_key: &PublicKey) -> [u8; 65] {
+    public_key.serialize_uncompressed()
+}
+
+/// Returns the 33 byte compressed public key
+pub(crate) fn serialize_compressed(public_key: &PublicKey) -> [u8; 33] {
+    public_key.serialize()
+}
+
+/// Adds the points of the public keys, fails if the sum is the point at infinity
+pub(crate) fn combine(public_key: &PublicKey, other: &PublicKey) -> Result<PublicKey> {
+    Ok(public_key.combine(other)?)
+}
+
+/// Signs the 32 byte digest with RFC 6979 nonce, returns the low-s `r || s` and the y parity
+pub(crate) fn sign_recoverable(secret_key: &SecretKey, digest: &[u8; 32]) -> ([u8; 64], bool) {
+    let message = Message::from_slice(digest).expect("digest is 32 bytes long");
+    let (recovery_id, compact) = secp256k1_context()
+        .sign_ecdsa_recoverable(&message, secret_key)
+        .serialize_compact();
+    (compact, recovery_id.to_i32() == 1)
+}
+
+/// Recovers the public key that signed the 32 byte digest from `r || s` and the recovery id
+pub(crate) fn recover(
+    digest: &[u8; 32],
+    signature: &[u8; 64],
+    recovery_id: u8,
+) -> Result<PublicKey> {
+    let message = Message::from_slice(digest).expect("digest is 32 bytes long");
+    let recovery_id = RecoveryId::from_i32(recovery_id.into())
+        .map_err(|_| Error::InvalidRecoveryId(recovery_id.into()))?;
+    let signature = RecoverableSignature::from_compact(signature, recovery_id)?;
+    Ok(secp256k1_context().recover_ecdsa(&message, &signature)?)
+}
+
+/// Verifies `r || s` of the 32 byte digest against the public key
+pub(crate) fn verify(digest: &[u8; 32], signature: &[u8; 64], public_key: &PublicKey) -> bool {
+    let message = Message::from_slice(digest).expect("digest is 32 bytes long");
+    let Ok(signature) = secp256k1::ecdsa::Signature::from_compact(signature) else {
+        return false;
+    };
+    secp256k1_context()
+        .verify_ecdsa(&message, &signature, public_key)
+        .is_ok()
+}
+
+/// Parses the DER encoded signature into `r || s`
+pub(crate) fn signature_from_der(der: &[u8]) -> Result<[u8; 64]> {
+    Ok(secp256k1::ecdsa::Signature::from_der(der)?.serialize_compact())
+}
@@ -0,0 +1,171 @@
+#[cfg(feature = "serde-secret")]
+use crate::PrivateKey;
+use crate::{Address, PublicKey};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Display;
+use core::str::FromStr;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde-secret")]
+use zeroize::Zeroizing;
+
+/// Serializes the address as the EIP-55 checksummed hex string
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_checksum())
+    }
+}
+
+/// Deserializes the address from the hex string, the `0x` prefix is optional and mixed-case
+/// strings have to have a valid checksum
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_from_str(deserializer)
+    }
+}
+
+/// Serializes the public key as the 65 byte uncompressed hex string with the `0x04` prefix
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", self.to_hex_full()))
+    }
+}
+
+/// Deserializes the public key from the hex string of the 33 byte compressed key or the
+/// uncompressed key with or without the 0x04 prefix, the `0x` prefix is optional
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_from_str(deserializer)
+    }
+}
+
+/// Serializes the private key as the 32 byte hex string with the `0x` prefix.
+///
+/// **Warning:** the serialized key is in plain text. Only serialize keys of test fixtures, or into
+/// storage that is encrypted at rest, never into logs or API responses.
+#[cfg(feature = "serde-secret")]
+impl Serialize for PrivateKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hex = Zeroizing::new(format!("0x{}", self.expose_secret_hex().as_str()));
+        serializer.serialize_str(&hex)
+    }
+}
+
+/// Deserializes the private key from the hex string, the `0x` prefix is optional. The string is
+/// wiped from memory after parsing, but copies made by the deserializer aren't.
+#[cfg(feature = "serde-secret")]
+impl<'de> Deserialize<'de> for PrivateKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = Zeroizing::new(String::deserialize(deserializer)?);
+        hex.parse().map_err(D::Error::custom)
+    }
+}
+
+/// Parses the string with `FromStr`, reporting the parsing error as a custom serde error
+fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(D::Error::custom)
+}
+
+/// Serializes the address as the lowercase hex string instead of the checksummed one, use it as
+/// `#[serde(with = "ethereum_private_key_to_address::address_lowercase")]`
+pub mod address_lowercase {
+    use super::*;
+
+    /// Serializes the address as the lowercase hex string with the `0x` prefix
+    pub fn serialize<S: Serializer>(address: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&address.to_lowercase())
+    }
+
+    /// Deserializes the address the same way as `Address::deserialize()`
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        Address::deserialize(deserializer)
+    }
+}
+
+/// Serializes the public key as the 33 byte compressed hex string instead of the uncompressed one,
+/// use it as `#[serde(with = "ethereum_private_key_to_address::public_key_compressed")]`
+pub mod public_key_compressed {
+    use super::*;
+
+    /// Serializes the public key as the 33 byte compressed hex string with the `0x` prefix
+    pub fn serialize<S: Serializer>(
+        public_key: &PublicKey,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", public_key.to_hex_compressed()))
+    }
+
+    /// Deserializes the public key the same way as `PublicKey::deserialize()`
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PublicKey, D::Error> {
+        PublicKey::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Account {
+        address: Address,
+        #[serde(with = "address_lowercase")]
+        lowercase: Address,
+        public_key: PublicKey,
+        #[serde(with = "public_key_compressed")]
+        compressed: PublicKey,
+    }
+
+    #[test]
+    fn test_serde() {
+        let public_key = PublicKey::from_str("8318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed753547f11ca8696646f2f3acb08e31016afac23e630c5d11f59f61fef57b0d2aa5").unwrap();
+        let account = Account {
+            address: public_key.address(),
+            lowercase: public_key.address(),
+            public_key,
+            compressed: public_key,
+        };
+
+        let value = serde_json::to_value(&account).unwrap();
+        assert_eq!(
+            json!({
+                "address": "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+                "lowercase": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "public_key": "0x048318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed753547f11ca8696646f2f3acb08e31016afac23e630c5d11f59f61fef57b0d2aa5",
+                "compressed": "0x038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75",
+            }),
+            value
+        );
+        assert_eq!(account, serde_json::from_value(value).unwrap());
+
+        assert!(
+            serde_json::from_str::<Address>("\"0xF39fd6e51aad88f6f4ce6ab8827279cfffb92266\"")
+                .is_err()
+        );
+        assert!(serde_json::from_str::<PublicKey>("\"0x04\"").is_err());
+    }
+
+    #[cfg(feature = "serde-secret")]
+    #[test]
+    fn test_serde_private_key() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let json = serde_json::to_string(&private_key).unwrap();
+        assert_eq!(
+            "\"0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80\"",
+            json
+        );
+        assert_eq!(private_key, serde_json::from_str(&json).unwrap());
+        assert!(serde_json::from_str::<PrivateKey>("\"0x00\"").is_err());
+    }
+}
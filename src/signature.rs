@@ -0,0 +1,611 @@
+use crate::utils::strip_hex_prefix;
+use crate::{backend, hash_message, Address, Error, PrivateKey, PublicKey, Result};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+use core::str::FromStr;
+
+/// Order of the secp256k1 curve
+const CURVE_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Half of the curve order, the largest s of the low-s form
+const HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Convention of encoding the recovery id (the y parity) into `v`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VEncoding {
+    /// 27 or 28, used by `personal_sign`, `eth_sign`, `ecrecover` and pre EIP-155 transactions
+    Legacy,
+    /// `chain_id * 2 + 35` or `chain_id * 2 + 36`, used by EIP-155 legacy transactions
+    Eip155(u64),
+    /// 0 or 1, used by typed transactions (EIP-2930, EIP-1559 and later)
+    Parity,
+}
+
+impl VEncoding {
+    /// Encodes the y parity into `v`. Fails with `InvalidRecoveryId(u64::MAX)` if the EIP-155
+    /// chain id is so large that `v` doesn't fit into `u64`.
+    pub fn encode(&self, y_parity: bool) -> Result<u64> {
+        self.offset()
+            .and_then(|offset| offset.checked_add(y_parity as u64))
+            .ok_or(Error::InvalidRecoveryId(u64::MAX))
+    }
+
+    /// Decodes the y parity from `v`, fails if `v` isn't valid for this encoding
+    pub fn decode(&self, v: u64) -> Result<bool> {
+        match self.offset().and_then(|offset| v.checked_sub(offset)) {
+            Some(0) => Ok(false),
+            Some(1) => Ok(true),
+            _ => Err(Error::InvalidRecoveryId(v)),
+        }
+    }
+
+    /// Returns `v` of the even y parity, `None` if it overflows
+    fn offset(&self) -> Option<u64> {
+        match self {
+            Self::Legacy => Some(27),
+            Self::Eip155(chain_id) => chain_id.checked_mul(2)?.checked_add(35),
+            Self::Parity => Some(0),
+        }
+    }
+}
+
+/// Recoverable ECDSA signature over secp256k1, as used by Ethereum.
+///
+/// The signature is always in the canonical low-s form. It's serialized into 65 bytes
+/// `r || s || v` where `v` is 27 or 28, which is the format of `personal_sign` and `eth_sign`.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{PrivateKey, Signature};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let signature = private_key.sign_message("hello world");
+///
+/// assert_eq!(signature, Signature::from_str(&signature.to_string()).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signature {
+    /// r
+    r: [u8; 32],
+    /// s
+    s: [u8; 32],
+    /// Parity of the y coordinate of the curve point r, the recovery id
+    y_parity: bool,
+}
+
+impl Signature {
+    /// Creates the signature from r, s and the y parity (recovery id). The signature is
+    /// normalized to the low-s form, see `normalize()`. Fails if s isn't below the curve order,
+    /// such s can't be normalized and no key signs with it.
+    pub fn new(r: [u8; 32], s: [u8; 32], y_parity: bool) -> Result<Self> {
+        if s >= CURVE_ORDER {
+            return Err(Error::SignatureOutOfRange);
+        }
+        Ok(Self { r, s, y_parity }.normalize())
+    }
+
+    /// Returns true if s is at most half of the curve order, which Ethereum requires since
+    /// EIP-2. Signatures created by this crate are always low-s.
+    pub fn is_low_s(&self) -> bool {
+        self.s <= HALF_ORDER
+    }
+
+    /// Returns the low-s form of the signature. If s is above half of the curve order it's
+    /// replaced by `n - s` and the y parity is flipped, both forms are valid for the same key and
+    /// message but only the low-s form is accepted by Ethereum. Every constructor normalizes
+    /// the signature, so external signatures (e.g. from HSMs) are accepted in both forms.
+    pub fn normalize(&self) -> Self {
+        if self.is_low_s() {
+            return *self;
+        }
+        let mut s = [0u8; 32];
+        let mut borrow = false;
+        let digits = s.iter_mut().zip(CURVE_ORDER.iter().zip(self.s.iter()));
+        for (digit, (order, subtrahend)) in digits.rev() {
+            let (difference, overflow) = order.overflowing_sub(*subtrahend);
+            let (difference, overflow_borrow) = difference.overflowing_sub(borrow as u8);
+            *digit = difference;
+            borrow = overflow || overflow_borrow;
+        }
+        Self {
+            r: self.r,
+            s,
+            y_parity: !self.y_parity,
+        }
+    }
+
+    /// Parses 65 bytes `r || s || v`, where `v` can be 0, 1, 27 or 28
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 65 {
+            return Err(Error::InvalidLength {
+                expected: "65",
+                actual: bytes.len(),
+            });
+        }
+        let y_parity = match bytes[64] {
+            0 | 27 => false,
+            1 | 28 => true,
+            v => return Err(Error::InvalidRecoveryId(v.into())),
+        };
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&bytes[..32]);
+        s.copy_from_slice(&bytes[32..64]);
+        Self::new(r, s, y_parity)
+    }
+
+    /// Returns r
+    pub fn r(&self) -> [u8; 32] {
+        self.r
+    }
+
+    /// Returns s
+    pub fn s(&self) -> [u8; 32] {
+        self.s
+    }
+
+    /// Returns the parity of the y coordinate of r, the recovery id
+    pub fn y_parity(&self) -> bool {
+        self.y_parity
+    }
+
+    /// Returns `v` as 27 or 28
+    pub fn v(&self) -> u8 {
+        27 + self.y_parity as u8
+    }
+
+    /// Returns the recovery id, 0 or 1
+    pub fn recovery_id(&self) -> u8 {
+        self.y_parity as u8
+    }
+
+    /// Returns `v` in the given encoding
+    /// ```
+    /// use core::str::FromStr;
+    /// use ethereum_private_key_to_address::{PrivateKey, VEncoding};
+    ///
+    /// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+    /// let signature = private_key.sign_hash(&[1; 32]);
+    ///
+    /// let v = signature.v_with(VEncoding::Eip155(1)).unwrap();
+    /// assert!(v == 37 || v == 38);
+    /// ```
+    pub fn v_with(&self, encoding: VEncoding) -> Result<u64> {
+        encoding.encode(self.y_parity)
+    }
+
+    /// Creates the signature from r, s and `v` in the given encoding
+    pub fn from_rsv(r: [u8; 32], s: [u8; 32], v: u64, encoding: VEncoding) -> Result<Self> {
+        Self::new(r, s, encoding.decode(v)?)
+    }
+
+    /// Serializes the signature into 65 bytes `r || s || v` where `v` is 27 or 28
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..64].copy_from_slice(&self.s);
+        bytes[64] = self.v();
+        bytes
+    }
+
+    /// Parses the 64 byte compact signature `r || yParityAndS` (EIP-2098), where the highest
+    /// bit of s holds the y parity
+    pub fn from_compact(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 64 {
+            return Err(Error::InvalidLength {
+                expected: "64",
+                actual: bytes.len(),
+            });
+        }
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&bytes[..32]);
+        s.copy_from_slice(&bytes[32..]);
+        let y_parity = s[0] & 0x80 != 0;
+        s[0] &= 0x7f;
+        Self::new(r, s, y_parity)
+    }
+
+    /// Serializes the signature into 64 byte compact form `r || yParityAndS` (EIP-2098). The
+    /// conversion is lossless because s is always in the low-s form, so its highest bit is free.
+    pub fn to_compact(&self) -> [u8; 64] {
+        let mut bytes = self.to_rs();
+        bytes[32] |= (self.y_parity as u8) << 7;
+        bytes
+    }
+
+    /// Parses r and s from the ASN.1 DER `SEQUENCE { INTEGER r, INTEGER s }` used by HSMs and
+    /// most non-Ethereum tools. DER doesn't contain the recovery id, so the y parity must be
+    /// given.
+    pub fn from_der(der: &[u8], y_parity: bool) -> Result<Self> {
+        let compact = backend::signature_from_der(der)?;
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&compact[..32]);
+        s.copy_from_slice(&compact[32..]);
+        Self::new(r, s, y_parity)
+    }
+
+    /// Returns the signature with the y parity that recovers the public key. Used for signers
+    /// that return only r and s, e.g. a KMS or an HSM, whose public key is known.
+    pub fn recover_parity(self, digest: &[u8; 32], public_key: &PublicKey) -> Result<Self> {
+        [false, true]
+            .into_iter()
+            .map(|y_parity| Self { y_parity, ..self })
+            .find(|signature| {
+                recover_public_key(digest, signature).is_ok_and(|key| key == *public_key)
+            })
+            .ok_or(Error::InvalidSignature)
+    }
+
+    /// Serializes r and s into ASN.1 DER `SEQUENCE { INTEGER r, INTEGER s }`, the recovery id
+    /// is left out
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut body = der_integer(&self.r);
+        body.extend(der_integer(&self.s));
+
+        let mut der = vec![0x30, body.len() as u8];
+        der.extend(body);
+        der
+    }
+
+    /// Returns the 64 byte `r || s` without the recovery id
+    pub fn to_rs(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..].copy_from_slice(&self.s);
+        bytes
+    }
+
+    /// Returns the signature as 0x prefixed hex string of `r || s || v`
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.to_bytes()))
+    }
+}
+
+/// Encodes the unsigned big-endian integer as DER `INTEGER`
+fn der_integer(value: &[u8; 32]) -> Vec<u8> {
+    let start = value
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(value.len() - 1);
+    let value = &value[start..];
+
+    let mut integer = vec![0x02];
+    // Integers are signed, so a leading zero keeps the value positive
+    if value[0] & 0x80 != 0 {
+        integer.extend([value.len() as u8 + 1, 0x00]);
+    } else {
+        integer.push(value.len() as u8);
+    }
+    integer.extend_from_slice(value);
+    integer
+}
+
+impl FromStr for Signature {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_slice(&hex::decode(strip_hex_prefix(s))?)
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl From<Signature> for [u8; 65] {
+    fn from(signature: Signature) -> Self {
+        signature.to_bytes()
+    }
+}
+
+impl TryFrom<&[u8]> for Signature {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(bytes)
+    }
+}
+
+/// Verifies the signature of the message signed by `personal_sign` (see `hash_message()`) was
+/// created by the private key of the address
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{verify, PrivateKey};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let signature = private_key.sign_message("hello world");
+///
+/// assert!(verify("hello world", &signature, &private_key.to_address()));
+/// ```
+pub fn verify(message: impl AsRef<[u8]>, signature: &Signature, address: &Address) -> bool {
+    recover_public_key(&hash_message(message), signature)
+        .is_ok_and(|public_key| public_key.address() == *address)
+}
+
+/// Verifies the signature of the 32 byte digest against the public key. The recovery id isn't
+/// needed, only r and s are checked.
+pub fn verify_prehashed(digest: &[u8; 32], signature: &Signature, public_key: &PublicKey) -> bool {
+    backend::verify(digest, &signature.to_rs(), public_key.as_backend())
+}
+
+/// Recovers the address that signed the message by `personal_sign`, the message is hashed by
+/// `hash_message()` first. This is what `ecrecover` returns for the EIP-191 prefixed hash.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{recover_address, Signature};
+///
+/// let signature = Signature::from_str("0xa461f509887bd19e312c0c58467ce8ff8e300d3c1a90b608a760c5b80318eaf15fe57c96f9175d6cd4daad4663763baa7e78836e067d0163e9a2ccf2ff753f5b1b").unwrap();
+/// let address = recover_address("hello world", &signature).unwrap();
+///
+/// assert_eq!("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266", address.to_string());
+/// ```
+pub fn recover_address(message: impl AsRef<[u8]>, signature: &Signature) -> Result<Address> {
+    recover_address_prehashed(&hash_message(message), signature)
+}
+
+/// Recovers the address that signed the 32 byte digest, the same as Solidity's `ecrecover`.
+/// Use this for digests that are already hashed, like EIP-712 or transaction hashes.
+pub fn recover_address_prehashed(digest: &[u8; 32], signature: &Signature) -> Result<Address> {
+    Ok(recover_public_key(digest, signature)?.address())
+}
+
+/// Recovers the public key that signed the 32 byte digest
+pub(crate) fn recover_public_key(digest: &[u8; 32], signature: &Signature) -> Result<PublicKey> {
+    PublicKey::recover(digest, &signature.to_rs(), signature.recovery_id())
+}
+
+impl PrivateKey {
+    /// Signs the 32 byte hash with deterministic RFC 6979 nonce. The hash is signed as is, use
+    /// `sign_message()` to sign a message the same way as `personal_sign`.
+    pub fn sign_hash(&self, hash: &[u8; 32]) -> Signature {
+        let (compact, y_parity) = backend::sign_recoverable(&self.private_key, hash);
+
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&compact[..32]);
+        s.copy_from_slice(&compact[32..]);
+        Signature { r, s, y_parity }.normalize()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const SIGNATURE: &str = "0xa461f509887bd19e312c0c58467ce8ff8e300d3c1a90b608a760c5b80318eaf15fe57c96f9175d6cd4daad4663763baa7e78836e067d0163e9a2ccf2ff753f5b1b";
+
+    #[test]
+    fn test_signature_encoding() {
+        let signature = Signature::from_str(SIGNATURE).unwrap();
+        assert_eq!(27, signature.v());
+        assert!(!signature.y_parity());
+        assert_eq!(SIGNATURE, signature.to_string());
+
+        let mut bytes = signature.to_bytes();
+        bytes[64] = 0;
+        assert_eq!(signature, Signature::try_from(&bytes[..]).unwrap());
+        bytes[64] = 29;
+        assert_eq!(
+            Signature::from_slice(&bytes),
+            Err(Error::InvalidRecoveryId(29))
+        );
+        assert!(matches!(
+            Signature::from_slice(&bytes[..64]),
+            Err(Error::InvalidLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_v_encoding() {
+        let signature = Signature::from_str(SIGNATURE).unwrap();
+        assert_eq!(0, signature.recovery_id());
+        assert_eq!(Ok(27), signature.v_with(VEncoding::Legacy));
+        assert_eq!(Ok(37), signature.v_with(VEncoding::Eip155(1)));
+        assert_eq!(Ok(22310257), signature.v_with(VEncoding::Eip155(11155111)));
+        assert_eq!(Ok(0), signature.v_with(VEncoding::Parity));
+        assert_eq!(
+            Err(Error::InvalidRecoveryId(u64::MAX)),
+            signature.v_with(VEncoding::Eip155(u64::MAX / 2))
+        );
+        assert_eq!(
+            Err(Error::InvalidRecoveryId(u64::MAX)),
+            VEncoding::Eip155((u64::MAX - 35) / 2).encode(true)
+        );
+        assert_eq!(
+            Err(Error::InvalidRecoveryId(37)),
+            VEncoding::Eip155(u64::MAX).decode(37)
+        );
+
+        for encoding in [VEncoding::Legacy, VEncoding::Eip155(5), VEncoding::Parity] {
+            for y_parity in [false, true] {
+                let v = encoding.encode(y_parity).unwrap();
+                assert_eq!(Ok(y_parity), encoding.decode(v));
+                let signature = Signature::from_rsv([1; 32], [2; 32], v, encoding).unwrap();
+                assert_eq!(y_parity, signature.y_parity());
+            }
+        }
+        assert_eq!(
+            VEncoding::Eip155(1).decode(27),
+            Err(Error::InvalidRecoveryId(27))
+        );
+        assert_eq!(
+            VEncoding::Legacy.decode(29),
+            Err(Error::InvalidRecoveryId(29))
+        );
+        assert_eq!(
+            VEncoding::Parity.decode(2),
+            Err(Error::InvalidRecoveryId(2))
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        // Examples from EIP-2098
+        for (signature, compact, v) in [
+            (
+                "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b907e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+                "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b907e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+                27,
+            ),
+            (
+                "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793",
+                "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793",
+                28,
+            ),
+        ] {
+            let mut bytes = hex::decode(signature).unwrap();
+            bytes.push(v);
+            let signature = Signature::from_slice(&bytes).unwrap();
+            assert_eq!(compact, hex::encode(signature.to_compact()));
+            assert_eq!(
+                signature,
+                Signature::from_compact(&signature.to_compact()).unwrap()
+            );
+        }
+        assert!(matches!(
+            Signature::from_compact(&[0; 65]),
+            Err(Error::InvalidLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_der() {
+        let signature = Signature::from_str(SIGNATURE).unwrap();
+        let der = signature.to_der();
+        assert_eq!(
+            "3045022100a461f509887bd19e312c0c58467ce8ff8e300d3c1a90b608a760c5b80318eaf102205fe57c96f9175d6cd4daad4663763baa7e78836e067d0163e9a2ccf2ff753f5b",
+            hex::encode(&der)
+        );
+        assert_eq!(signature, Signature::from_der(&der, false).unwrap());
+
+        let mut r = [0u8; 32];
+        r[31] = 1;
+        let short = Signature::new(r, [0x7f; 32], true).unwrap();
+        assert_eq!(
+            format!("30250201010220{}", "7f".repeat(32)),
+            hex::encode(short.to_der())
+        );
+        assert_eq!(short, Signature::from_der(&short.to_der(), true).unwrap());
+
+        assert!(matches!(
+            Signature::from_der(&der[..der.len() - 1], false),
+            Err(Error::Secp256k1(_))
+        ));
+    }
+
+    #[test]
+    fn test_recover_parity() {
+        let signature = Signature::from_str(SIGNATURE).unwrap();
+        let digest = hash_message("hello world");
+        let public_key = recover_public_key(&digest, &signature).unwrap();
+
+        let without_parity = Signature::from_der(&signature.to_der(), true).unwrap();
+        assert_eq!(
+            signature,
+            without_parity.recover_parity(&digest, &public_key).unwrap()
+        );
+        assert_eq!(
+            without_parity.recover_parity(&[0u8; 32], &public_key),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let signature = Signature::from_str(SIGNATURE).unwrap();
+        assert!(signature.is_low_s());
+        assert_eq!(signature, signature.normalize());
+
+        // Same signature with s replaced by n - s and flipped parity
+        let high_s = "0xa461f509887bd19e312c0c58467ce8ff8e300d3c1a90b608a760c5b80318eaf1a01a836906e8a2932b2552b99c89c4543c365978a8cb9ed7d62f9199d0c101e61c";
+        let normalized = Signature::from_str(high_s).unwrap();
+        assert_eq!(signature, normalized);
+        assert!(normalized.is_low_s());
+
+        let der = format!("3046022100{}022100{}", &high_s[2..66], &high_s[66..130]);
+        assert_eq!(
+            signature,
+            Signature::from_der(&hex::decode(der).unwrap(), true).unwrap()
+        );
+
+        // s equal to or above the curve order can't be normalized
+        assert_eq!(
+            Signature::new(signature.r(), CURVE_ORDER, false),
+            Err(Error::SignatureOutOfRange)
+        );
+        let mut bytes = signature.to_bytes();
+        bytes[32..64].copy_from_slice(&[0xff; 32]);
+        assert_eq!(
+            Signature::from_slice(&bytes),
+            Err(Error::SignatureOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let address = private_key.to_address();
+        let signature = Signature::from_str(SIGNATURE).unwrap();
+        assert!(verify("hello world", &signature, &address));
+        assert!(!verify("hello world!", &signature, &address));
+        assert!(!verify(
+            "hello world",
+            &signature,
+            &PrivateKey::insecure_random_from_seed(1).to_address()
+        ));
+
+        let digest = hash_message("hello world");
+        let public_key = private_key.to_public_key();
+        assert!(verify_prehashed(&digest, &signature, &public_key));
+        // The recovery id doesn't matter for verification with the public key
+        let flipped = Signature::new(signature.r(), signature.s(), true).unwrap();
+        assert!(verify_prehashed(&digest, &flipped, &public_key));
+        assert!(!verify(b"hello world", &flipped, &address));
+        assert!(!verify_prehashed(&[0; 32], &signature, &public_key));
+        assert!(!verify_prehashed(
+            &digest,
+            &Signature::new([0; 32], [0; 32], false).unwrap(),
+            &public_key
+        ));
+    }
+
+    #[test]
+    fn test_recover_address() {
+        let address = Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap();
+        let signature = Signature::from_str(SIGNATURE).unwrap();
+        assert_eq!(address, recover_address("hello world", &signature).unwrap());
+        assert_eq!(
+            address,
+            recover_address_prehashed(&hash_message("hello world"), &signature).unwrap()
+        );
+        assert_ne!(address, recover_address("hello", &signature).unwrap());
+
+        let private_key = PrivateKey::insecure_random_from_seed(7);
+        let digest = [0x42; 32];
+        assert_eq!(
+            private_key.to_address(),
+            recover_address_prehashed(&digest, &private_key.sign_hash(&digest)).unwrap()
+        );
+
+        // r must be a valid x coordinate
+        let invalid = Signature::new([0; 32], signature.s(), false).unwrap();
+        assert!(matches!(
+            recover_address("hello world", &invalid),
+            Err(Error::Secp256k1(_))
+        ));
+    }
+}
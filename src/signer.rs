@@ -0,0 +1,94 @@
+use crate::{hash_message, Address, PrivateKey, Result, Signature, Transaction};
+use alloc::vec::Vec;
+
+/// Anything that can sign on behalf of an address, e.g. a local private key, a hardware wallet
+/// or a remote signer. Only `address()` and `sign_digest()` have to be implemented, the other
+/// methods are built on top of them.
+///
+/// The trait is object safe, so signers of different kinds can be used as `Box<dyn Signer>`.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{verify, PrivateKey, Signer};
+///
+/// fn sign_in(signer: &dyn Signer) -> bool {
+///     let signature = signer.sign_message(b"hello world").unwrap();
+///     verify("hello world", &signature, &signer.address())
+/// }
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+///
+/// assert!(sign_in(&private_key));
+/// ```
+pub trait Signer {
+    /// Returns the address of the signer
+    fn address(&self) -> Address;
+
+    /// Signs the 32 byte digest as is
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Signature>;
+
+    /// Signs the message the same way as `personal_sign`, the message is hashed by
+    /// `hash_message()`
+    fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        self.sign_digest(&hash_message(message))
+    }
+
+    /// Signs the transaction and returns the raw signed transaction
+    fn sign_transaction(&self, transaction: &dyn Transaction) -> Result<Vec<u8>> {
+        let signature = self.sign_digest(&transaction.signing_hash())?;
+        Ok(transaction.encode_signed(&signature))
+    }
+}
+
+impl Signer for PrivateKey {
+    fn address(&self) -> Address {
+        self.to_address()
+    }
+
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Signature> {
+        Ok(self.sign_hash(digest))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::Eip1559Transaction;
+    use std::str::FromStr;
+
+    fn signer() -> Box<dyn Signer> {
+        Box::new(
+            PrivateKey::from_str(
+                "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_private_key_signer() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let signer = signer();
+        assert_eq!(private_key.to_address(), signer.address());
+
+        let digest = [7u8; 32];
+        assert_eq!(
+            private_key.sign_hash(&digest),
+            signer.sign_digest(&digest).unwrap()
+        );
+        assert_eq!(
+            private_key.sign_message("hello world"),
+            signer.sign_message(b"hello world").unwrap()
+        );
+
+        let transaction = Eip1559Transaction::new(1)
+            .with_nonce(3)
+            .with_max_fee_per_gas(30_000_000_000);
+        assert_eq!(
+            private_key.sign_transaction(&transaction),
+            signer.sign_transaction(&transaction).unwrap()
+        );
+    }
+}
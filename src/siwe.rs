@@ -0,0 +1,537 @@
+use crate::{verify, Address, Error, PrivateKey, Result, Signature};
+use rand_core::{CryptoRngCore, OsRng};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Text after the domain on the first line of the message
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+/// Characters of the generated nonce
+const NONCE_ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Length of the generated nonce
+const NONCE_LENGTH: usize = 17;
+
+/// Sign-In with Ethereum (EIP-4361) message. The message is signed as a personal message, the
+/// same way as `personal_sign`.
+///
+/// `new()` generates a random nonce and sets the issued at time to now, override them with
+/// `with_nonce()` and `with_issued_at()` when the server issued them. The times are RFC 3339
+/// strings, e.g. `2021-09-30T16:25:24Z`.
+/// ```
+/// use std::str::FromStr;
+/// use ethereum_private_key_to_address::{PrivateKey, SiweMessage};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+///
+/// let message = SiweMessage::new("example.com", private_key.to_address(), "https://example.com/login", 1)
+///     .with_statement("I accept the Terms of Service")
+///     .with_nonce("32891756")
+///     .with_issued_at("2021-09-30T16:25:24Z");
+/// let signature = message.sign(&private_key);
+///
+/// let received = SiweMessage::from_str(&message.to_string()).unwrap();
+/// assert!(received.verify(&signature));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SiweMessage {
+    /// Scheme of the origin, e.g. `https`
+    scheme: Option<String>,
+    /// RFC 3986 authority that requests the signing
+    domain: String,
+    /// Address of the signer
+    address: Address,
+    /// Human readable assertion the user signs, must not contain a newline
+    statement: Option<String>,
+    /// RFC 3986 URI of the resource that is the subject of the signing
+    uri: String,
+    /// EIP-155 chain id the session is bound to
+    chain_id: u64,
+    /// At least 8 alphanumeric characters against replay attacks
+    nonce: String,
+    /// Time the message was issued
+    issued_at: String,
+    /// Time after which the message is no longer valid
+    expiration_time: Option<String>,
+    /// Time before which the message isn't valid yet
+    not_before: Option<String>,
+    /// System specific identifier of the request
+    request_id: Option<String>,
+    /// URIs the user wishes to have resolved as part of the authentication
+    resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Creates the message with a random nonce issued now
+    pub fn new(domain: &str, address: Address, uri: &str, chain_id: u64) -> Self {
+        Self {
+            scheme: None,
+            domain: domain.to_string(),
+            address,
+            statement: None,
+            uri: uri.to_string(),
+            chain_id,
+            nonce: generate_nonce(&mut OsRng),
+            issued_at: format_time(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("system time is after the unix epoch")
+                    .as_secs(),
+            ),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+
+    /// Sets the scheme of the origin, e.g. `https`
+    pub fn with_scheme(mut self, scheme: &str) -> Self {
+        self.scheme = Some(scheme.to_string());
+        self
+    }
+
+    /// Sets the statement shown to the user.
+    ///
+    /// Panics if the statement contains a newline.
+    pub fn with_statement(mut self, statement: &str) -> Self {
+        assert!(
+            !statement.contains('\n'),
+            "statement must not contain a newline"
+        );
+        self.statement = Some(statement.to_string());
+        self
+    }
+
+    /// Sets the nonce issued by the server.
+    ///
+    /// Panics if the nonce isn't at least 8 alphanumeric characters.
+    pub fn with_nonce(mut self, nonce: &str) -> Self {
+        assert!(
+            is_valid_nonce(nonce),
+            "nonce must be at least 8 alphanumeric characters"
+        );
+        self.nonce = nonce.to_string();
+        self
+    }
+
+    /// Sets the time the message was issued
+    pub fn with_issued_at(mut self, issued_at: &str) -> Self {
+        self.issued_at = issued_at.to_string();
+        self
+    }
+
+    /// Sets the time after which the message is no longer valid
+    pub fn with_expiration_time(mut self, expiration_time: &str) -> Self {
+        self.expiration_time = Some(expiration_time.to_string());
+        self
+    }
+
+    /// Sets the time before which the message isn't valid yet
+    pub fn with_not_before(mut self, not_before: &str) -> Self {
+        self.not_before = Some(not_before.to_string());
+        self
+    }
+
+    /// Sets the identifier of the request
+    pub fn with_request_id(mut self, request_id: &str) -> Self {
+        self.request_id = Some(request_id.to_string());
+        self
+    }
+
+    /// Adds the resource to the list of resources
+    pub fn with_resource(mut self, resource: &str) -> Self {
+        self.resources.push(resource.to_string());
+        self
+    }
+
+    /// Returns the scheme of the origin
+    pub fn scheme(&self) -> Option<&str> {
+        self.scheme.as_deref()
+    }
+
+    /// Returns the domain
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Returns the address of the signer
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns the statement
+    pub fn statement(&self) -> Option<&str> {
+        self.statement.as_deref()
+    }
+
+    /// Returns the URI
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns the chain id
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Returns the nonce
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// Returns the time the message was issued
+    pub fn issued_at(&self) -> &str {
+        &self.issued_at
+    }
+
+    /// Returns the expiration time
+    pub fn expiration_time(&self) -> Option<&str> {
+        self.expiration_time.as_deref()
+    }
+
+    /// Returns the not before time
+    pub fn not_before(&self) -> Option<&str> {
+        self.not_before.as_deref()
+    }
+
+    /// Returns the request id
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// Returns the resources
+    pub fn resources(&self) -> &[String] {
+        &self.resources
+    }
+
+    /// Signs the message as a personal message
+    pub fn sign(&self, private_key: &PrivateKey) -> Signature {
+        private_key.sign_message(self.to_string())
+    }
+
+    /// Verifies that the signature of the message was made by the address of the message. Only
+    /// the signature is checked, the server must also check the domain, the nonce and the times.
+    pub fn verify(&self, signature: &Signature) -> bool {
+        verify(self.to_string(), signature, &self.address)
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{scheme}://")?;
+        }
+        writeln!(f, "{}{PREAMBLE}", self.domain)?;
+        writeln!(f, "{}", self.address.to_checksum())?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: 1")?;
+        writeln!(f, "Chain ID: {}", self.chain_id)?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {expiration_time}")?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\nNot Before: {not_before}")?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {request_id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {resource}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = Error;
+
+    /// Parses the message in the EIP-4361 format, e.g. the message received from the client
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.split('\n');
+        let mut next = |expected: &str| {
+            lines
+                .next()
+                .ok_or_else(|| invalid(&format!("missing {expected}")))
+        };
+
+        let origin = next("preamble")?
+            .strip_suffix(PREAMBLE)
+            .ok_or_else(|| invalid("invalid preamble"))?;
+        let (scheme, domain) = match origin.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme.to_string()), domain),
+            None => (None, origin),
+        };
+        if domain.is_empty() {
+            return Err(invalid("missing domain"));
+        }
+
+        let address = next("address")?;
+        let parsed = Address::from_str(address).map_err(|_| invalid("invalid address"))?;
+        if parsed.to_checksum() != address {
+            return Err(invalid("address must be EIP-55 checksummed"));
+        }
+
+        if !next("empty line")?.is_empty() {
+            return Err(invalid("expected empty line after the address"));
+        }
+        let statement = match next("statement")? {
+            "" => None,
+            statement => {
+                if !next("empty line")?.is_empty() {
+                    return Err(invalid("expected empty line after the statement"));
+                }
+                Some(statement.to_string())
+            }
+        };
+
+        let uri = tagged(next("URI")?, "URI")?;
+        if tagged(next("version")?, "Version")? != "1" {
+            return Err(invalid("unsupported version"));
+        }
+        let chain_id = tagged(next("chain id")?, "Chain ID")?
+            .parse()
+            .map_err(|_| invalid("invalid chain id"))?;
+        let nonce = tagged(next("nonce")?, "Nonce")?;
+        if !is_valid_nonce(nonce) {
+            return Err(invalid("nonce must be at least 8 alphanumeric characters"));
+        }
+        let issued_at = tagged(next("issued at")?, "Issued At")?;
+
+        let mut message = Self {
+            scheme,
+            domain: domain.to_string(),
+            address: parsed,
+            statement,
+            uri: uri.to_string(),
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at: issued_at.to_string(),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        };
+
+        // The optional fields must come in this order
+        let mut line = lines.next();
+        if let Some(value) = line.and_then(|line| line.strip_prefix("Expiration Time: ")) {
+            message.expiration_time = Some(value.to_string());
+            line = lines.next();
+        }
+        if let Some(value) = line.and_then(|line| line.strip_prefix("Not Before: ")) {
+            message.not_before = Some(value.to_string());
+            line = lines.next();
+        }
+        if let Some(value) = line.and_then(|line| line.strip_prefix("Request ID: ")) {
+            message.request_id = Some(value.to_string());
+            line = lines.next();
+        }
+        if line == Some("Resources:") {
+            line = lines.next();
+            while let Some(resource) = line.and_then(|line| line.strip_prefix("- ")) {
+                message.resources.push(resource.to_string());
+                line = lines.next();
+            }
+        }
+        if let Some(line) = line {
+            return Err(invalid(&format!("unexpected line \"{line}\"")));
+        }
+
+        Ok(message)
+    }
+}
+
+/// Returns the value of the line `tag: value`
+fn tagged<'a>(line: &'a str, tag: &str) -> Result<&'a str> {
+    line.strip_prefix(tag)
+        .and_then(|line| line.strip_prefix(": "))
+        .ok_or_else(|| invalid(&format!("expected \"{tag}: \"")))
+}
+
+/// Returns true if the nonce is at least 8 alphanumeric characters
+fn is_valid_nonce(nonce: &str) -> bool {
+    nonce.len() >= 8 && nonce.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Generates the random alphanumeric nonce
+fn generate_nonce(rng: &mut impl CryptoRngCore) -> String {
+    let mut nonce = String::with_capacity(NONCE_LENGTH);
+    while nonce.len() < NONCE_LENGTH {
+        // Rejects the bytes above the largest multiple of the alphabet length so every
+        // character is equally likely
+        let byte = (rng.next_u32() & 0xff) as usize;
+        if byte < 256 - 256 % NONCE_ALPHABET.len() {
+            nonce.push(NONCE_ALPHABET[byte % NONCE_ALPHABET.len()] as char);
+        }
+    }
+    nonce
+}
+
+/// Formats the unix timestamp as RFC 3339 UTC time, e.g. `2021-09-30T16:25:24Z`
+fn format_time(timestamp: u64) -> String {
+    let days = timestamp / 86400;
+    let seconds = timestamp % 86400;
+
+    // Civil date from the days since the epoch, shifted so the year starts in March
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Creates the error of invalid SIWE message
+fn invalid(reason: &str) -> Error {
+    Error::InvalidSiweMessage(reason.to_string())
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    const MESSAGE: &str = "service.org wants you to sign in with your Ethereum account:
+0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    fn message() -> SiweMessage {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        SiweMessage::new(
+            "service.org",
+            private_key.to_address(),
+            "https://service.org/login",
+            1,
+        )
+        .with_statement("I accept the ServiceOrg Terms of Service: https://service.org/tos")
+        .with_nonce("32891756")
+        .with_issued_at("2021-09-30T16:25:24Z")
+        .with_resource("ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/")
+        .with_resource("https://example.com/my-web2-claim.json")
+    }
+
+    #[test]
+    fn test_format_and_parse() {
+        let message = message();
+        assert_eq!(MESSAGE, message.to_string());
+        assert_eq!(message, SiweMessage::from_str(MESSAGE).unwrap());
+
+        let message = message
+            .with_scheme("https")
+            .with_expiration_time("2021-10-01T16:25:24Z")
+            .with_not_before("2021-09-30T16:25:24Z")
+            .with_request_id("request-1");
+        assert_eq!(
+            message,
+            SiweMessage::from_str(&message.to_string()).unwrap()
+        );
+
+        // Without the statement there are two empty lines
+        let message = SiweMessage::new("service.org", message.address(), "https://service.org", 5)
+            .with_nonce("32891756")
+            .with_issued_at("2021-09-30T16:25:24Z");
+        assert_eq!(
+            "service.org wants you to sign in with your Ethereum account:\n0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266\n\n\nURI: https://service.org\nVersion: 1\nChain ID: 5\nNonce: 32891756\nIssued At: 2021-09-30T16:25:24Z",
+            message.to_string()
+        );
+        assert_eq!(
+            message,
+            SiweMessage::from_str(&message.to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let message = message();
+        let signature = message.sign(&private_key);
+        assert_eq!(
+            "0x5fc75587d62039cacde94928c8ae50c1b8f6d2cfc52c4464b4fd581ed45a2707594217feab3681c165550e6e82b0e8f86b39db35e8c102c5154be90ddf2729421b",
+            signature.to_string()
+        );
+        assert!(message.verify(&signature));
+        assert!(!message.clone().with_nonce("32891757").verify(&signature));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for (message, reason) in [
+            (
+                MESSAGE.replace(
+                    "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+                    "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                ),
+                "address must be EIP-55 checksummed",
+            ),
+            (
+                MESSAGE.replace("Version: 1", "Version: 2"),
+                "unsupported version",
+            ),
+            (
+                MESSAGE.replace("32891756", "1234"),
+                "nonce must be at least 8 alphanumeric characters",
+            ),
+            (MESSAGE.replace("wants you", "asks you"), "invalid preamble"),
+            (format!("{MESSAGE}\nfoo"), "unexpected line \"foo\""),
+        ] {
+            assert_eq!(
+                SiweMessage::from_str(&message),
+                Err(Error::InvalidSiweMessage(reason.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let message = SiweMessage::new(
+            "example.com",
+            Address::from([0u8; 20]),
+            "https://example.com",
+            1,
+        );
+        assert_eq!(NONCE_LENGTH, message.nonce().len());
+        assert!(is_valid_nonce(message.nonce()));
+        assert_eq!(
+            message,
+            SiweMessage::from_str(&message.to_string()).unwrap()
+        );
+
+        assert_eq!("1970-01-01T00:00:00Z", format_time(0));
+        assert_eq!("2021-09-30T16:25:24Z", format_time(1_633_019_124));
+        assert_eq!("2000-02-29T23:59:59Z", format_time(951_868_799));
+    }
+}
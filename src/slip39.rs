@@ -0,0 +1,785 @@
+use crate::slip39_wordlist::WORDLIST;
+use crate::{Error, ExtendedPrivateKey, PrivateKey, Result};
+use hmac::{Hmac, Mac};
+use rand_core::CryptoRngCore;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use zeroize::Zeroizing;
+
+/// Number of bits encoded by one word
+const RADIX_BITS: usize = 10;
+/// Number of words with the identifier, iteration exponent and sharing parameters
+const METADATA_WORDS: usize = 4;
+/// Number of checksum words
+const CHECKSUM_WORDS: usize = 3;
+/// Minimum length of the master secret in bytes
+const MIN_SECRET_LENGTH: usize = 16;
+/// Maximum number of groups and members in a group
+const MAX_SHARE_COUNT: u8 = 16;
+/// Base number of PBKDF2 iterations of the Feistel cipher, split between its 4 rounds
+const BASE_ITERATIONS: u32 = 10000;
+/// x coordinate of the share holding the digest of the secret
+const DIGEST_INDEX: u8 = 254;
+/// x coordinate of the share holding the secret itself
+const SECRET_INDEX: u8 = 255;
+
+/// SLIP-39 Shamir's secret sharing scheme used to back up a private key or master seed as
+/// multiple mnemonic shares.
+///
+/// The secret is split into groups and every group into member shares. To recover the secret,
+/// `group_threshold` groups are needed, each with at least its member threshold of shares.
+/// ```
+/// use ethereum_private_key_to_address::{PrivateKey, Slip39};
+///
+/// let private_key = PrivateKey::random();
+///
+/// // Any 2 of 3 shares recover the key
+/// let scheme = Slip39::threshold(2, 3).unwrap();
+/// let shares = private_key.to_slip39_shares(&scheme, "").unwrap();
+///
+/// let recovered = PrivateKey::from_slip39_shares(&[&shares[0][0], &shares[0][2]], "").unwrap();
+/// assert_eq!(private_key, recovered);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slip39 {
+    /// Number of groups required to recover the secret
+    group_threshold: u8,
+    /// Member threshold and member count of every group
+    groups: Vec<(u8, u8)>,
+    /// Exponent of the number of PBKDF2 iterations
+    iteration_exponent: u8,
+}
+
+impl Slip39 {
+    /// Iteration exponent used by default, the same as Trezor uses
+    pub const DEFAULT_ITERATION_EXPONENT: u8 = 1;
+
+    /// Creates the scheme with `group_threshold` of the groups required to recover the secret.
+    /// Every group is a pair of the member threshold and the member count.
+    pub fn new(group_threshold: u8, groups: &[(u8, u8)]) -> Result<Self> {
+        let invalid = |reason: String| Err(Error::InvalidSharingScheme(reason));
+
+        if groups.is_empty() || groups.len() > MAX_SHARE_COUNT as usize {
+            return invalid(format!(
+                "number of groups has to be between 1 and {MAX_SHARE_COUNT}"
+            ));
+        }
+        if group_threshold == 0 || group_threshold as usize > groups.len() {
+            return invalid(format!(
+                "group threshold has to be between 1 and {}",
+                groups.len()
+            ));
+        }
+        for &(threshold, count) in groups {
+            if count == 0 || count > MAX_SHARE_COUNT {
+                return invalid(format!(
+                    "member count has to be between 1 and {MAX_SHARE_COUNT}"
+                ));
+            }
+            if threshold == 0 || threshold > count {
+                return invalid(format!("member threshold has to be between 1 and {count}"));
+            }
+            if threshold == 1 && count > 1 {
+                return invalid(
+                    "member threshold 1 can only be used with 1 member, use 1-of-1 instead".into(),
+                );
+            }
+        }
+
+        Ok(Self {
+            group_threshold,
+            groups: groups.to_vec(),
+            iteration_exponent: Self::DEFAULT_ITERATION_EXPONENT,
+        })
+    }
+
+    /// Creates the scheme with one group where `threshold` of `count` shares recover the secret
+    pub fn threshold(threshold: u8, count: u8) -> Result<Self> {
+        Self::new(1, &[(threshold, count)])
+    }
+
+    /// Sets the exponent of the number of PBKDF2 iterations, the passphrase is hashed
+    /// `10000 * 2^iteration_exponent` times. Has to be less than 16.
+    pub fn with_iteration_exponent(mut self, iteration_exponent: u8) -> Result<Self> {
+        if iteration_exponent >= 16 {
+            return Err(Error::InvalidSharingScheme(
+                "iteration exponent has to be less than 16".into(),
+            ));
+        }
+        self.iteration_exponent = iteration_exponent;
+        Ok(self)
+    }
+
+    /// Returns the number of groups required to recover the secret
+    pub fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    /// Returns the member threshold and member count of every group
+    pub fn groups(&self) -> &[(u8, u8)] {
+        &self.groups
+    }
+
+    /// Splits the secret protected by the passphrase into mnemonic shares, returned per group.
+    /// The secret has to be at least 16 bytes long and its length has to be even.
+    pub fn split(&self, secret: &[u8], passphrase: &str) -> Result<Vec<Vec<Zeroizing<String>>>> {
+        self.split_with(&mut rand_core::OsRng, secret, passphrase)
+    }
+
+    /// Same as `split()` but uses the given random number generator
+    pub fn split_with<R: CryptoRngCore + ?Sized>(
+        &self,
+        rng: &mut R,
+        secret: &[u8],
+        passphrase: &str,
+    ) -> Result<Vec<Vec<Zeroizing<String>>>> {
+        if secret.len() < MIN_SECRET_LENGTH || secret.len() % 2 != 0 {
+            return Err(Error::InvalidSharingScheme(format!(
+                "secret has to be at least {MIN_SECRET_LENGTH} bytes long and its length has to be even"
+            )));
+        }
+        validate_passphrase(passphrase)?;
+
+        let identifier = (rng.next_u32() & 0x7fff) as u16;
+        let encrypted = feistel(
+            secret,
+            passphrase,
+            self.iteration_exponent,
+            identifier,
+            true,
+            false,
+        );
+
+        let group_shares = split_secret(
+            rng,
+            self.group_threshold,
+            self.groups.len() as u8,
+            &encrypted,
+        );
+        Ok(group_shares
+            .iter()
+            .zip(&self.groups)
+            .map(|((group_index, group_secret), &(threshold, count))| {
+                split_secret(rng, threshold, count, group_secret)
+                    .into_iter()
+                    .map(|(member_index, value)| {
+                        Share {
+                            identifier,
+                            extendable: true,
+                            iteration_exponent: self.iteration_exponent,
+                            group_index: *group_index,
+                            group_threshold: self.group_threshold,
+                            group_count: self.groups.len() as u8,
+                            member_index,
+                            member_threshold: threshold,
+                            value,
+                        }
+                        .to_mnemonic()
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Recovers the secret protected by the passphrase from the mnemonic shares. Shares from
+    /// different groups can be passed in any order, extra shares are ignored.
+    pub fn combine<S: AsRef<str>>(shares: &[S], passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+        let invalid = |reason: &str| Err(Error::InvalidShare(reason.to_string()));
+
+        validate_passphrase(passphrase)?;
+        let shares = shares
+            .iter()
+            .map(|share| Share::parse(share.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        let first = match shares.first() {
+            Some(first) => first,
+            None => return invalid("no shares given"),
+        };
+
+        let mut groups: BTreeMap<u8, BTreeMap<u8, &Share>> = BTreeMap::new();
+        for share in &shares {
+            if share.identifier != first.identifier
+                || share.extendable != first.extendable
+                || share.iteration_exponent != first.iteration_exponent
+                || share.group_threshold != first.group_threshold
+                || share.group_count != first.group_count
+                || share.value.len() != first.value.len()
+            {
+                return invalid("shares belong to different secrets");
+            }
+            let members = groups.entry(share.group_index).or_default();
+            if members
+                .values()
+                .any(|member| member.member_threshold != share.member_threshold)
+            {
+                return invalid("shares of one group have different member thresholds");
+            }
+            match members.insert(share.member_index, share) {
+                Some(duplicate) if duplicate.value != share.value => {
+                    return invalid("two different shares have the same index")
+                }
+                _ => {}
+            }
+        }
+
+        let group_secrets = groups
+            .iter()
+            .filter_map(|(group_index, members)| {
+                let threshold = members.values().next()?.member_threshold;
+                let members = members
+                    .values()
+                    .take(threshold as usize)
+                    .map(|member| (member.member_index, member.value.clone()))
+                    .collect::<Vec<_>>();
+                (members.len() == threshold as usize).then(|| {
+                    recover_secret(threshold, &members).map(|secret| (*group_index, secret))
+                })
+            })
+            .take(first.group_threshold as usize)
+            .collect::<Result<Vec<_>>>()?;
+        if group_secrets.len() < first.group_threshold as usize {
+            return invalid(&format!(
+                "{} complete groups are required, got {}",
+                first.group_threshold,
+                group_secrets.len()
+            ));
+        }
+
+        let encrypted = recover_secret(first.group_threshold, &group_secrets)?;
+        Ok(feistel(
+            &encrypted,
+            passphrase,
+            first.iteration_exponent,
+            first.identifier,
+            first.extendable,
+            true,
+        ))
+    }
+}
+
+impl PrivateKey {
+    /// Splits the private key protected by the passphrase into SLIP-39 mnemonic shares, returned
+    /// per group
+    pub fn to_slip39_shares(
+        &self,
+        scheme: &Slip39,
+        passphrase: &str,
+    ) -> Result<Vec<Vec<Zeroizing<String>>>> {
+        scheme.split(&self.secret_bytes()[..], passphrase)
+    }
+
+    /// Recovers the private key from SLIP-39 mnemonic shares created by `to_slip39_shares()`
+    pub fn from_slip39_shares<S: AsRef<str>>(shares: &[S], passphrase: &str) -> Result<Self> {
+        Self::from_slice(&Slip39::combine(shares, passphrase)?)
+    }
+}
+
+impl ExtendedPrivateKey {
+    /// Recovers the master key from SLIP-39 mnemonic shares of a master secret, the same way
+    /// Trezor wallets with Shamir backup derive their accounts
+    pub fn from_slip39_shares<S: AsRef<str>>(shares: &[S], passphrase: &str) -> Result<Self> {
+        Self::from_seed(&Slip39::combine(shares, passphrase)?)
+    }
+}
+
+/// One decoded mnemonic share
+struct Share {
+    /// Random identifier shared by all shares of one secret
+    identifier: u16,
+    /// Whether the identifier is left out of the encryption salt
+    extendable: bool,
+    /// Exponent of the number of PBKDF2 iterations
+    iteration_exponent: u8,
+    /// Index of the group
+    group_index: u8,
+    /// Number of groups required to recover the secret
+    group_threshold: u8,
+    /// Number of groups
+    group_count: u8,
+    /// Index of the member in the group
+    member_index: u8,
+    /// Number of members required to recover the group secret
+    member_threshold: u8,
+    /// Share of the group secret
+    value: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    /// Decodes the share from its mnemonic and validates the checksum
+    fn parse(mnemonic: &str) -> Result<Self> {
+        let words = mnemonic
+            .split_whitespace()
+            .map(|word| {
+                let word = word.to_lowercase();
+                WORDLIST
+                    .binary_search(&word.as_str())
+                    .map(|index| index as u16)
+                    .map_err(|_| Error::InvalidShare(format!("unknown word {word:?}")))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Zeroizing::new)?;
+
+        let min_words =
+            METADATA_WORDS + (MIN_SECRET_LENGTH * 8).div_ceil(RADIX_BITS) + CHECKSUM_WORDS;
+        if words.len() < min_words {
+            return Err(Error::InvalidShare(format!(
+                "share has to have at least {min_words} words"
+            )));
+        }
+
+        let id_exp = (words[0] as u32) << RADIX_BITS | words[1] as u32;
+        let extendable = (id_exp >> 4) & 1 == 1;
+        if rs1024_polymod(customization(extendable), &words) != 1 {
+            return Err(Error::InvalidShare("invalid checksum".into()));
+        }
+
+        let params = (words[2] as u32) << RADIX_BITS | words[3] as u32;
+        let nibble = |shift: u32| (params >> shift & 0xf) as u8;
+        let share = Self {
+            identifier: (id_exp >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_exp & 0xf) as u8,
+            group_index: nibble(16),
+            group_threshold: nibble(12) + 1,
+            group_count: nibble(8) + 1,
+            member_index: nibble(4),
+            member_threshold: nibble(0) + 1,
+            value: words_to_bytes(&words[METADATA_WORDS..words.len() - CHECKSUM_WORDS])?,
+        };
+        if share.group_threshold > share.group_count {
+            return Err(Error::InvalidShare(
+                "group threshold is greater than group count".into(),
+            ));
+        }
+        if share.group_index >= share.group_count {
+            return Err(Error::InvalidShare(
+                "group index is greater than group count".into(),
+            ));
+        }
+        Ok(share)
+    }
+
+    /// Encodes the share into its mnemonic with the checksum
+    fn to_mnemonic(&self) -> Zeroizing<String> {
+        let id_exp = (self.identifier as u32) << 5
+            | (self.extendable as u32) << 4
+            | self.iteration_exponent as u32;
+        let params = (self.group_index as u32) << 16
+            | (self.group_threshold as u32 - 1) << 12
+            | (self.group_count as u32 - 1) << 8
+            | (self.member_index as u32) << 4
+            | (self.member_threshold as u32 - 1);
+
+        let mut words = Zeroizing::new(vec![
+            (id_exp >> RADIX_BITS) as u16,
+            (id_exp & 0x3ff) as u16,
+            (params >> RADIX_BITS) as u16,
+            (params & 0x3ff) as u16,
+        ]);
+        words.extend_from_slice(&bytes_to_words(&self.value));
+
+        let mut checksum_input = Zeroizing::new(words.to_vec());
+        checksum_input.extend_from_slice(&[0; CHECKSUM_WORDS]);
+        let checksum = rs1024_polymod(customization(self.extendable), &checksum_input) ^ 1;
+        for i in (0..CHECKSUM_WORDS).rev() {
+            words.push((checksum >> (i * RADIX_BITS) & 0x3ff) as u16);
+        }
+
+        Zeroizing::new(
+            words
+                .iter()
+                .map(|word| WORDLIST[*word as usize])
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+}
+
+/// Only printable ASCII characters are allowed in the passphrase
+fn validate_passphrase(passphrase: &str) -> Result<()> {
+    if passphrase.bytes().all(|b| (32..=126).contains(&b)) {
+        Ok(())
+    } else {
+        Err(Error::InvalidSharingScheme(
+            "passphrase can only contain printable ASCII characters".into(),
+        ))
+    }
+}
+
+/// Customization string of the checksum and encryption salt
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        b"shamir_extendable"
+    } else {
+        b"shamir"
+    }
+}
+
+/// Reed-Solomon checksum over GF(1024) of the customization string followed by the words
+fn rs1024_polymod(customization: &[u8], words: &[u16]) -> u32 {
+    const GENERATOR: [u32; 10] = [
+        0xe0e040, 0x1c1c080, 0x3838100, 0x7070200, 0xe0e0009, 0x1c0c2412, 0x38086c24, 0x3090fc48,
+        0x21b1f890, 0x3f3f120,
+    ];
+    let values = customization
+        .iter()
+        .map(|b| *b as u32)
+        .chain(words.iter().map(|w| *w as u32));
+
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 20;
+        checksum = (checksum & 0xfffff) << RADIX_BITS ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if top >> i & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// Converts bytes into 10 bit words, the value is padded with leading zero bits
+fn bytes_to_words(bytes: &[u8]) -> Zeroizing<Vec<u16>> {
+    let word_count = (bytes.len() * 8).div_ceil(RADIX_BITS);
+    let mut words = Zeroizing::new(Vec::with_capacity(word_count));
+    let mut acc = 0u32;
+    let mut acc_bits = word_count * RADIX_BITS - bytes.len() * 8;
+    for byte in bytes {
+        acc = acc << 8 | *byte as u32;
+        acc_bits += 8;
+        if acc_bits >= RADIX_BITS {
+            acc_bits -= RADIX_BITS;
+            words.push((acc >> acc_bits & 0x3ff) as u16);
+        }
+    }
+    words
+}
+
+/// Converts 10 bit words into bytes, the leading padding bits have to be zero
+fn words_to_bytes(words: &[u16]) -> Result<Zeroizing<Vec<u8>>> {
+    let padding = words.len() * RADIX_BITS % 16;
+    if padding > 8 {
+        return Err(Error::InvalidShare("invalid share length".into()));
+    }
+    if words[0] >> (RADIX_BITS - padding) != 0 {
+        return Err(Error::InvalidShare("padding bits aren't zero".into()));
+    }
+
+    let mut bytes = Zeroizing::new(Vec::with_capacity(words.len() * RADIX_BITS / 8));
+    let mut acc = 0u32;
+    let mut acc_bits = 0;
+    for (i, word) in words.iter().enumerate() {
+        let bits = if i == 0 {
+            RADIX_BITS - padding
+        } else {
+            RADIX_BITS
+        };
+        acc = acc << bits | (*word as u32 & ((1 << bits) - 1));
+        acc_bits += bits;
+        while acc_bits >= 8 {
+            acc_bits -= 8;
+            bytes.push((acc >> acc_bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Encrypts or decrypts the master secret with the 4 round Feistel cipher keyed by the passphrase
+fn feistel(
+    secret: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    decrypt: bool,
+) -> Zeroizing<Vec<u8>> {
+    let half = secret.len() / 2;
+    let mut left = Zeroizing::new(secret[..half].to_vec());
+    let mut right = Zeroizing::new(secret[half..].to_vec());
+
+    let mut salt_prefix = Vec::new();
+    if !extendable {
+        salt_prefix.extend_from_slice(customization(false));
+        salt_prefix.extend_from_slice(&identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATIONS << iteration_exponent) / 4;
+
+    for round in 0..4u8 {
+        let round = if decrypt { 3 - round } else { round };
+        let mut password = Zeroizing::new(vec![round]);
+        password.extend_from_slice(passphrase.as_bytes());
+        let mut salt = Zeroizing::new(salt_prefix.clone());
+        salt.extend_from_slice(&right);
+
+        let mut key = Zeroizing::new(vec![0u8; half]);
+        pbkdf2::pbkdf2_hmac::<Sha256>(&password, &salt, iterations, &mut key);
+        for (l, k) in left.iter_mut().zip(key.iter()) {
+            *l ^= k;
+        }
+        std::mem::swap(&mut left, &mut right);
+    }
+
+    let mut output = right;
+    output.extend_from_slice(&left);
+    output
+}
+
+/// Logarithm and exponent tables of GF(256) with the Rijndael polynomial and generator 3
+const fn gf256_tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut value: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = value as u8;
+        log[value as usize] = i as u8;
+        value ^= value << 1;
+        if value & 0x100 != 0 {
+            value ^= 0x11b;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+const GF256: ([u8; 255], [u8; 256]) = gf256_tables();
+
+/// Evaluates the polynomial going through the shares at `x` using Lagrange interpolation
+fn interpolate(shares: &[(u8, Zeroizing<Vec<u8>>)], x: u8) -> Zeroizing<Vec<u8>> {
+    let (exp, log) = &GF256;
+    if let Some((_, value)) = shares.iter().find(|(index, _)| *index == x) {
+        return value.clone();
+    }
+
+    let log_product: i32 = shares
+        .iter()
+        .map(|(index, _)| log[(index ^ x) as usize] as i32)
+        .sum();
+    let mut result = Zeroizing::new(vec![0u8; shares[0].1.len()]);
+    for (index, value) in shares {
+        let log_basis = log_product
+            - log[(index ^ x) as usize] as i32
+            - shares
+                .iter()
+                .filter(|(other, _)| other != index)
+                .map(|(other, _)| log[(index ^ other) as usize] as i32)
+                .sum::<i32>();
+        let log_basis = log_basis.rem_euclid(255);
+        for (r, v) in result.iter_mut().zip(value.iter()) {
+            if *v != 0 {
+                *r ^= exp[((log[*v as usize] as i32 + log_basis) % 255) as usize];
+            }
+        }
+    }
+    result
+}
+
+/// First 4 bytes of HMAC-SHA256 of the secret keyed by the random part of the digest share
+fn digest(random_part: &[u8], secret: &[u8]) -> [u8; 4] {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(random_part).expect("HMAC accepts key of any length");
+    mac.update(secret);
+    let mut digest = [0u8; 4];
+    digest.copy_from_slice(&mac.finalize().into_bytes()[..4]);
+    digest
+}
+
+/// Splits the secret into `count` shares where any `threshold` of them recover it
+fn split_secret<R: CryptoRngCore + ?Sized>(
+    rng: &mut R,
+    threshold: u8,
+    count: u8,
+    secret: &[u8],
+) -> Vec<(u8, Zeroizing<Vec<u8>>)> {
+    if threshold == 1 {
+        return (0..count)
+            .map(|index| (index, Zeroizing::new(secret.to_vec())))
+            .collect();
+    }
+
+    let random_count = threshold - 2;
+    let mut shares = (0..random_count)
+        .map(|index| {
+            let mut value = Zeroizing::new(vec![0u8; secret.len()]);
+            rng.fill_bytes(&mut value);
+            (index, value)
+        })
+        .collect::<Vec<_>>();
+
+    let mut random_part = Zeroizing::new(vec![0u8; secret.len() - 4]);
+    rng.fill_bytes(&mut random_part);
+    let mut digest_share = Zeroizing::new(digest(&random_part, secret).to_vec());
+    digest_share.extend_from_slice(&random_part);
+
+    let mut base_shares = shares.clone();
+    base_shares.push((DIGEST_INDEX, digest_share));
+    base_shares.push((SECRET_INDEX, Zeroizing::new(secret.to_vec())));
+    for index in random_count..count {
+        shares.push((index, interpolate(&base_shares, index)));
+    }
+    shares
+}
+
+/// Recovers the secret from `threshold` shares and verifies its digest
+fn recover_secret(
+    threshold: u8,
+    shares: &[(u8, Zeroizing<Vec<u8>>)],
+) -> Result<Zeroizing<Vec<u8>>> {
+    if threshold == 1 {
+        return Ok(shares[0].1.clone());
+    }
+
+    let secret = interpolate(shares, SECRET_INDEX);
+    let digest_share = interpolate(shares, DIGEST_INDEX);
+    if digest_share[..4] != digest(&digest_share[4..], &secret) {
+        return Err(Error::InvalidShare(
+            "digest of the shared secret doesn't match".into(),
+        ));
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn test_vectors() {
+        // Test vectors 1 and 4 from SLIP-39, both use passphrase TREZOR
+        let share = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard";
+        assert_eq!(
+            "bb54aac4b89dc868ba37d9cc21b2cece",
+            hex::encode(Slip39::combine(&[share], "TREZOR").unwrap())
+        );
+
+        let shares = [
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+            "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+        ];
+        assert_eq!(
+            "b43ceb7e57a0ea8766221624d01b0864",
+            hex::encode(Slip39::combine(&shares, "TREZOR").unwrap())
+        );
+        assert!(matches!(
+            Slip39::combine(&shares[..1], "TREZOR"),
+            Err(Error::InvalidShare(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_share() {
+        let share = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision decision";
+        assert_eq!(
+            Slip39::combine(&[share], ""),
+            Err(Error::InvalidShare("invalid checksum".into()))
+        );
+        assert!(matches!(
+            Slip39::combine(&["duckling enlarge notaword"], ""),
+            Err(Error::InvalidShare(_))
+        ));
+        assert!(matches!(
+            Slip39::combine::<&str>(&[], ""),
+            Err(Error::InvalidShare(_))
+        ));
+    }
+
+    #[test]
+    fn test_groups() {
+        let secret =
+            hex::decode("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap();
+        let scheme = Slip39::new(2, &[(1, 1), (2, 3), (3, 5)]).unwrap();
+        let shares = scheme
+            .split_with(&mut ChaCha20Rng::seed_from_u64(1), &secret, "passphrase")
+            .unwrap();
+        assert_eq!(
+            [1, 3, 5],
+            [shares[0].len(), shares[1].len(), shares[2].len()]
+        );
+        assert_eq!(33, shares[0][0].split_whitespace().count());
+
+        let combine = |shares: &[&Zeroizing<String>]| {
+            Slip39::combine(shares, "passphrase").map(|secret| secret.to_vec())
+        };
+        assert_eq!(
+            secret,
+            combine(&[&shares[0][0], &shares[1][2], &shares[1][0]]).unwrap()
+        );
+        assert_eq!(
+            secret,
+            combine(&[
+                &shares[2][4],
+                &shares[1][1],
+                &shares[2][0],
+                &shares[2][2],
+                &shares[1][2]
+            ])
+            .unwrap()
+        );
+        // Second group doesn't reach its threshold
+        assert!(combine(&[&shares[0][0], &shares[1][2]]).is_err());
+        // Wrong passphrase decrypts into a different secret
+        assert_ne!(
+            secret,
+            Slip39::combine(&[&shares[0][0], &shares[1][0], &shares[1][1]], "")
+                .unwrap()
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_invalid_scheme() {
+        for (threshold, groups) in [
+            (0, vec![(1, 1)]),
+            (2, vec![(1, 1)]),
+            (1, vec![]),
+            (1, vec![(1, 2)]),
+            (1, vec![(3, 2)]),
+            (1, vec![(2, 17)]),
+        ] {
+            assert!(matches!(
+                Slip39::new(threshold, &groups),
+                Err(Error::InvalidSharingScheme(_))
+            ));
+        }
+        let scheme = Slip39::threshold(2, 3).unwrap();
+        assert!(scheme.split(&[1; 15], "").is_err());
+        assert!(scheme.split(&[1; 17], "").is_err());
+        assert!(scheme.split(&[1; 16], "žluťoučký").is_err());
+        assert!(scheme.clone().with_iteration_exponent(16).is_err());
+    }
+
+    #[test]
+    fn test_private_key_shares() {
+        let private_key = PrivateKey::random();
+        let scheme = Slip39::threshold(3, 5)
+            .unwrap()
+            .with_iteration_exponent(0)
+            .unwrap();
+        let shares = private_key.to_slip39_shares(&scheme, "TREZOR").unwrap();
+        let picked = [&shares[0][4], &shares[0][1], &shares[0][3]];
+        assert_eq!(
+            private_key,
+            PrivateKey::from_slip39_shares(&picked, "TREZOR").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_master_key_shares() {
+        let master = ExtendedPrivateKey::from_slip39_shares(
+            &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+            "TREZOR",
+        )
+        .unwrap();
+        assert_eq!(
+            master,
+            ExtendedPrivateKey::from_seed(
+                &hex::decode("bb54aac4b89dc868ba37d9cc21b2cece").unwrap()
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,515 @@
+use crate::rlp::{encode_bytes, encode_list, encode_uint, encode_uint_bytes};
+use crate::utils::{keccak256, uint256};
+use crate::{Address, PrivateKey, Signature};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Transaction that can be signed by `PrivateKey::sign_transaction()`
+pub trait Transaction {
+    /// Returns the unsigned transaction as it's hashed for signing, the type byte followed by the
+    /// RLP encoded fields. Hardware wallets sign this payload.
+    fn encode_unsigned(&self) -> Vec<u8>;
+
+    /// Returns the hash that is signed
+    fn signing_hash(&self) -> [u8; 32] {
+        keccak256(&self.encode_unsigned())
+    }
+
+    /// Returns the raw signed transaction, which can be sent by `eth_sendRawTransaction`
+    fn encode_signed(&self, signature: &Signature) -> Vec<u8>;
+}
+
+/// Address and storage slots the transaction plans to access (EIP-2930)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccessListItem {
+    /// Accessed contract
+    address: Address,
+    /// Accessed storage slots of the contract
+    storage_keys: Vec<[u8; 32]>,
+}
+
+impl AccessListItem {
+    /// Creates the item of the contract and its storage slots
+    pub fn new(address: Address, storage_keys: Vec<[u8; 32]>) -> Self {
+        Self {
+            address,
+            storage_keys,
+        }
+    }
+
+    /// Returns the contract
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns the storage slots
+    pub fn storage_keys(&self) -> &[[u8; 32]] {
+        &self.storage_keys
+    }
+}
+
+/// Encodes the access list as RLP list of `[address, [storage keys]]`
+pub(crate) fn encode_access_list(access_list: &[AccessListItem]) -> Vec<u8> {
+    let items = access_list
+        .iter()
+        .map(|item| {
+            let storage_keys = item
+                .storage_keys
+                .iter()
+                .map(|key| encode_bytes(&key[..]))
+                .collect::<Vec<_>>();
+            encode_list(&[
+                encode_bytes(item.address.as_bytes()),
+                encode_list(&storage_keys),
+            ])
+        })
+        .collect::<Vec<_>>();
+    encode_list(&items)
+}
+
+/// Encodes the recipient, contract creation is the empty string
+pub(crate) fn encode_to(to: Option<Address>) -> Vec<u8> {
+    match to {
+        Some(to) => encode_bytes(to.as_bytes()),
+        None => encode_bytes(&[]),
+    }
+}
+
+/// Encodes `y_parity, r, s` of the signature
+pub(crate) fn encode_signature(signature: &Signature) -> [Vec<u8>; 3] {
+    [
+        encode_uint(signature.y_parity() as u128),
+        encode_uint_bytes(&signature.r()),
+        encode_uint_bytes(&signature.s()),
+    ]
+}
+
+/// Encodes the typed transaction envelope `type || rlp(fields)`
+pub(crate) fn encode_envelope(transaction_type: u8, fields: &[Vec<u8>]) -> Vec<u8> {
+    let mut encoded = vec![transaction_type];
+    encoded.extend_from_slice(&encode_list(fields));
+    encoded
+}
+
+/// Type `0x01` transaction with access list (EIP-2930).
+///
+/// All fields except the chain id default to zero or empty, without the recipient the
+/// transaction creates a contract.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{AccessListItem, Address, Eip2930Transaction, PrivateKey};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let to = Address::from_str("0x70997970c51812dc3a010c7d01b50e0d17dc79c8").unwrap();
+///
+/// let transaction = Eip2930Transaction::new(1)
+///     .with_nonce(0)
+///     .with_gas_price(20_000_000_000)
+///     .with_gas_limit(50_000)
+///     .with_to(to)
+///     .with_value(1_000_000_000_000_000_000)
+///     .with_access_list(vec![AccessListItem::new(to, vec![[0; 32]])]);
+///
+/// let raw = private_key.sign_transaction(&transaction);
+/// println!("0x{}", hex::encode(raw));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Eip2930Transaction {
+    /// EIP-155 chain id
+    chain_id: u64,
+    /// Nonce of the sender
+    nonce: u64,
+    /// Gas price in wei
+    gas_price: u128,
+    /// Maximum gas the transaction can use
+    gas_limit: u64,
+    /// Recipient, `None` creates a contract
+    to: Option<Address>,
+    /// Value in wei as 256 bit big-endian integer
+    value: [u8; 32],
+    /// Call data or the init code of the contract
+    data: Vec<u8>,
+    /// Addresses and storage slots the transaction plans to access
+    access_list: Vec<AccessListItem>,
+}
+
+impl Eip2930Transaction {
+    /// Type of the transaction envelope
+    pub const TYPE: u8 = 0x01;
+
+    /// Creates the transaction on the chain
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            nonce: 0,
+            gas_price: 0,
+            gas_limit: 0,
+            to: None,
+            value: [0u8; 32],
+            data: Vec::new(),
+            access_list: Vec::new(),
+        }
+    }
+
+    /// Sets the nonce of the sender
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Sets the gas price in wei
+    pub fn with_gas_price(mut self, gas_price: u128) -> Self {
+        self.gas_price = gas_price;
+        self
+    }
+
+    /// Sets the maximum gas the transaction can use
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Sets the recipient
+    pub fn with_to(mut self, to: Address) -> Self {
+        self.to = Some(to);
+        self
+    }
+
+    /// Sets the value in wei
+    pub fn with_value(self, value: u128) -> Self {
+        self.with_value_uint256(uint256(value))
+    }
+
+    /// Sets the value in wei given as 256 bit big-endian integer
+    pub fn with_value_uint256(mut self, value: [u8; 32]) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the call data, or the init code if the transaction creates a contract
+    pub fn with_data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.data = data.into();
+        self
+    }
+
+    /// Sets the access list
+    pub fn with_access_list(mut self, access_list: Vec<AccessListItem>) -> Self {
+        self.access_list = access_list;
+        self
+    }
+
+    /// Returns the chain id
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Returns the nonce
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Returns the recipient
+    pub fn to(&self) -> Option<Address> {
+        self.to
+    }
+
+    /// Returns the access list
+    pub fn access_list(&self) -> &[AccessListItem] {
+        &self.access_list
+    }
+
+    /// Returns the RLP encoded fields without the signature
+    fn fields(&self) -> Vec<Vec<u8>> {
+        vec![
+            encode_uint(self.chain_id.into()),
+            encode_uint(self.nonce.into()),
+            encode_uint(self.gas_price),
+            encode_uint(self.gas_limit.into()),
+            encode_to(self.to),
+            encode_uint_bytes(&self.value),
+            encode_bytes(&self.data),
+            encode_access_list(&self.access_list),
+        ]
+    }
+}
+
+impl Transaction for Eip2930Transaction {
+    fn encode_unsigned(&self) -> Vec<u8> {
+        encode_envelope(Self::TYPE, &self.fields())
+    }
+
+    fn encode_signed(&self, signature: &Signature) -> Vec<u8> {
+        let mut fields = self.fields();
+        fields.extend(encode_signature(signature));
+        encode_envelope(Self::TYPE, &fields)
+    }
+}
+
+/// Type `0x02` transaction with fee market (EIP-1559), the default transaction type on mainnet.
+///
+/// All fields except the chain id default to zero or empty, without the recipient the
+/// transaction creates a contract.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{Address, Eip1559Transaction, PrivateKey};
+///
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+///
+/// let transaction = Eip1559Transaction::new(1)
+///     .with_nonce(0)
+///     .with_max_priority_fee_per_gas(2_000_000_000)
+///     .with_max_fee_per_gas(30_000_000_000)
+///     .with_gas_limit(21_000)
+///     .with_to(Address::from_str("0x70997970c51812dc3a010c7d01b50e0d17dc79c8").unwrap())
+///     .with_value(1_000_000_000_000_000_000);
+///
+/// let raw = private_key.sign_transaction(&transaction);
+/// println!("0x{}", hex::encode(raw));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Eip1559Transaction {
+    /// EIP-155 chain id
+    chain_id: u64,
+    /// Nonce of the sender
+    nonce: u64,
+    /// Maximum tip for the block producer per gas in wei
+    max_priority_fee_per_gas: u128,
+    /// Maximum total fee per gas in wei, including the base fee
+    max_fee_per_gas: u128,
+    /// Maximum gas the transaction can use
+    gas_limit: u64,
+    /// Recipient, `None` creates a contract
+    to: Option<Address>,
+    /// Value in wei as 256 bit big-endian integer
+    value: [u8; 32],
+    /// Call data or the init code of the contract
+    data: Vec<u8>,
+    /// Addresses and storage slots the transaction plans to access
+    access_list: Vec<AccessListItem>,
+}
+
+impl Eip1559Transaction {
+    /// Type of the transaction envelope
+    pub const TYPE: u8 = 0x02;
+
+    /// Creates the transaction on the chain
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            nonce: 0,
+            max_priority_fee_per_gas: 0,
+            max_fee_per_gas: 0,
+            gas_limit: 0,
+            to: None,
+            value: [0u8; 32],
+            data: Vec::new(),
+            access_list: Vec::new(),
+        }
+    }
+
+    /// Sets the nonce of the sender
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Sets the maximum tip for the block producer per gas in wei
+    pub fn with_max_priority_fee_per_gas(mut self, max_priority_fee_per_gas: u128) -> Self {
+        self.max_priority_fee_per_gas = max_priority_fee_per_gas;
+        self
+    }
+
+    /// Sets the maximum total fee per gas in wei, including the base fee
+    pub fn with_max_fee_per_gas(mut self, max_fee_per_gas: u128) -> Self {
+        self.max_fee_per_gas = max_fee_per_gas;
+        self
+    }
+
+    /// Sets the maximum gas the transaction can use
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Sets the recipient
+    pub fn with_to(mut self, to: Address) -> Self {
+        self.to = Some(to);
+        self
+    }
+
+    /// Sets the value in wei
+    pub fn with_value(self, value: u128) -> Self {
+        self.with_value_uint256(uint256(value))
+    }
+
+    /// Sets the value in wei given as 256 bit big-endian integer
+    pub fn with_value_uint256(mut self, value: [u8; 32]) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the call data, or the init code if the transaction creates a contract
+    pub fn with_data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.data = data.into();
+        self
+    }
+
+    /// Sets the access list
+    pub fn with_access_list(mut self, access_list: Vec<AccessListItem>) -> Self {
+        self.access_list = access_list;
+        self
+    }
+
+    /// Returns the chain id
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Returns the nonce
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Returns the recipient
+    pub fn to(&self) -> Option<Address> {
+        self.to
+    }
+
+    /// Returns the access list
+    pub fn access_list(&self) -> &[AccessListItem] {
+        &self.access_list
+    }
+
+    /// Returns the RLP encoded fields without the signature
+    fn fields(&self) -> Vec<Vec<u8>> {
+        vec![
+            encode_uint(self.chain_id.into()),
+            encode_uint(self.nonce.into()),
+            encode_uint(self.max_priority_fee_per_gas),
+            encode_uint(self.max_fee_per_gas),
+            encode_uint(self.gas_limit.into()),
+            encode_to(self.to),
+            encode_uint_bytes(&self.value),
+            encode_bytes(&self.data),
+            encode_access_list(&self.access_list),
+        ]
+    }
+}
+
+impl Transaction for Eip1559Transaction {
+    fn encode_unsigned(&self) -> Vec<u8> {
+        encode_envelope(Self::TYPE, &self.fields())
+    }
+
+    fn encode_signed(&self, signature: &Signature) -> Vec<u8> {
+        let mut fields = self.fields();
+        fields.extend(encode_signature(signature));
+        encode_envelope(Self::TYPE, &fields)
+    }
+}
+
+impl PrivateKey {
+    /// Signs the transaction and returns the raw signed transaction, which can be sent by
+    /// `eth_sendRawTransaction`. The transaction hash is Keccak-256 of the raw transaction.
+    pub fn sign_transaction<T: Transaction>(&self, transaction: &T) -> Vec<u8> {
+        transaction.encode_signed(&self.sign_hash(&transaction.signing_hash()))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn private_key() -> PrivateKey {
+        PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_eip2930_transaction() {
+        let usdc = Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap();
+        let mut slot = [0u8; 32];
+        slot[31] = 1;
+        let transaction = Eip2930Transaction::new(1)
+            .with_gas_price(20_000_000_000)
+            .with_gas_limit(50_000)
+            .with_to(Address::from_str("0x70997970c51812dc3a010c7d01b50e0d17dc79c8").unwrap())
+            .with_value(1_000_000_000_000_000_000)
+            .with_access_list(vec![AccessListItem::new(usdc, vec![[0; 32], slot])]);
+        assert_eq!(
+            "dc2e80a2b528fba7ba38df7d069ed43db38ea86ca2179ca5b464e5906157dce4",
+            hex::encode(transaction.signing_hash())
+        );
+
+        let raw = private_key().sign_transaction(&transaction);
+        assert_eq!(
+            "01f8ca01808504a817c80082c3509470997970c51812dc3a010c7d01b50e0d17dc79c8880de0b6b3a764000080f85bf85994a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48f842a00000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000101a0c0bdcd54d4266d8fc43f62b51767c9efe8a840218406fb0344285d2e4cfabbe4a03361e3e185a99245d4bae2be92663c56fc1d33247e1c2646642a79b96ee64f98",
+            hex::encode(&raw)
+        );
+        assert_eq!(
+            "94b8524b856aa0ca2e26e0fc9b8279b78de7dfe98748645b49d3df7d78cd8b34",
+            hex::encode(keccak256(&raw))
+        );
+    }
+
+    #[test]
+    fn test_eip2930_contract_creation() {
+        let transaction = Eip2930Transaction::new(1)
+            .with_nonce(5)
+            .with_gas_price(1_000_000_000)
+            .with_gas_limit(1_000_000)
+            .with_data(hex::decode("6080604052").unwrap());
+        assert_eq!(
+            "2680d151fc8d2b684d1a8936e74ddf0a28617dbe5da19b28415c1d4927ede8e2",
+            hex::encode(transaction.signing_hash())
+        );
+        assert_eq!(
+            "01f8570105843b9aca00830f42408080856080604052c080a03a5168f015658ee681bfe2079adb887a2f25af5be005fd46010f9425608cbe60a04f9abf2cf1647a05a05cb201480f8f5968ce976b680dc3d043e15d0ccca67ef2",
+            hex::encode(private_key().sign_transaction(&transaction))
+        );
+    }
+
+    #[test]
+    fn test_eip1559_transaction() {
+        let transaction = Eip1559Transaction::new(1)
+            .with_max_priority_fee_per_gas(2_000_000_000)
+            .with_max_fee_per_gas(30_000_000_000)
+            .with_gas_limit(21_000)
+            .with_to(Address::from_str("0x70997970c51812dc3a010c7d01b50e0d17dc79c8").unwrap())
+            .with_value(1_000_000_000_000_000_000);
+        assert_eq!(
+            "92f8ea6d20e4198bca291b89f9aa67f52672bdba5a4d5017f83d96c93bdcfd5d",
+            hex::encode(transaction.signing_hash())
+        );
+
+        let raw = private_key().sign_transaction(&transaction);
+        assert_eq!(
+            "02f873018084773594008506fc23ac008252089470997970c51812dc3a010c7d01b50e0d17dc79c8880de0b6b3a764000080c001a0bd2ca7c862745e82596cacce0cfa91ae48aacf42672ee780de717878f8e93288a07ce519725313b047715c1d1dfa53a02c1210ff7402e7ea2d0cb5a4c80a47adba",
+            hex::encode(&raw)
+        );
+        assert_eq!(
+            "b20c598731849a13ef5bdf0d907c960006f85ba4d843d326a53c5f772b216b06",
+            hex::encode(keccak256(&raw))
+        );
+    }
+
+    #[test]
+    fn test_eip1559_token_transfer() {
+        let usdc = Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap();
+        // transfer(0x70997970c51812dc3a010c7d01b50e0d17dc79c8, 1000000)
+        let data = hex::decode("a9059cbb00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c800000000000000000000000000000000000000000000000000000000000f4240").unwrap();
+        let transaction = Eip1559Transaction::new(1)
+            .with_nonce(7)
+            .with_max_priority_fee_per_gas(1_000_000_000)
+            .with_max_fee_per_gas(50_000_000_000)
+            .with_gas_limit(60_000)
+            .with_to(usdc)
+            .with_data(data)
+            .with_access_list(vec![AccessListItem::new(usdc, vec![])]);
+        assert_eq!(
+            "02f8c70107843b9aca00850ba43b740082ea6094a0b86991c6218b36c1d19d4a2e9eb0ce3606eb4880b844a9059cbb00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c800000000000000000000000000000000000000000000000000000000000f4240d7d694a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48c001a0043fada5d63c284ed093566f4aa78d88a65f7ddb1cc582f3463e8e5b8b91f03aa030c49ce5b792d04404bf46564332d325b400546712d275f278a4f3eb0f16abeb",
+            hex::encode(private_key().sign_transaction(&transaction))
+        );
+    }
+}
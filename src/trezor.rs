@@ -0,0 +1,777 @@
+use crate::{
+    Address, DerivationPath, Eip712, Eip712Domain, Error, Result, RlpItem, Signature, Signer,
+    Transaction, TypedData,
+};
+use rusb::{DeviceHandle, GlobalContext};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// USB vendor id of Trezor devices with WebUSB firmware
+const VENDOR_ID: u16 = 0x1209;
+
+/// USB product id of Trezor devices with WebUSB firmware
+const PRODUCT_ID: u16 = 0x53c1;
+
+/// Interface of the wire protocol
+const INTERFACE: u8 = 0;
+
+/// Endpoint the messages are written to
+const ENDPOINT_OUT: u8 = 0x01;
+
+/// Endpoint the messages are read from
+const ENDPOINT_IN: u8 = 0x81;
+
+/// Size of the USB packet
+const PACKET_SIZE: usize = 64;
+
+/// Timeout of writing the packet, reading waits until the user confirms on the device
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum length of the transaction data sent in one message
+const DATA_CHUNK: usize = 1024;
+
+// Message types of the wire protocol
+const INITIALIZE: u16 = 0;
+const FAILURE: u16 = 3;
+const FEATURES: u16 = 17;
+const PIN_MATRIX_REQUEST: u16 = 18;
+const PIN_MATRIX_ACK: u16 = 19;
+const BUTTON_REQUEST: u16 = 26;
+const BUTTON_ACK: u16 = 27;
+const PASSPHRASE_REQUEST: u16 = 41;
+const PASSPHRASE_ACK: u16 = 42;
+const ETHEREUM_GET_ADDRESS: u16 = 56;
+const ETHEREUM_ADDRESS: u16 = 57;
+const ETHEREUM_SIGN_TX: u16 = 58;
+const ETHEREUM_TX_REQUEST: u16 = 59;
+const ETHEREUM_TX_ACK: u16 = 60;
+const ETHEREUM_SIGN_MESSAGE: u16 = 64;
+const ETHEREUM_MESSAGE_SIGNATURE: u16 = 66;
+const ETHEREUM_SIGN_TX_EIP1559: u16 = 452;
+const ETHEREUM_SIGN_TYPED_DATA: u16 = 464;
+const ETHEREUM_TYPED_DATA_STRUCT_REQUEST: u16 = 465;
+const ETHEREUM_TYPED_DATA_STRUCT_ACK: u16 = 466;
+const ETHEREUM_TYPED_DATA_VALUE_REQUEST: u16 = 467;
+const ETHEREUM_TYPED_DATA_VALUE_ACK: u16 = 468;
+const ETHEREUM_TYPED_DATA_SIGNATURE: u16 = 469;
+const ETHEREUM_SIGN_TYPED_HASH: u16 = 470;
+
+// Data types of the typed data members, `EthereumDataType`
+const DATA_TYPE_UINT: u64 = 1;
+const DATA_TYPE_INT: u64 = 2;
+const DATA_TYPE_BYTES: u64 = 3;
+const DATA_TYPE_STRING: u64 = 4;
+const DATA_TYPE_BOOL: u64 = 5;
+const DATA_TYPE_ADDRESS: u64 = 6;
+const DATA_TYPE_ARRAY: u64 = 7;
+const DATA_TYPE_STRUCT: u64 = 8;
+
+/// Callback that returns the PIN entered by the user as the positions of the digits in the
+/// scrambled matrix shown on the device (Trezor One)
+pub type PinCallback = Box<dyn Fn() -> String + Send + Sync>;
+
+/// Signer backed by a Trezor device connected over USB. The key at the derivation path never
+/// leaves the device, every signature is confirmed on the device.
+///
+/// The PIN is entered on the device, except on Trezor One which shows a scrambled matrix and
+/// needs the PIN callback. The passphrase, if enabled, is always entered on the device. Trezor
+/// signs messages, typed data, EIP-155 legacy and EIP-1559 transactions but not raw digests, so
+/// `sign_digest()` returns an error. The firmware has no EIP-2930 transactions.
+/// ```no_run
+/// use ethereum_private_key_to_address::{DerivationPath, Signer, TrezorSigner};
+///
+/// let signer = TrezorSigner::new(DerivationPath::ethereum(0)).unwrap();
+/// println!("{}", signer.address());
+///
+/// let signature = signer.sign_message(b"hello world").unwrap();
+/// ```
+pub struct TrezorSigner {
+    /// Open USB device, calls must not interleave
+    device: Mutex<DeviceHandle<GlobalContext>>,
+    /// Callback asking for the PIN
+    pin: Option<PinCallback>,
+    /// Derivation path of the key
+    path: DerivationPath,
+    /// Address at the path
+    address: Address,
+}
+
+impl TrezorSigner {
+    /// Opens the first connected Trezor device and fetches the address at the path
+    pub fn new(path: DerivationPath) -> Result<Self> {
+        Self::open(path, None)
+    }
+
+    /// Same as `new()` but asks for the PIN by the callback when the device requests it
+    pub fn with_pin_callback(path: DerivationPath, pin: PinCallback) -> Result<Self> {
+        Self::open(path, Some(pin))
+    }
+
+    /// Opens the device, starts the session and fetches the address
+    fn open(path: DerivationPath, pin: Option<PinCallback>) -> Result<Self> {
+        let device = rusb::devices()
+            .map_err(usb_error)?
+            .iter()
+            .find(|device| {
+                device.device_descriptor().is_ok_and(|descriptor| {
+                    descriptor.vendor_id() == VENDOR_ID && descriptor.product_id() == PRODUCT_ID
+                })
+            })
+            .ok_or_else(|| Error::Signer("no Trezor device found".to_string()))?;
+        let handle = device.open().map_err(usb_error)?;
+        handle.claim_interface(INTERFACE).map_err(usb_error)?;
+
+        let mut signer = Self {
+            device: Mutex::new(handle),
+            pin,
+            path,
+            address: Address::from([0u8; 20]),
+        };
+        signer.call(INITIALIZE, &[], FEATURES)?;
+        signer.address = signer.get_address(false)?;
+        Ok(signer)
+    }
+
+    /// Returns the derivation path of the key
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    /// Shows the address on the device and waits for the user to confirm it, so the user can
+    /// check the address isn't replaced by malware on the host
+    pub fn confirm_address(&self) -> Result<()> {
+        self.get_address(true)?;
+        Ok(())
+    }
+
+    /// Signs the typed data JSON the same way as `eth_signTypedData_v4`. The types and the
+    /// values are sent member by member, so the device shows the content of the message. Trezor
+    /// One doesn't support it, use `sign_typed_data_hashes()` there.
+    pub fn sign_typed_data_json(&self, typed_data: &TypedData) -> Result<Signature> {
+        const RESPONSES: [u16; 3] = [
+            ETHEREUM_TYPED_DATA_STRUCT_REQUEST,
+            ETHEREUM_TYPED_DATA_VALUE_REQUEST,
+            ETHEREUM_TYPED_DATA_SIGNATURE,
+        ];
+
+        let mut request = ProtoWriter::new();
+        request.path(1, &self.path);
+        request.string(2, typed_data.primary_type());
+        // Arrays of structs are hashed like MetaMask does, the same as `TypedData`
+        request.uint(3, 1);
+
+        let device = self.device.lock().expect("lock isn't poisoned");
+        let (mut response_type, mut response) = self.exchange(
+            &device,
+            ETHEREUM_SIGN_TYPED_DATA,
+            &request.finish(),
+            &RESPONSES,
+        )?;
+        loop {
+            let mut ack = ProtoWriter::new();
+            let ack_type = match response_type {
+                ETHEREUM_TYPED_DATA_STRUCT_REQUEST => {
+                    let name = std::str::from_utf8(field_bytes(&response, 1)?)
+                        .map_err(|_| invalid_response("struct name"))?;
+                    for (name, ty) in typed_data.members(name)? {
+                        let mut member = ProtoWriter::new();
+                        member.bytes(1, &field_type(typed_data, ty)?);
+                        member.string(2, name);
+                        ack.bytes(1, &member.finish());
+                    }
+                    ETHEREUM_TYPED_DATA_STRUCT_ACK
+                }
+                ETHEREUM_TYPED_DATA_VALUE_REQUEST => {
+                    let (ty, value) = typed_data.member(&field_uints(&response, 1)?)?;
+                    // Arrays are sent as their length, the items are requested one by one
+                    let value = match value.as_array() {
+                        Some(items) => u16::try_from(items.len())
+                            .map_err(|_| Error::InvalidTypedData(format!("{ty} is too long")))?
+                            .to_be_bytes()
+                            .to_vec(),
+                        None => typed_data.encode_atomic(ty, value)?,
+                    };
+                    ack.bytes(1, &value);
+                    ETHEREUM_TYPED_DATA_VALUE_ACK
+                }
+                _ => return Signature::from_slice(field_bytes(&response, 1)?),
+            };
+            (response_type, response) =
+                self.exchange(&device, ack_type, &ack.finish(), &RESPONSES)?;
+        }
+    }
+
+    /// Signs the EIP-712 typed data. The device shows only the domain separator and the struct
+    /// hash. Only Trezor One signs the hashes, newer models need the full typed data, see
+    /// `sign_typed_data_json()`.
+    pub fn sign_typed_data<T: Eip712>(
+        &self,
+        domain: &Eip712Domain,
+        value: &T,
+    ) -> Result<Signature> {
+        self.sign_typed_data_hashes(&domain.separator(), &value.struct_hash())
+    }
+
+    /// Signs the EIP-712 typed data given by the domain separator and the struct hash of the
+    /// message
+    pub fn sign_typed_data_hashes(
+        &self,
+        domain_separator: &[u8; 32],
+        struct_hash: &[u8; 32],
+    ) -> Result<Signature> {
+        let mut message = ProtoWriter::new();
+        message.path(1, &self.path);
+        message.bytes(2, domain_separator);
+        message.bytes(3, struct_hash);
+        let response = self.call(
+            ETHEREUM_SIGN_TYPED_HASH,
+            &message.finish(),
+            ETHEREUM_TYPED_DATA_SIGNATURE,
+        )?;
+        Signature::from_slice(field_bytes(&response, 1)?)
+    }
+
+    /// Returns the address at the path, optionally shown on the device
+    fn get_address(&self, show_display: bool) -> Result<Address> {
+        let mut message = ProtoWriter::new();
+        message.path(1, &self.path);
+        message.uint(2, show_display.into());
+        let response = self.call(ETHEREUM_GET_ADDRESS, &message.finish(), ETHEREUM_ADDRESS)?;
+        let address = std::str::from_utf8(field_bytes(&response, 2)?)
+            .map_err(|_| invalid_response("address"))?;
+        Address::from_str(address)
+    }
+
+    /// Sends the message and returns the response of the expected type. Button, PIN and
+    /// passphrase requests in between are answered.
+    fn call(&self, message_type: u16, message: &[u8], expected: u16) -> Result<Vec<u8>> {
+        let device = self.device.lock().expect("lock isn't poisoned");
+        self.call_locked(&device, message_type, message, expected)
+    }
+
+    /// Same as `call()` on the already locked device
+    fn call_locked(
+        &self,
+        device: &DeviceHandle<GlobalContext>,
+        message_type: u16,
+        message: &[u8],
+        expected: u16,
+    ) -> Result<Vec<u8>> {
+        self.exchange(device, message_type, message, &[expected])
+            .map(|(_, response)| response)
+    }
+
+    /// Sends the message and returns the type and the response of any of the expected types
+    fn exchange(
+        &self,
+        device: &DeviceHandle<GlobalContext>,
+        message_type: u16,
+        message: &[u8],
+        expected: &[u16],
+    ) -> Result<(u16, Vec<u8>)> {
+        let (mut message_type, mut message) = (message_type, message.to_vec());
+        loop {
+            write_message(device, message_type, &message)?;
+            let (response_type, response) = read_message(device)?;
+            (message_type, message) = match response_type {
+                response_type if expected.contains(&response_type) => {
+                    return Ok((response_type, response))
+                }
+                BUTTON_REQUEST => (BUTTON_ACK, Vec::new()),
+                PIN_MATRIX_REQUEST => {
+                    let pin = self.pin.as_ref().ok_or_else(|| {
+                        Error::Signer("Trezor asks for the PIN but no callback is set".to_string())
+                    })?;
+                    let mut ack = ProtoWriter::new();
+                    ack.bytes(1, pin().as_bytes());
+                    (PIN_MATRIX_ACK, ack.finish())
+                }
+                PASSPHRASE_REQUEST => {
+                    // Entered on the device
+                    let mut ack = ProtoWriter::new();
+                    ack.uint(3, 1);
+                    (PASSPHRASE_ACK, ack.finish())
+                }
+                FAILURE => {
+                    let reason = field_bytes(&response, 2).unwrap_or(b"unknown failure");
+                    return Err(Error::Signer(format!(
+                        "Trezor failure: {}",
+                        String::from_utf8_lossy(reason)
+                    )));
+                }
+                response_type => {
+                    return Err(Error::Signer(format!(
+                        "unexpected Trezor message type {response_type}"
+                    )))
+                }
+            };
+        }
+    }
+}
+
+impl fmt::Debug for TrezorSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrezorSigner")
+            .field("path", &format_args!("{}", self.path))
+            .field("address", &format_args!("{}", self.address))
+            .finish_non_exhaustive()
+    }
+}
+
+impl Signer for TrezorSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn sign_digest(&self, _digest: &[u8; 32]) -> Result<Signature> {
+        Err(Error::Signer(
+            "Trezor can't sign raw digests, only messages, typed data and transactions".to_string(),
+        ))
+    }
+
+    fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        let mut request = ProtoWriter::new();
+        request.path(1, &self.path);
+        request.bytes(2, message);
+        let response = self.call(
+            ETHEREUM_SIGN_MESSAGE,
+            &request.finish(),
+            ETHEREUM_MESSAGE_SIGNATURE,
+        )?;
+        Signature::from_slice(field_bytes(&response, 2)?)
+    }
+
+    fn sign_transaction(&self, transaction: &dyn Transaction) -> Result<Vec<u8>> {
+        let unsigned = transaction.encode_unsigned();
+        let (message_type, data, request) = match unsigned.split_first() {
+            Some((0x02, payload)) => {
+                let (data, request) = eip1559_request(&self.path, payload)?;
+                (ETHEREUM_SIGN_TX_EIP1559, data, request)
+            }
+            Some((0xc0.., _)) => {
+                let (data, request) = legacy_request(&self.path, &unsigned)?;
+                (ETHEREUM_SIGN_TX, data, request)
+            }
+            _ => {
+                return Err(Error::Signer(
+                    "Trezor supports only legacy and EIP-1559 transactions".to_string(),
+                ))
+            }
+        };
+
+        let device = self.device.lock().expect("lock isn't poisoned");
+        let mut response =
+            self.call_locked(&device, message_type, &request, ETHEREUM_TX_REQUEST)?;
+        // The device asks for the rest of the data by its length
+        let mut sent = data.len().min(DATA_CHUNK);
+        while let Some(length) = field_uint(&response, 1)? {
+            let chunk = data
+                .get(sent..sent + length as usize)
+                .ok_or_else(|| invalid_response("data length"))?;
+            sent += chunk.len();
+            let mut ack = ProtoWriter::new();
+            ack.bytes(1, chunk);
+            response =
+                self.call_locked(&device, ETHEREUM_TX_ACK, &ack.finish(), ETHEREUM_TX_REQUEST)?;
+        }
+
+        let v = field_uint(&response, 2)?.ok_or_else(|| invalid_response("signature"))?;
+        let (r, s) = (field_bytes(&response, 3)?, field_bytes(&response, 4)?);
+        let (Ok(r), Ok(s)) = (<[u8; 32]>::try_from(r), <[u8; 32]>::try_from(s)) else {
+            return Err(invalid_response("signature"));
+        };
+        // EIP-1559 returns the parity, legacy transactions `chain_id * 2 + 35 + parity`, which
+        // Trezor One truncates to 32 bits for large chain ids. The parity survives both.
+        let y_parity = match v {
+            0 | 1 => v == 1,
+            _ => v % 2 == 0,
+        };
+        let signature = Signature::new(r, s, y_parity)?;
+        Ok(transaction.encode_signed(&signature))
+    }
+}
+
+/// Creates `EthereumSignTx` from the RLP encoded fields of the unsigned EIP-155 legacy
+/// transaction, returns the data of the transaction and the message
+fn legacy_request(path: &DerivationPath, unsigned: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    let item = RlpItem::decode(unsigned)?;
+    let fields = match item.as_list() {
+        Some(fields) if fields.len() == 9 => fields,
+        _ => {
+            return Err(Error::Signer(
+                "Trezor signs only EIP-155 legacy transactions with a chain id".to_string(),
+            ))
+        }
+    };
+    let bytes = |index: usize| rlp_bytes(&fields[index]);
+
+    let data = bytes(5)?.to_vec();
+    let mut message = ProtoWriter::new();
+    message.path(1, path);
+    message.bytes(2, bytes(0)?);
+    message.bytes(3, bytes(1)?);
+    message.bytes(4, bytes(2)?);
+    message.bytes(6, bytes(4)?);
+    message.bytes(7, &data[..data.len().min(DATA_CHUNK)]);
+    message.uint(8, data.len() as u64);
+    message.uint(9, rlp_chain_id(&fields[6])?);
+    let to = bytes(3)?;
+    if !to.is_empty() {
+        message.string(11, &Address::from_slice(to)?.to_checksum());
+    }
+    Ok((data, message.finish()))
+}
+
+/// Creates `EthereumSignTxEIP1559` from the RLP encoded fields of the unsigned transaction,
+/// returns the data of the transaction and the message
+fn eip1559_request(path: &DerivationPath, payload: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    let item = RlpItem::decode(payload)?;
+    let fields = match item.as_list() {
+        Some(fields) if fields.len() == 9 => fields,
+        _ => {
+            return Err(Error::InvalidRlp(
+                "expected 9 transaction fields".to_string(),
+            ))
+        }
+    };
+    let bytes = |index: usize| rlp_bytes(&fields[index]);
+
+    let chain_id = rlp_chain_id(&fields[0])?;
+    let data = bytes(7)?.to_vec();
+    let mut message = ProtoWriter::new();
+    message.path(1, path);
+    message.bytes(2, bytes(1)?);
+    message.bytes(3, bytes(3)?);
+    message.bytes(4, bytes(2)?);
+    message.bytes(5, bytes(4)?);
+    let to = bytes(5)?;
+    if !to.is_empty() {
+        message.string(6, &Address::from_slice(to)?.to_checksum());
+    }
+    message.bytes(7, bytes(6)?);
+    message.bytes(8, &data[..data.len().min(DATA_CHUNK)]);
+    message.uint(9, data.len() as u64);
+    message.uint(10, chain_id);
+    for item in fields[8].as_list().unwrap_or_default() {
+        let Some([address, keys]) = item.as_list() else {
+            return Err(Error::InvalidRlp("invalid access list".to_string()));
+        };
+        let mut entry = ProtoWriter::new();
+        let address = address
+            .as_bytes()
+            .ok_or_else(|| Error::InvalidRlp("invalid access list".to_string()))?;
+        entry.string(1, &Address::from_slice(address)?.to_checksum());
+        for key in keys.as_list().unwrap_or_default() {
+            entry.bytes(2, key.as_bytes().unwrap_or_default());
+        }
+        message.bytes(11, &entry.finish());
+    }
+    Ok((data, message.finish()))
+}
+
+/// Returns the bytes of the RLP string
+fn rlp_bytes(item: &RlpItem) -> Result<&[u8]> {
+    item.as_bytes()
+        .ok_or_else(|| Error::InvalidRlp("expected string, found list".to_string()))
+}
+
+/// Returns the chain id of the transaction
+fn rlp_chain_id(item: &RlpItem) -> Result<u64> {
+    u64::try_from(item.to_uint()?).map_err(|_| Error::InvalidRlp("chain id is too big".to_string()))
+}
+
+/// Encodes `EthereumFieldType` of the typed data member type
+fn field_type(typed_data: &TypedData, ty: &str) -> Result<Vec<u8>> {
+    let unknown = || Error::InvalidTypedData(format!("unknown type {ty}"));
+    let size = |suffix: &str, default: u64| match suffix {
+        "" => Ok(default),
+        suffix => suffix.parse::<u64>().map_err(|_| unknown()),
+    };
+
+    let mut field = ProtoWriter::new();
+    if let Some((item_type, length)) = ty.strip_suffix(']').and_then(|ty| ty.rsplit_once('[')) {
+        field.uint(1, DATA_TYPE_ARRAY);
+        if !length.is_empty() {
+            field.uint(2, size(length, 0)?);
+        }
+        field.bytes(3, &field_type(typed_data, item_type)?);
+    } else if let Ok(members) = typed_data.members(ty) {
+        field.uint(1, DATA_TYPE_STRUCT);
+        field.uint(2, members.len() as u64);
+        field.string(4, ty);
+    } else {
+        let (data_type, size) = match ty {
+            "string" => (DATA_TYPE_STRING, None),
+            "bool" => (DATA_TYPE_BOOL, None),
+            "address" => (DATA_TYPE_ADDRESS, None),
+            "bytes" => (DATA_TYPE_BYTES, None),
+            _ if ty.starts_with("bytes") => (DATA_TYPE_BYTES, Some(size(&ty[5..], 32)?)),
+            _ if ty.starts_with("uint") => (DATA_TYPE_UINT, Some(size(&ty[4..], 256)? / 8)),
+            _ if ty.starts_with("int") => (DATA_TYPE_INT, Some(size(&ty[3..], 256)? / 8)),
+            _ => return Err(unknown()),
+        };
+        field.uint(1, data_type);
+        if let Some(size) = size {
+            field.uint(2, size);
+        }
+    }
+    Ok(field.finish())
+}
+
+/// Writes the message in 64 byte packets, the first one starts with `?##`, the message type and
+/// the length, the following ones with `?`
+fn write_message(
+    device: &DeviceHandle<GlobalContext>,
+    message_type: u16,
+    message: &[u8],
+) -> Result<()> {
+    let mut data = b"##".to_vec();
+    data.extend_from_slice(&message_type.to_be_bytes());
+    data.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    data.extend_from_slice(message);
+    for chunk in data.chunks(PACKET_SIZE - 1) {
+        let mut packet = [0u8; PACKET_SIZE];
+        packet[0] = b'?';
+        packet[1..1 + chunk.len()].copy_from_slice(chunk);
+        device
+            .write_interrupt(ENDPOINT_OUT, &packet, WRITE_TIMEOUT)
+            .map_err(usb_error)?;
+    }
+    Ok(())
+}
+
+/// Reads the message type and the message
+fn read_message(device: &DeviceHandle<GlobalContext>) -> Result<(u16, Vec<u8>)> {
+    let mut packet = [0u8; PACKET_SIZE];
+    let read = |packet: &mut [u8; PACKET_SIZE]| {
+        // Zero timeout waits until the user confirms or rejects on the device
+        device
+            .read_interrupt(ENDPOINT_IN, packet, Duration::ZERO)
+            .map_err(usb_error)
+    };
+
+    read(&mut packet)?;
+    if &packet[..3] != b"?##" {
+        return Err(invalid_response("packet"));
+    }
+    let message_type = u16::from_be_bytes([packet[3], packet[4]]);
+    let length = u32::from_be_bytes([packet[5], packet[6], packet[7], packet[8]]) as usize;
+    let mut message = packet[9..].to_vec();
+    while message.len() < length {
+        read(&mut packet)?;
+        if packet[0] != b'?' {
+            return Err(invalid_response("packet"));
+        }
+        message.extend_from_slice(&packet[1..]);
+    }
+    message.truncate(length);
+    Ok((message_type, message))
+}
+
+/// Minimal protobuf encoder of the wire protocol messages
+struct ProtoWriter(Vec<u8>);
+
+impl ProtoWriter {
+    /// Creates the empty message
+    fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Writes the varint field
+    fn uint(&mut self, field: u32, value: u64) {
+        self.varint(u64::from(field) << 3);
+        self.varint(value);
+    }
+
+    /// Writes the length delimited field
+    fn bytes(&mut self, field: u32, value: &[u8]) {
+        self.varint((u64::from(field) << 3) | 2);
+        self.varint(value.len() as u64);
+        self.0.extend_from_slice(value);
+    }
+
+    /// Writes the string field
+    fn string(&mut self, field: u32, value: &str) {
+        self.bytes(field, value.as_bytes());
+    }
+
+    /// Writes the path as the repeated `address_n` field
+    fn path(&mut self, field: u32, path: &DerivationPath) {
+        for index in path.indexes() {
+            self.uint(field, (*index).into());
+        }
+    }
+
+    /// Writes the variable length integer
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    /// Returns the encoded message
+    fn finish(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Value of the protobuf field
+enum ProtoValue<'a> {
+    /// Varint field
+    Uint(u64),
+    /// Length delimited field
+    Bytes(&'a [u8]),
+}
+
+/// Parses the fields of the protobuf message
+fn parse_message(mut message: &[u8]) -> Result<Vec<(u32, ProtoValue<'_>)>> {
+    let mut fields = Vec::new();
+    while !message.is_empty() {
+        let key = read_varint(&mut message)?;
+        let value = match key & 7 {
+            0 => ProtoValue::Uint(read_varint(&mut message)?),
+            2 => {
+                let length = read_varint(&mut message)? as usize;
+                if message.len() < length {
+                    return Err(invalid_response("message"));
+                }
+                let (value, rest) = message.split_at(length);
+                message = rest;
+                ProtoValue::Bytes(value)
+            }
+            _ => return Err(invalid_response("message")),
+        };
+        fields.push(((key >> 3) as u32, value));
+    }
+    Ok(fields)
+}
+
+/// Reads the variable length integer from the start of the input
+fn read_varint(input: &mut &[u8]) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input
+            .split_first()
+            .ok_or_else(|| invalid_response("message"))?;
+        *input = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return Ok(value);
+        }
+    }
+    Err(invalid_response("message"))
+}
+
+/// Returns the length delimited field of the message
+fn field_bytes(message: &[u8], field: u32) -> Result<&[u8]> {
+    parse_message(message)?
+        .into_iter()
+        .find_map(|(number, value)| match value {
+            ProtoValue::Bytes(bytes) if number == field => Some(bytes),
+            _ => None,
+        })
+        .ok_or_else(|| invalid_response("message"))
+}
+
+/// Returns the varint field of the message if it's present
+fn field_uint(message: &[u8], field: u32) -> Result<Option<u64>> {
+    Ok(parse_message(message)?
+        .into_iter()
+        .find_map(|(number, value)| match value {
+            ProtoValue::Uint(value) if number == field => Some(value),
+            _ => None,
+        }))
+}
+
+/// Returns the repeated varint field of the message, packed or not
+fn field_uints(message: &[u8], field: u32) -> Result<Vec<u32>> {
+    let mut values = Vec::new();
+    for (number, value) in parse_message(message)? {
+        match value {
+            _ if number != field => {}
+            ProtoValue::Uint(value) => values.push(value),
+            ProtoValue::Bytes(mut packed) => {
+                while !packed.is_empty() {
+                    values.push(read_varint(&mut packed)?);
+                }
+            }
+        }
+    }
+    values
+        .into_iter()
+        .map(|value| u32::try_from(value).map_err(|_| invalid_response("message")))
+        .collect()
+}
+
+/// Creates the error of the malformed response
+fn invalid_response(part: &str) -> Error {
+    Error::Signer(format!("Trezor returned invalid {part}"))
+}
+
+/// Converts the error of the USB transport
+fn usb_error(error: rusb::Error) -> Error {
+    Error::Signer(format!("Trezor USB error: {error}"))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_protobuf() {
+        let mut message = ProtoWriter::new();
+        message.path(1, &DerivationPath::ethereum(0));
+        message.uint(2, 300);
+        message.string(3, "hi");
+        let message = message.finish();
+        assert_eq!(
+            "08ac8080800808bc808080080880808080080800080010ac021a026869",
+            hex::encode(&message)
+        );
+        assert_eq!(Some(300), field_uint(&message, 2).unwrap());
+        assert_eq!(None, field_uint(&message, 4).unwrap());
+        assert_eq!(b"hi", field_bytes(&message, 3).unwrap());
+        assert!(field_bytes(&message[..message.len() - 1], 3).is_err());
+
+        assert_eq!(
+            vec![1, 7, 1],
+            field_uints(&hex::decode("080108070801").unwrap(), 1).unwrap()
+        );
+        assert_eq!(
+            vec![1, 7, 1],
+            field_uints(&hex::decode("0a03010701").unwrap(), 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_field_type() {
+        let typed_data = TypedData::parse(
+            r#"{
+                "types": {
+                    "Mail": [{"name": "to", "type": "Person[2]"}],
+                    "Person": [{"name": "name", "type": "string"}, {"name": "wallet", "type": "address"}]
+                },
+                "primaryType": "Mail",
+                "message": {}
+            }"#,
+        )
+        .unwrap();
+        let encode = |ty| field_type(&typed_data, ty).map(hex::encode);
+
+        assert_eq!(Ok("08011020".to_string()), encode("uint256"));
+        assert_eq!(Ok("08021001".to_string()), encode("int8"));
+        assert_eq!(Ok("08031004".to_string()), encode("bytes4"));
+        assert_eq!(Ok("0803".to_string()), encode("bytes"));
+        assert_eq!(Ok("0806".to_string()), encode("address"));
+        assert_eq!(Ok("08071a0408011001".to_string()), encode("uint8[]"));
+        assert_eq!(
+            Ok("080710021a0c080810022206506572736f6e".to_string()),
+            encode("Person[2]")
+        );
+        assert!(encode("float").is_err());
+    }
+}
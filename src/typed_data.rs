@@ -0,0 +1,672 @@
+use crate::eip712::hash_with_separator;
+use crate::utils::{keccak256, strip_hex_prefix};
+use crate::{Address, Error, PrivateKey, Result, Signature};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::str::FromStr;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Name of the domain type
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// Fields of the domain type in the order of the specification, used when the JSON doesn't
+/// define the domain type
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+/// Member of a struct type
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct Field {
+    /// Name of the member
+    name: String,
+    /// Solidity type of the member, e.g. `uint256`, `Person` or `address[]`
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+/// JSON payload of `eth_signTypedData_v4`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypedDataJson {
+    types: BTreeMap<String, Vec<Field>>,
+    #[serde(default)]
+    domain: Map<String, Value>,
+    primary_type: String,
+    message: Value,
+}
+
+/// EIP-712 typed data in the JSON format of `eth_signTypedData_v4`, hashed dynamically from the
+/// types in the payload. Use this to sign payloads produced by dapps, for your own structs the
+/// `Eip712` trait is simpler.
+///
+/// Integers can be given as JSON numbers, decimal strings or `0x` prefixed hex strings. If the
+/// `EIP712Domain` type is missing it's inferred from the fields of the domain.
+/// ```
+/// use core::str::FromStr;
+/// use ethereum_private_key_to_address::{PrivateKey, TypedData};
+///
+/// let json = r#"{
+///     "types": {
+///         "EIP712Domain": [{"name": "name", "type": "string"}, {"name": "chainId", "type": "uint256"}],
+///         "Greeting": [{"name": "text", "type": "string"}]
+///     },
+///     "domain": {"name": "Example", "chainId": 1},
+///     "primaryType": "Greeting",
+///     "message": {"text": "hello"}
+/// }"#;
+///
+/// let typed_data = TypedData::parse(json).unwrap();
+/// let private_key = PrivateKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+/// let signature = private_key.sign_typed_data_json(&typed_data).unwrap();
+///
+/// println!("{signature}");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedData {
+    /// Struct types by name, including the domain type
+    types: BTreeMap<String, Vec<Field>>,
+    /// Values of the domain fields
+    domain: Value,
+    /// Name of the type of the message
+    primary_type: String,
+    /// Message to sign
+    message: Value,
+}
+
+impl TypedData {
+    /// Parses the JSON payload with the `types`, `domain`, `primaryType` and `message` fields
+    pub fn parse(json: &str) -> Result<Self> {
+        let json: TypedDataJson =
+            serde_json::from_str(json).map_err(|error| invalid(error.to_string()))?;
+
+        let mut types = json.types;
+        types.entry(DOMAIN_TYPE.to_string()).or_insert_with(|| {
+            DOMAIN_FIELDS
+                .iter()
+                .filter(|(name, _)| json.domain.contains_key(*name))
+                .map(|(name, ty)| Field {
+                    name: name.to_string(),
+                    ty: ty.to_string(),
+                })
+                .collect()
+        });
+        if !types.contains_key(&json.primary_type) {
+            return Err(invalid(format!(
+                "unknown primary type {}",
+                json.primary_type
+            )));
+        }
+
+        Ok(Self {
+            types,
+            domain: Value::Object(json.domain),
+            primary_type: json.primary_type,
+            message: json.message,
+        })
+    }
+
+    /// Returns the name of the type of the message
+    pub fn primary_type(&self) -> &str {
+        &self.primary_type
+    }
+
+    /// Returns the encoded type, the type followed by the referenced struct types sorted by name
+    pub fn encode_type(&self, name: &str) -> Result<String> {
+        let mut dependencies = BTreeSet::new();
+        self.collect_dependencies(name, &mut dependencies)?;
+        dependencies.remove(name);
+
+        let mut encoded = String::new();
+        for name in core::iter::once(name).chain(dependencies) {
+            let fields = self
+                .fields(name)?
+                .iter()
+                .map(|field| format!("{} {}", field.ty, field.name))
+                .collect::<Vec<_>>();
+            encoded.push_str(&format!("{name}({})", fields.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    /// Returns the domain separator, the struct hash of the domain
+    pub fn domain_separator(&self) -> Result<[u8; 32]> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// Returns the struct hash of the message
+    pub fn struct_hash(&self) -> Result<[u8; 32]> {
+        self.hash_struct(&self.primary_type, &self.message)
+    }
+
+    /// Returns the hash that is signed, Keccak-256 of
+    /// `0x19 || 0x01 || domain separator || struct hash`. If the primary type is the domain
+    /// itself the struct hash is left out.
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        let separator = self.domain_separator()?;
+        if self.primary_type == DOMAIN_TYPE {
+            let mut data = vec![0x19, 0x01];
+            data.extend_from_slice(&separator);
+            return Ok(keccak256(&data));
+        }
+        Ok(hash_with_separator(&separator, &self.struct_hash()?))
+    }
+
+    /// Returns the names and the types of the members of the struct type
+    pub(crate) fn members(&self, name: &str) -> Result<Vec<(&str, &str)>> {
+        Ok(self
+            .fields(name)?
+            .iter()
+            .map(|field| (field.name.as_str(), field.ty.as_str()))
+            .collect())
+    }
+
+    /// Returns the type and the value of the member at the path, for hardware wallets that
+    /// request the typed data member by member. The path starts with 0 for the domain or 1 for
+    /// the message, followed by the member indexes of the structs and the item indexes of the
+    /// arrays.
+    pub(crate) fn member(&self, path: &[u32]) -> Result<(&str, &Value)> {
+        let (mut ty, mut value) = match path.first() {
+            Some(0) => (DOMAIN_TYPE, &self.domain),
+            Some(1) => (self.primary_type.as_str(), &self.message),
+            _ => return Err(invalid("member path has to start with 0 or 1")),
+        };
+        for index in &path[1..] {
+            let index = *index as usize;
+            let array = ty.strip_suffix(']').and_then(|ty| ty.rsplit_once('['));
+            if let Some((item_type, _)) = array {
+                value = value
+                    .as_array()
+                    .and_then(|items| items.get(index))
+                    .ok_or_else(|| invalid(format!("{ty} has no item {index}")))?;
+                ty = item_type;
+            } else {
+                let field = self
+                    .fields(ty)?
+                    .get(index)
+                    .ok_or_else(|| invalid(format!("{ty} has no member {index}")))?;
+                value = value
+                    .get(&field.name)
+                    .ok_or_else(|| invalid(format!("{ty} is missing field {}", field.name)))?;
+                ty = &field.ty;
+            }
+        }
+        Ok((ty, value))
+    }
+
+    /// Encodes the value of the atomic type as it is instead of the 32 byte word: integers and
+    /// `bytesN` in their size, addresses in 20 bytes, bools in one byte, `string` and `bytes`
+    /// unpadded
+    pub(crate) fn encode_atomic(&self, ty: &str, value: &Value) -> Result<Vec<u8>> {
+        let mismatch = || invalid(format!("{value} is not a valid {ty}"));
+        match ty {
+            "string" => return Ok(value.as_str().ok_or_else(mismatch)?.as_bytes().to_vec()),
+            "bytes" => return parse_bytes(value).ok_or_else(mismatch),
+            _ if ty.ends_with(']') || self.types.contains_key(ty) => {
+                return Err(invalid(format!("{ty} isn't an atomic type")))
+            }
+            _ => {}
+        }
+
+        let word = self.encode_value(ty, value)?;
+        let bytes = if let Some(suffix) = ty.strip_prefix("bytes") {
+            &word[..integer_size(suffix, 1, 32).ok_or_else(|| unknown_type(ty))?]
+        } else if let Some(suffix) = ty.strip_prefix("uint").or(ty.strip_prefix("int")) {
+            &word[32 - integer_size(suffix, 8, 256).ok_or_else(|| unknown_type(ty))? / 8..]
+        } else if ty == "address" {
+            &word[12..]
+        } else {
+            &word[31..]
+        };
+        Ok(bytes.to_vec())
+    }
+
+    /// Returns the fields of the struct type
+    fn fields(&self, name: &str) -> Result<&[Field]> {
+        self.types
+            .get(name)
+            .map(|fields| fields.as_slice())
+            .ok_or_else(|| invalid(format!("unknown type {name}")))
+    }
+
+    /// Adds the type and all struct types it references to the set
+    fn collect_dependencies<'a>(
+        &'a self,
+        name: &'a str,
+        found: &mut BTreeSet<&'a str>,
+    ) -> Result<()> {
+        let fields = self.fields(name)?;
+        if !found.insert(name) {
+            return Ok(());
+        }
+        for field in fields {
+            let base = base_type(&field.ty);
+            if self.types.contains_key(base) {
+                self.collect_dependencies(base, found)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `hashStruct` of the value of the struct type
+    fn hash_struct(&self, name: &str, value: &Value) -> Result<[u8; 32]> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| invalid(format!("value of {name} is not an object")))?;
+
+        let mut data = keccak256(self.encode_type(name)?.as_bytes()).to_vec();
+        for field in self.fields(name)? {
+            let value = object
+                .get(&field.name)
+                .ok_or_else(|| invalid(format!("{name} is missing field {}", field.name)))?;
+            data.extend_from_slice(&self.encode_value(&field.ty, value)?);
+        }
+        Ok(keccak256(&data))
+    }
+
+    /// Encodes the value of the type into a 32 byte word
+    fn encode_value(&self, ty: &str, value: &Value) -> Result<[u8; 32]> {
+        let mismatch = || invalid(format!("{value} is not a valid {ty}"));
+
+        let array = ty.strip_suffix(']').and_then(|ty| ty.rsplit_once('['));
+        if let Some((item_type, length)) = array {
+            let items = value.as_array().ok_or_else(mismatch)?;
+            if !length.is_empty() && length.parse::<usize>().ok() != Some(items.len()) {
+                return Err(mismatch());
+            }
+            let mut data = Vec::with_capacity(items.len() * 32);
+            for item in items {
+                data.extend_from_slice(&self.encode_value(item_type, item)?);
+            }
+            return Ok(keccak256(&data));
+        }
+        if self.types.contains_key(ty) {
+            return self.hash_struct(ty, value);
+        }
+
+        let mut word = [0u8; 32];
+        match ty {
+            "string" => word = keccak256(value.as_str().ok_or_else(mismatch)?.as_bytes()),
+            "bytes" => word = keccak256(&parse_bytes(value).ok_or_else(mismatch)?),
+            "address" => {
+                let address = value.as_str().ok_or_else(mismatch)?;
+                // Dapps don't always send valid checksums, the address is hashed as bytes anyway
+                let address = Address::from_str(&address.to_lowercase()).map_err(|_| mismatch())?;
+                word[12..].copy_from_slice(address.as_bytes());
+            }
+            "bool" => {
+                word[31] = match value {
+                    Value::Bool(value) => *value as u8,
+                    Value::String(value) if value == "true" => 1,
+                    Value::String(value) if value == "false" => 0,
+                    _ => return Err(mismatch()),
+                }
+            }
+            _ if ty.starts_with("bytes") => {
+                let size = integer_size(&ty[5..], 1, 32).ok_or_else(|| unknown_type(ty))?;
+                let bytes = parse_bytes(value).ok_or_else(mismatch)?;
+                if bytes.len() > size {
+                    return Err(mismatch());
+                }
+                word[..bytes.len()].copy_from_slice(&bytes);
+            }
+            _ if ty.starts_with("uint") => {
+                let bits = integer_size(&ty[4..], 8, 256).ok_or_else(|| unknown_type(ty))?;
+                let (negative, magnitude) = parse_integer(value).ok_or_else(mismatch)?;
+                if (negative && magnitude != [0u8; 32]) || (bits < 256 && magnitude >= pow2(bits)) {
+                    return Err(mismatch());
+                }
+                word = magnitude;
+            }
+            _ if ty.starts_with("int") => {
+                let bits = integer_size(&ty[3..], 8, 256).ok_or_else(|| unknown_type(ty))?;
+                let (negative, magnitude) = parse_integer(value).ok_or_else(mismatch)?;
+                let limit = pow2(bits - 1);
+                if (negative && magnitude > limit) || (!negative && magnitude >= limit) {
+                    return Err(mismatch());
+                }
+                word = if negative {
+                    negate(magnitude)
+                } else {
+                    magnitude
+                };
+            }
+            _ => return Err(unknown_type(ty)),
+        }
+        Ok(word)
+    }
+}
+
+impl FromStr for TypedData {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl PrivateKey {
+    /// Signs the typed data JSON the same way as `eth_signTypedData_v4`, the data is hashed by
+    /// `TypedData::hash()`
+    pub fn sign_typed_data_json(&self, typed_data: &TypedData) -> Result<Signature> {
+        Ok(self.sign_hash(&typed_data.hash()?))
+    }
+}
+
+/// Creates the error of invalid typed data
+fn invalid(reason: impl Into<String>) -> Error {
+    Error::InvalidTypedData(reason.into())
+}
+
+/// Creates the error of unsupported member type
+fn unknown_type(ty: &str) -> Error {
+    invalid(format!("unknown type {ty}"))
+}
+
+/// Returns the type without the array dimensions, e.g. `Person` for `Person[][2]`
+fn base_type(ty: &str) -> &str {
+    ty.split('[').next().unwrap_or(ty)
+}
+
+/// Parses the size suffix of `uintN`, `intN` or `bytesN`, which must be a multiple of `step`
+/// up to `max`. Missing suffix means `max`, e.g. `uint` is `uint256`.
+fn integer_size(suffix: &str, step: usize, max: usize) -> Option<usize> {
+    if suffix.is_empty() && step > 1 {
+        return Some(max);
+    }
+    let size = suffix.parse::<usize>().ok()?;
+    (size > 0 && size <= max && size % step == 0 && !suffix.starts_with('0')).then_some(size)
+}
+
+/// Parses the `0x` prefixed hex string into bytes
+fn parse_bytes(value: &Value) -> Option<Vec<u8>> {
+    hex::decode(strip_hex_prefix(value.as_str()?)).ok()
+}
+
+/// Parses the integer from JSON number, decimal string or `0x` prefixed hex string. Returns the
+/// sign and the 256 bit big-endian magnitude.
+fn parse_integer(value: &Value) -> Option<(bool, [u8; 32])> {
+    let mut magnitude = [0u8; 32];
+    match value {
+        Value::Number(number) => {
+            let (negative, value) = match number.as_u64() {
+                Some(value) => (false, value),
+                None => (true, number.as_i64()?.unsigned_abs()),
+            };
+            magnitude[24..].copy_from_slice(&value.to_be_bytes());
+            Some((negative, magnitude))
+        }
+        Value::String(value) => {
+            let (negative, value) = match value.strip_prefix('-') {
+                Some(value) => (true, value),
+                None => (false, value.as_str()),
+            };
+            if let Some(hex) = value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+                if hex.is_empty() || hex.len() > 64 {
+                    return None;
+                }
+                let hex = format!("{hex:0>64}");
+                hex::decode_to_slice(hex, &mut magnitude).ok()?;
+            } else {
+                if value.is_empty() {
+                    return None;
+                }
+                for digit in value.chars() {
+                    let mut carry = digit.to_digit(10)?;
+                    for byte in magnitude.iter_mut().rev() {
+                        let product = *byte as u32 * 10 + carry;
+                        *byte = product as u8;
+                        carry = product >> 8;
+                    }
+                    if carry != 0 {
+                        return None;
+                    }
+                }
+            }
+            Some((negative, magnitude))
+        }
+        _ => None,
+    }
+}
+
+/// Returns `2^exponent` as 256 bit big-endian integer, the exponent must be below 256
+fn pow2(exponent: usize) -> [u8; 32] {
+    let mut value = [0u8; 32];
+    value[31 - exponent / 8] = 1 << (exponent % 8);
+    value
+}
+
+/// Returns the two's complement of the 256 bit big-endian integer
+fn negate(mut value: [u8; 32]) -> [u8; 32] {
+    let mut carry = true;
+    for byte in value.iter_mut().rev() {
+        let (sum, overflow) = (!*byte).overflowing_add(carry as u8);
+        *byte = sum;
+        carry = overflow;
+    }
+    value
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    // Example from the EIP-712 specification
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Person": [
+                {"name": "name", "type": "string"},
+                {"name": "wallet", "type": "address"}
+            ],
+            "Mail": [
+                {"name": "from", "type": "Person"},
+                {"name": "to", "type": "Person"},
+                {"name": "contents", "type": "string"}
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+            "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    const ORDER: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "salt", "type": "bytes32"}
+            ],
+            "Order": [
+                {"name": "maker", "type": "address"},
+                {"name": "amounts", "type": "uint256[]"},
+                {"name": "delta", "type": "int64"},
+                {"name": "tags", "type": "string[]"},
+                {"name": "data", "type": "bytes"},
+                {"name": "flag", "type": "bool"},
+                {"name": "id", "type": "bytes4"},
+                {"name": "legs", "type": "Leg[2]"}
+            ],
+            "Leg": [
+                {"name": "asset", "type": "address"},
+                {"name": "size", "type": "uint128"}
+            ]
+        },
+        "domain": {
+            "name": "Exchange",
+            "chainId": "0x89",
+            "salt": "0x0000000000000000000000000000000000000000000000000000000000000001"
+        },
+        "primaryType": "Order",
+        "message": {
+            "maker": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "amounts": [
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                "0x1bc16d674ec80000",
+                7
+            ],
+            "delta": -5,
+            "tags": ["a", "b"],
+            "data": "0xdeadbeef",
+            "flag": true,
+            "id": "0x12345678",
+            "legs": [
+                {"asset": "0x70997970c51812dc3a010c7d01b50e0d17dc79c8", "size": "1000"},
+                {"asset": "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc", "size": 2}
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_mail() {
+        let typed_data = TypedData::parse(MAIL).unwrap();
+        assert_eq!(
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+            typed_data.encode_type("Mail").unwrap()
+        );
+        assert_eq!(
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f",
+            hex::encode(typed_data.domain_separator().unwrap())
+        );
+        assert_eq!(
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2",
+            hex::encode(typed_data.hash().unwrap())
+        );
+
+        let private_key = PrivateKey::try_from(keccak256(b"cow")).unwrap();
+        assert_eq!(
+            "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c",
+            private_key.sign_typed_data_json(&typed_data).unwrap().to_string()
+        );
+
+        // The domain type is inferred from the domain fields if it's missing
+        let mut json: Value = serde_json::from_str(MAIL).unwrap();
+        json["types"].as_object_mut().unwrap().remove(DOMAIN_TYPE);
+        let inferred = TypedData::parse(&json.to_string()).unwrap();
+        assert_eq!(typed_data.hash(), inferred.hash());
+    }
+
+    #[test]
+    fn test_arrays_and_integers() {
+        let typed_data = TypedData::from_str(ORDER).unwrap();
+        assert_eq!(
+            "Order(address maker,uint256[] amounts,int64 delta,string[] tags,bytes data,bool flag,bytes4 id,Leg[2] legs)Leg(address asset,uint128 size)",
+            typed_data.encode_type("Order").unwrap()
+        );
+        assert_eq!(
+            "01a20fe9daf4943104dbf80d6a97c9784a82efd5f194f4ad5680f865c2e1b9b5",
+            hex::encode(typed_data.hash().unwrap())
+        );
+
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        assert_eq!(
+            "0x340d0fc1d0b60e591cd902e15c5c966fa71dbf2017d270fe748d852a12856401516a2da5cc5210d750e38c746dc175ccd9ef4dab39fcf69175142c8f82b89f801c",
+            private_key.sign_typed_data_json(&typed_data).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_members() {
+        let typed_data = TypedData::from_str(ORDER).unwrap();
+        assert_eq!(
+            vec![("asset", "address"), ("size", "uint128")],
+            typed_data.members("Leg").unwrap()
+        );
+        assert!(typed_data.members("Unknown").is_err());
+
+        let encode = |path: &[u32]| {
+            let (ty, value) = typed_data.member(path)?;
+            typed_data.encode_atomic(ty, value).map(hex::encode)
+        };
+        assert_eq!(Ok("45786368616e6765".to_string()), encode(&[0, 0]));
+        assert_eq!(
+            Ok("f39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string()),
+            encode(&[1, 0])
+        );
+        assert_eq!(
+            Ok(format!("{:0>64}", "1bc16d674ec80000")),
+            encode(&[1, 1, 1])
+        );
+        assert_eq!(Ok("fffffffffffffffb".to_string()), encode(&[1, 2]));
+        assert_eq!(Ok("62".to_string()), encode(&[1, 3, 1]));
+        assert_eq!(Ok("deadbeef".to_string()), encode(&[1, 4]));
+        assert_eq!(Ok("01".to_string()), encode(&[1, 5]));
+        assert_eq!(Ok("12345678".to_string()), encode(&[1, 6]));
+        assert_eq!(Ok(format!("{:0>32}", "02")), encode(&[1, 7, 1, 1]));
+
+        assert_eq!(
+            2,
+            typed_data
+                .member(&[1, 7])
+                .unwrap()
+                .1
+                .as_array()
+                .unwrap()
+                .len()
+        );
+        assert!(encode(&[1, 7]).is_err());
+        assert!(encode(&[1, 7, 0]).is_err());
+        assert!(encode(&[1, 8]).is_err());
+        assert!(encode(&[2]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_typed_data() {
+        for (from, to) in [
+            (r#""primaryType": "Order""#, r#""primaryType": "Unknown""#),
+            (r#""type": "uint128""#, r#""type": "uint129""#),
+            (r#""size": 2"#, r#""size": -2"#),
+            (r#""delta": -5"#, r#""delta": "0x10000000000000000""#),
+            (r#""id": "0x12345678""#, r#""id": "0x1234567890""#),
+            (r#""Leg[2]""#, r#""Leg[3]""#),
+            (r#""flag": true"#, r#""flag": 1"#),
+            (r#""data": "0xdeadbeef","#, ""),
+        ] {
+            let json = ORDER.replace(from, to);
+            assert!(
+                matches!(
+                    TypedData::parse(&json).and_then(|typed_data| typed_data.hash()),
+                    Err(Error::InvalidTypedData(_))
+                ),
+                "{to}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_integer() {
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(Some((false, [0xff; 32])), parse_integer(&Value::from(max)));
+        assert_eq!(None, parse_integer(&Value::from(format!("{max}0"))));
+        assert_eq!(None, parse_integer(&Value::from("12a")));
+        assert_eq!(
+            [0xff; 32],
+            negate(parse_integer(&Value::from(1)).unwrap().1)
+        );
+        assert_eq!(pow2(255), negate(pow2(255)));
+    }
+}
@@ -0,0 +1,1559 @@
+use crate::derivation_path::HARDENED;
+use crate::utils::keccak256;
+use crate::{
+    hash_message, Address, DerivationPath, Error, ExtendedPrivateKey, IntoDerivationPath,
+    PrivateKey, PublicKey, Result, Signature, TypedData, VEncoding,
+};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Bytewords, the minimal form of a word is its first and last letter
+const BYTEWORDS: [&str; 256] = [
+    "able", "acid", "also", "apex", "aqua", "arch", "atom", "aunt", "away", "axis", "back", "bald",
+    "barn", "belt", "beta", "bias", "blue", "body", "brag", "brew", "bulb", "buzz", "calm", "cash",
+    "cats", "chef", "city", "claw", "code", "cola", "cook", "cost", "crux", "curl", "cusp", "cyan",
+    "dark", "data", "days", "deli", "dice", "diet", "door", "down", "draw", "drop", "drum", "dull",
+    "duty", "each", "easy", "echo", "edge", "epic", "even", "exam", "exit", "eyes", "fact", "fair",
+    "fern", "figs", "film", "fish", "fizz", "flap", "flew", "flux", "foxy", "free", "frog", "fuel",
+    "fund", "gala", "game", "gear", "gems", "gift", "girl", "glow", "good", "gray", "grim", "guru",
+    "gush", "gyro", "half", "hang", "hard", "hawk", "heat", "help", "high", "hill", "holy", "hope",
+    "horn", "huts", "iced", "idea", "idle", "inch", "inky", "into", "iris", "iron", "item", "jade",
+    "jazz", "join", "jolt", "jowl", "judo", "jugs", "jump", "junk", "jury", "keep", "keno", "kept",
+    "keys", "kick", "kiln", "king", "kite", "kiwi", "knob", "lamb", "lava", "lazy", "leaf", "legs",
+    "liar", "limp", "lion", "list", "logo", "loud", "love", "luau", "luck", "lung", "main", "many",
+    "math", "maze", "memo", "menu", "meow", "mild", "mint", "miss", "monk", "nail", "navy", "need",
+    "news", "next", "noon", "note", "numb", "obey", "oboe", "omit", "onyx", "open", "oval", "owls",
+    "paid", "part", "peck", "play", "plus", "poem", "pool", "pose", "puff", "puma", "purr", "quad",
+    "quiz", "race", "ramp", "real", "redo", "rich", "road", "rock", "roof", "ruby", "ruin", "runs",
+    "rust", "safe", "saga", "scar", "sets", "silk", "skew", "slot", "soap", "solo", "song", "stub",
+    "surf", "swan", "taco", "task", "taxi", "tent", "tied", "time", "tiny", "toil", "tomb", "toys",
+    "trip", "tuna", "twin", "ugly", "undo", "unit", "urge", "user", "vast", "very", "veto", "vial",
+    "vibe", "view", "visa", "void", "vows", "wall", "wand", "warm", "wasp", "wave", "waxy", "webs",
+    "what", "when", "whiz", "wolf", "work", "yank", "yawn", "yell", "yoga", "yurt", "zaps", "zero",
+    "zest", "zinc", "zone", "zoom",
+];
+
+/// CBOR tag of the UUID
+const TAG_UUID: u64 = 37;
+
+/// CBOR tag of `crypto-hdkey`
+const TAG_HDKEY: u64 = 303;
+
+/// CBOR tag of `crypto-keypath`
+const TAG_KEYPATH: u64 = 304;
+
+/// CBOR tag of `eth-sign-request`
+const TAG_ETH_SIGN_REQUEST: u64 = 401;
+
+/// CBOR tag of `eth-signature`
+const TAG_ETH_SIGNATURE: u64 = 402;
+
+/// Deepest nesting of the decoded CBOR, the registry types need only a few levels
+const MAX_DEPTH: usize = 8;
+
+/// Uniform Resource (BCR-2020-005), the CBOR payload with its registry type, e.g.
+/// `ur:eth-sign-request/...`. The payload is encoded in minimal Bytewords with a CRC32 checksum,
+/// so the UR fits into an alphanumeric QR code.
+///
+/// Payloads which don't fit into one QR code are split into parts by `to_parts()` and shown as an
+/// animated QR code, `UrDecoder` joins the scanned parts back.
+/// ```
+/// use ethereum_private_key_to_address::Ur;
+///
+/// let ur: Ur = "UR:CRYPTO-SEED/OEADGDSTASLPLABGHYDRPFMKBGGUFGLUDPRFGMAOTPIECFFLTNLPQDENOS".parse().unwrap();
+/// assert_eq!(ur.ur_type(), "crypto-seed");
+/// assert_eq!(ur.to_string(), "ur:crypto-seed/oeadgdstaslplabghydrpfmkbggufgludprfgmaotpiecffltnlpqdenos");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ur {
+    /// Registry type, lowercase letters, digits and hyphens
+    ur_type: String,
+    /// CBOR payload
+    cbor: Vec<u8>,
+}
+
+impl Ur {
+    /// Creates the UR of the CBOR payload, fails if the type isn't made of lowercase letters,
+    /// digits and hyphens
+    pub fn new(ur_type: impl Into<String>, cbor: Vec<u8>) -> Result<Self> {
+        let ur_type = ur_type.into();
+        let valid = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-';
+        if ur_type.is_empty() || !ur_type.chars().all(valid) {
+            return Err(invalid_ur(format!("invalid type {ur_type:?}")));
+        }
+        Ok(Self { ur_type, cbor })
+    }
+
+    /// Returns the registry type
+    pub fn ur_type(&self) -> &str {
+        &self.ur_type
+    }
+
+    /// Returns the CBOR payload
+    pub fn cbor(&self) -> &[u8] {
+        &self.cbor
+    }
+
+    /// Splits the UR into the parts of an animated QR code, `ur:{type}/{seq}-{count}/...`, with at
+    /// most `max_fragment_len` bytes of the payload in each. Returns the single-part UR if the
+    /// payload fits into one fragment.
+    ///
+    /// These are the `count` parts holding one fragment each. Keep the animation going with the
+    /// fountain coded parts of `part()` after them, so the scanner recovers the missed fragments
+    /// without waiting for the whole loop.
+    ///
+    /// # Panics
+    ///
+    /// If `max_fragment_len` is zero.
+    pub fn to_parts(&self, max_fragment_len: usize) -> Vec<String> {
+        assert!(max_fragment_len > 0, "fragment length can't be zero");
+        if self.cbor.len() <= max_fragment_len {
+            return vec![self.to_string()];
+        }
+        let count = self.cbor.len().div_ceil(max_fragment_len);
+        (1..=count as u32)
+            .map(|seq_num| self.part(max_fragment_len, seq_num))
+            .collect()
+    }
+
+    /// Returns the part `seq_num` of the multi-part UR, counted from 1. The first `count` parts
+    /// hold one fragment each, the parts after them are fountain coded, the XOR of the fragments
+    /// chosen by the sequence number as in the reference implementation, so the animation can
+    /// go on for as long as the scanner needs.
+    /// ```
+    /// use ethereum_private_key_to_address::{Ur, UrDecoder};
+    ///
+    /// let ur = Ur::new("bytes", (0..=255).collect()).unwrap();
+    /// assert!(ur.part(60, 100).starts_with("ur:bytes/100-5/"));
+    ///
+    /// let mut decoder = UrDecoder::new();
+    /// let decoded = (1..)
+    ///     .find_map(|seq_num| decoder.receive(&ur.part(60, seq_num * 2)).unwrap())
+    ///     .unwrap();
+    /// assert_eq!(decoded, ur);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `max_fragment_len` or `seq_num` is zero.
+    pub fn part(&self, max_fragment_len: usize, seq_num: u32) -> String {
+        assert!(max_fragment_len > 0, "fragment length can't be zero");
+        assert!(seq_num > 0, "sequence numbers start at 1");
+        let count = self.cbor.len().div_ceil(max_fragment_len).max(1);
+        let fragment_len = self.cbor.len().div_ceil(count);
+        let checksum = crc32(&self.cbor);
+
+        // The last fragment is padded with zeroes
+        let mut data = vec![0; fragment_len];
+        for index in choose_fragments(seq_num, count, checksum) {
+            let start = (index * fragment_len).min(self.cbor.len());
+            let end = (start + fragment_len).min(self.cbor.len());
+            xor(&mut data, &self.cbor[start..end]);
+        }
+        let part = Cbor::Array(vec![
+            Cbor::Uint(u64::from(seq_num)),
+            Cbor::Uint(count as u64),
+            Cbor::Uint(self.cbor.len() as u64),
+            Cbor::Uint(u64::from(checksum)),
+            Cbor::Bytes(data),
+        ]);
+        format!(
+            "ur:{}/{seq_num}-{count}/{}",
+            self.ur_type,
+            encode_bytewords(&part.encode())
+        )
+    }
+}
+
+impl fmt::Display for Ur {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ur:{}/{}", self.ur_type, encode_bytewords(&self.cbor))
+    }
+}
+
+impl FromStr for Ur {
+    type Err = Error;
+
+    /// Parses the single-part UR, case insensitive as QR codes use uppercase
+    fn from_str(s: &str) -> Result<Self> {
+        match split_ur(s)? {
+            (ur_type, None, bytewords) => Self::new(ur_type, decode_bytewords(&bytewords)?),
+            (_, Some(_), _) => Err(invalid_ur("multi-part UR, join the parts by UrDecoder")),
+        }
+    }
+}
+
+/// Joins the parts of a multi-part UR scanned from an animated QR code. The parts can come in any
+/// order and repeat.
+///
+/// The fountain coded parts, which mix several fragments, are reduced by the fragments already
+/// known, so the fragments missed in the first loop are recovered from the parts after it.
+/// ```
+/// use ethereum_private_key_to_address::{Ur, UrDecoder};
+///
+/// let ur = Ur::new("bytes", vec![0x45; 300]).unwrap();
+///
+/// let mut decoder = UrDecoder::new();
+/// let mut decoded = None;
+/// for part in ur.to_parts(100).iter().rev() {
+///     decoded = decoder.receive(part).unwrap();
+/// }
+/// assert_eq!(decoded, Some(ur));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UrDecoder {
+    /// Type of the UR, set by the first part
+    ur_type: Option<String>,
+    /// Length of the payload
+    message_len: usize,
+    /// CRC32 of the payload
+    checksum: u32,
+    /// Length of the fragments, set by the first part
+    fragment_len: usize,
+    /// Fragments by their index, `None` until scanned or recovered
+    fragments: Vec<Option<Vec<u8>>>,
+    /// Fountain coded parts which still mix several unknown fragments, the sorted indexes of the
+    /// fragments and the XOR of them
+    mixed: Vec<(Vec<usize>, Vec<u8>)>,
+}
+
+impl UrDecoder {
+    /// Creates the decoder waiting for the first part
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the scanned part, returns the UR once all fragments are scanned. Single-part URs are
+    /// returned right away. Fails if the part is malformed or belongs to a different UR.
+    pub fn receive(&mut self, part: &str) -> Result<Option<Ur>> {
+        let (ur_type, sequence, bytewords) = split_ur(part)?;
+        let Some((seq_num, seq_len)) = sequence else {
+            return Ur::new(ur_type, decode_bytewords(&bytewords)?).map(Some);
+        };
+
+        let cbor = Cbor::decode(&decode_bytewords(&bytewords)?)?;
+        let (fragment, message_len, checksum) = match cbor.as_array("part")? {
+            [num, len, message_len, checksum, Cbor::Bytes(fragment)]
+                if num.as_uint("sequence number")? == seq_num as u64
+                    && len.as_uint("sequence length")? == seq_len as u64 =>
+            {
+                let message_len = usize::try_from(message_len.as_uint("message length")?)
+                    .map_err(|_| invalid_ur("message length is too large"))?;
+                let checksum = u32::try_from(checksum.as_uint("checksum")?)
+                    .map_err(|_| invalid_ur("checksum is too large"))?;
+                (fragment, message_len, checksum)
+            }
+            _ => return Err(invalid_ur("part doesn't match its sequence")),
+        };
+
+        match &self.ur_type {
+            None => {
+                if fragment.is_empty() || fragment.len().saturating_mul(seq_len) < message_len {
+                    return Err(invalid_ur("fragments are shorter than the message"));
+                }
+                self.ur_type = Some(ur_type);
+                self.message_len = message_len;
+                self.checksum = checksum;
+                self.fragment_len = fragment.len();
+                self.fragments = vec![None; seq_len];
+                self.mixed.clear();
+            }
+            Some(expected)
+                if *expected != ur_type
+                    || self.message_len != message_len
+                    || self.checksum != checksum
+                    || self.fragments.len() != seq_len =>
+            {
+                return Err(invalid_ur("part belongs to a different UR"));
+            }
+            Some(_) if self.fragment_len != fragment.len() => {
+                return Err(invalid_ur("fragments have different lengths"));
+            }
+            Some(_) => {}
+        }
+
+        let seq_num =
+            u32::try_from(seq_num).map_err(|_| invalid_ur("sequence number is too large"))?;
+        self.add_part(
+            choose_fragments(seq_num, seq_len, checksum),
+            fragment.clone(),
+        );
+        if !self.is_complete() {
+            return Ok(None);
+        }
+
+        let mut message: Vec<u8> = self.fragments.iter().flatten().flatten().copied().collect();
+        message.truncate(self.message_len);
+        if crc32(&message) != self.checksum {
+            return Err(invalid_ur("checksum of the joined message doesn't match"));
+        }
+        let ur_type = self.ur_type.clone().expect("type is set by the first part");
+        Ur::new(ur_type, message).map(Some)
+    }
+
+    /// Reduces the part by the known fragments and keeps it while it mixes several unknown ones.
+    /// Each recovered fragment reduces the kept parts again, which may recover more of them.
+    fn add_part(&mut self, indexes: Vec<usize>, data: Vec<u8>) {
+        let mut queue = vec![(indexes, data)];
+        while let Some((mut indexes, mut data)) = queue.pop() {
+            indexes.retain(|&index| match &self.fragments[index] {
+                Some(fragment) => {
+                    xor(&mut data, fragment);
+                    false
+                }
+                None => true,
+            });
+            match indexes[..] {
+                [] => {}
+                [index] => {
+                    self.fragments[index] = Some(data);
+                    let (reducible, mixed) = std::mem::take(&mut self.mixed)
+                        .into_iter()
+                        .partition(|(mixed, _)| mixed.contains(&index));
+                    self.mixed = mixed;
+                    queue.extend(reducible);
+                }
+                _ => {
+                    if !self.mixed.iter().any(|(mixed, _)| *mixed == indexes) {
+                        self.mixed.push((indexes, data));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns true once all fragments are scanned
+    pub fn is_complete(&self) -> bool {
+        !self.fragments.is_empty() && self.fragments.iter().all(Option::is_some)
+    }
+
+    /// Returns the number of scanned fragments and the number of all fragments, zeroes before the
+    /// first part
+    pub fn progress(&self) -> (usize, usize) {
+        let scanned = self.fragments.iter().filter(|f| f.is_some()).count();
+        (scanned, self.fragments.len())
+    }
+}
+
+/// Account key (`crypto-hdkey`, BCR-2020-007) exported from the offline wallet to the watch-only
+/// one, e.g. MetaMask. It holds the public key and the chain code, so the watch-only wallet derives
+/// the addresses of the account without the private keys.
+/// ```
+/// use ethereum_private_key_to_address::{CryptoHdKey, ExtendedPrivateKey, Mnemonic};
+///
+/// let mnemonic = Mnemonic::parse("test test test test test test test test test test test junk").unwrap();
+/// let master = ExtendedPrivateKey::from_seed(&mnemonic.to_seed()[..]).unwrap();
+///
+/// let key = CryptoHdKey::from_master(&master, "m/44'/60'/0'").unwrap().with_name("Offline wallet");
+/// let ur = key.to_ur().to_string();
+///
+/// assert_eq!(CryptoHdKey::from_ur(&ur.parse().unwrap()).unwrap(), key);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CryptoHdKey {
+    /// Public key of the account
+    public_key: PublicKey,
+    /// Chain code of the account
+    chain_code: Option<[u8; 32]>,
+    /// Derivation path of the account from the master key
+    origin: Option<DerivationPath>,
+    /// Fingerprint of the master key
+    source_fingerprint: Option<[u8; 4]>,
+    /// Fingerprint of the parent key
+    parent_fingerprint: Option<[u8; 4]>,
+    /// Name of the account shown by the watch-only wallet
+    name: Option<String>,
+    /// Note, e.g. `account.standard` for the BIP-44 accounts in MetaMask
+    note: Option<String>,
+}
+
+impl CryptoHdKey {
+    /// UR type of the key
+    pub const UR_TYPE: &'static str = "crypto-hdkey";
+
+    /// Creates the key from the public key and the chain code
+    pub fn new(public_key: PublicKey, chain_code: [u8; 32]) -> Self {
+        Self {
+            public_key,
+            chain_code: Some(chain_code),
+            origin: None,
+            source_fingerprint: None,
+            parent_fingerprint: None,
+            name: None,
+            note: None,
+        }
+    }
+
+    /// Derives the account key at the path from the master key, with the origin and the
+    /// fingerprints set
+    pub fn from_master(master: &ExtendedPrivateKey, path: impl IntoDerivationPath) -> Result<Self> {
+        let path = path.into_derivation_path()?;
+        let account = master.derive_path(&path)?;
+        let mut key = Self::new(account.private_key().to_public_key(), *account.chain_code())
+            .with_origin(path)
+            .with_source_fingerprint(master.fingerprint());
+        if account.depth() > 0 {
+            key = key.with_parent_fingerprint(account.parent_fingerprint());
+        }
+        Ok(key)
+    }
+
+    /// Sets the derivation path of the key from the master key
+    pub fn with_origin(mut self, origin: DerivationPath) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Sets the fingerprint of the master key
+    pub fn with_source_fingerprint(mut self, fingerprint: [u8; 4]) -> Self {
+        self.source_fingerprint = Some(fingerprint);
+        self
+    }
+
+    /// Sets the fingerprint of the parent key
+    pub fn with_parent_fingerprint(mut self, fingerprint: [u8; 4]) -> Self {
+        self.parent_fingerprint = Some(fingerprint);
+        self
+    }
+
+    /// Sets the name of the account
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the note
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Returns the public key
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// Returns the chain code
+    pub fn chain_code(&self) -> Option<&[u8; 32]> {
+        self.chain_code.as_ref()
+    }
+
+    /// Returns the derivation path from the master key
+    pub fn origin(&self) -> Option<&DerivationPath> {
+        self.origin.as_ref()
+    }
+
+    /// Returns the fingerprint of the master key
+    pub fn source_fingerprint(&self) -> Option<[u8; 4]> {
+        self.source_fingerprint
+    }
+
+    /// Returns the fingerprint of the parent key
+    pub fn parent_fingerprint(&self) -> Option<[u8; 4]> {
+        self.parent_fingerprint
+    }
+
+    /// Returns the name of the account
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the note
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    /// Encodes the key into the `crypto-hdkey` UR
+    pub fn to_ur(&self) -> Ur {
+        let mut map = vec![(
+            3,
+            Cbor::Bytes(self.public_key.serialize_compressed().to_vec()),
+        )];
+        if let Some(chain_code) = self.chain_code {
+            map.push((4, Cbor::Bytes(chain_code.to_vec())));
+        }
+        if let Some(origin) = &self.origin {
+            map.push((6, encode_keypath(origin, self.source_fingerprint)));
+        }
+        if let Some(fingerprint) = self.parent_fingerprint {
+            map.push((8, Cbor::Uint(u32::from_be_bytes(fingerprint).into())));
+        }
+        if let Some(name) = &self.name {
+            map.push((9, Cbor::Text(name.clone())));
+        }
+        if let Some(note) = &self.note {
+            map.push((10, Cbor::Text(note.clone())));
+        }
+        registry_ur(Self::UR_TYPE, Cbor::Map(map))
+    }
+
+    /// Decodes the key from the `crypto-hdkey` UR, the tagged CBOR is accepted too. Private keys
+    /// aren't supported.
+    pub fn from_ur(ur: &Ur) -> Result<Self> {
+        let cbor = registry_cbor(ur, Self::UR_TYPE)?;
+        let map = cbor.untag(TAG_HDKEY)?.as_map("crypto-hdkey")?;
+        if let Some(is_private) = field(map, 2) {
+            if is_private.as_bool("is-private")? {
+                return Err(invalid_ur("private crypto-hdkey isn't supported"));
+            }
+        }
+
+        let key_data = field(map, 3).ok_or_else(|| invalid_ur("crypto-hdkey has no key data"))?;
+        let public_key = PublicKey::from_slice(key_data.as_bytes("key data")?)?;
+        let chain_code = field(map, 4)
+            .map(|chain_code| {
+                <[u8; 32]>::try_from(chain_code.as_bytes("chain code")?)
+                    .map_err(|_| invalid_ur("chain code isn't 32 bytes long"))
+            })
+            .transpose()?;
+        let (origin, source_fingerprint) = match field(map, 6) {
+            Some(keypath) => {
+                let (origin, fingerprint) = decode_keypath(keypath)?;
+                (Some(origin), fingerprint)
+            }
+            None => (None, None),
+        };
+
+        Ok(Self {
+            public_key,
+            chain_code,
+            origin,
+            source_fingerprint,
+            parent_fingerprint: field(map, 8).map(decode_fingerprint).transpose()?,
+            name: field(map, 9)
+                .map(|name| decode_text(name, "name"))
+                .transpose()?,
+            note: field(map, 10)
+                .map(|note| decode_text(note, "note"))
+                .transpose()?,
+        })
+    }
+}
+
+/// Type of the data in `EthSignRequest`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EthDataType {
+    /// RLP of the unsigned legacy transaction
+    Transaction,
+    /// UTF-8 JSON of the EIP-712 typed data
+    TypedData,
+    /// Personal message, signed the same way as `personal_sign`
+    PersonalMessage,
+    /// EIP-2718 typed transaction without the signature, e.g. EIP-1559
+    TypedTransaction,
+}
+
+impl EthDataType {
+    /// Returns the value of the type in the CBOR
+    fn to_u64(self) -> u64 {
+        match self {
+            Self::Transaction => 1,
+            Self::TypedData => 2,
+            Self::PersonalMessage => 3,
+            Self::TypedTransaction => 4,
+        }
+    }
+
+    /// Parses the value of the type in the CBOR
+    fn from_u64(value: u64) -> Result<Self> {
+        match value {
+            1 => Ok(Self::Transaction),
+            2 => Ok(Self::TypedData),
+            3 => Ok(Self::PersonalMessage),
+            4 => Ok(Self::TypedTransaction),
+            value => Err(invalid_ur(format!("unknown data type {value}"))),
+        }
+    }
+}
+
+/// Signing request (`eth-sign-request`, EIP-4527) scanned from the watch-only wallet. The offline
+/// wallet signs it and shows the `EthSignature` back as a QR code.
+/// ```
+/// use ethereum_private_key_to_address::{
+///     DerivationPath, EthDataType, EthSignRequest, EthSignature, ExtendedPrivateKey, Mnemonic,
+/// };
+///
+/// let mnemonic = Mnemonic::parse("test test test test test test test test test test test junk").unwrap();
+/// let master = ExtendedPrivateKey::from_seed(&mnemonic.to_seed()[..]).unwrap();
+///
+/// // Created by the watch-only wallet
+/// let request = EthSignRequest::new(b"hello world".to_vec(), EthDataType::PersonalMessage, DerivationPath::ethereum(0))
+///     .with_source_fingerprint(master.fingerprint());
+/// let scanned = request.to_ur().to_string();
+///
+/// // Signed by the offline wallet
+/// let request = EthSignRequest::from_ur(&scanned.parse().unwrap()).unwrap();
+/// let signature = request.sign_with_master(&master).unwrap();
+/// let shown = signature.to_ur().to_string();
+///
+/// let signature = EthSignature::from_ur(&shown.parse().unwrap()).unwrap();
+/// assert_eq!(27, signature.v());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthSignRequest {
+    /// Id of the request, copied into the signature
+    request_id: Option<Uuid>,
+    /// Data to sign
+    sign_data: Vec<u8>,
+    /// Type of the data
+    data_type: EthDataType,
+    /// Chain id of the transaction
+    chain_id: Option<u64>,
+    /// Derivation path of the signing key
+    path: DerivationPath,
+    /// Fingerprint of the master key
+    source_fingerprint: Option<[u8; 4]>,
+    /// Address of the signing key
+    address: Option<Address>,
+    /// Name of the requesting wallet
+    origin: Option<String>,
+}
+
+impl EthSignRequest {
+    /// UR type of the request
+    pub const UR_TYPE: &'static str = "eth-sign-request";
+
+    /// Creates the request to sign the data by the key at the path
+    pub fn new(sign_data: Vec<u8>, data_type: EthDataType, path: DerivationPath) -> Self {
+        Self {
+            request_id: None,
+            sign_data,
+            data_type,
+            chain_id: None,
+            path,
+            source_fingerprint: None,
+            address: None,
+            origin: None,
+        }
+    }
+
+    /// Sets the id of the request
+    pub fn with_request_id(mut self, request_id: Uuid) -> Self {
+        self.request_id = Some(request_id);
+        self
+    }
+
+    /// Sets the chain id, used for `v` of legacy transactions
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the fingerprint of the master key
+    pub fn with_source_fingerprint(mut self, fingerprint: [u8; 4]) -> Self {
+        self.source_fingerprint = Some(fingerprint);
+        self
+    }
+
+    /// Sets the address of the signing key
+    pub fn with_address(mut self, address: Address) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    /// Sets the name of the requesting wallet
+    pub fn with_origin(mut self, origin: impl Into<String>) -> Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    /// Returns the id of the request
+    pub fn request_id(&self) -> Option<Uuid> {
+        self.request_id
+    }
+
+    /// Returns the data to sign
+    pub fn sign_data(&self) -> &[u8] {
+        &self.sign_data
+    }
+
+    /// Returns the type of the data
+    pub fn data_type(&self) -> EthDataType {
+        self.data_type
+    }
+
+    /// Returns the chain id
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+
+    /// Returns the derivation path of the signing key
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    /// Returns the fingerprint of the master key
+    pub fn source_fingerprint(&self) -> Option<[u8; 4]> {
+        self.source_fingerprint
+    }
+
+    /// Returns the address of the signing key
+    pub fn address(&self) -> Option<&Address> {
+        self.address.as_ref()
+    }
+
+    /// Returns the name of the requesting wallet
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// Returns the hash to sign, fails if the typed data is invalid
+    pub fn digest(&self) -> Result<[u8; 32]> {
+        self.digest_and_encoding().map(|(digest, _)| digest)
+    }
+
+    /// Signs the data by the private key, fails if the request is for a different address
+    pub fn sign(&self, private_key: &PrivateKey) -> Result<EthSignature> {
+        if let Some(address) = &self.address {
+            if *address != private_key.to_address() {
+                return Err(Error::Signer(format!(
+                    "request is for address {address}, not {}",
+                    private_key.to_address()
+                )));
+            }
+        }
+
+        let (digest, encoding) = self.digest_and_encoding()?;
+        let signature = private_key.sign_hash(&digest);
+        let mut signature = EthSignature::new(signature, signature.v_with(encoding)?);
+        signature.request_id = self.request_id;
+        Ok(signature)
+    }
+
+    /// Derives the key at the path from the master key and signs the data, fails if the request
+    /// is for a different master key
+    pub fn sign_with_master(&self, master: &ExtendedPrivateKey) -> Result<EthSignature> {
+        if let Some(fingerprint) = self.source_fingerprint {
+            if fingerprint != master.fingerprint() {
+                return Err(Error::Signer(format!(
+                    "request is for master key {}, not {}",
+                    hex::encode(fingerprint),
+                    hex::encode(master.fingerprint())
+                )));
+            }
+        }
+        self.sign(master.derive_path(&self.path)?.private_key())
+    }
+
+    /// Encodes the request into the `eth-sign-request` UR
+    pub fn to_ur(&self) -> Ur {
+        let mut map = Vec::new();
+        if let Some(request_id) = self.request_id {
+            map.push((1, encode_uuid(request_id)));
+        }
+        map.push((2, Cbor::Bytes(self.sign_data.clone())));
+        map.push((3, Cbor::Uint(self.data_type.to_u64())));
+        if let Some(chain_id) = self.chain_id {
+            map.push((4, Cbor::Uint(chain_id)));
+        }
+        map.push((5, encode_keypath(&self.path, self.source_fingerprint)));
+        if let Some(address) = &self.address {
+            map.push((6, Cbor::Bytes(address.to_vec())));
+        }
+        if let Some(origin) = &self.origin {
+            map.push((7, Cbor::Text(origin.clone())));
+        }
+        registry_ur(Self::UR_TYPE, Cbor::Map(map))
+    }
+
+    /// Decodes the request from the `eth-sign-request` UR
+    pub fn from_ur(ur: &Ur) -> Result<Self> {
+        let cbor = registry_cbor(ur, Self::UR_TYPE)?;
+        let map = cbor
+            .untag(TAG_ETH_SIGN_REQUEST)?
+            .as_map("eth-sign-request")?;
+        let sign_data = field(map, 2)
+            .ok_or_else(|| invalid_ur("eth-sign-request has no sign data"))?
+            .as_bytes("sign data")?
+            .to_vec();
+        let data_type = field(map, 3)
+            .ok_or_else(|| invalid_ur("eth-sign-request has no data type"))?
+            .as_uint("data type")
+            .and_then(EthDataType::from_u64)?;
+        let (path, source_fingerprint) = decode_keypath(
+            field(map, 5).ok_or_else(|| invalid_ur("eth-sign-request has no derivation path"))?,
+        )?;
+
+        Ok(Self {
+            request_id: field(map, 1).map(decode_uuid).transpose()?,
+            sign_data,
+            data_type,
+            chain_id: field(map, 4)
+                .map(|chain_id| chain_id.as_uint("chain id"))
+                .transpose()?,
+            path,
+            source_fingerprint,
+            address: field(map, 6)
+                .map(|address| Address::from_slice(address.as_bytes("address")?))
+                .transpose()?,
+            origin: field(map, 7)
+                .map(|origin| decode_text(origin, "origin"))
+                .transpose()?,
+        })
+    }
+
+    /// Returns the hash to sign and the encoding of `v` expected by the watch-only wallet
+    fn digest_and_encoding(&self) -> Result<([u8; 32], VEncoding)> {
+        match self.data_type {
+            EthDataType::Transaction => {
+                let encoding = self.chain_id.map_or(VEncoding::Legacy, VEncoding::Eip155);
+                Ok((keccak256(&self.sign_data), encoding))
+            }
+            EthDataType::TypedData => {
+                let json = std::str::from_utf8(&self.sign_data)
+                    .map_err(|_| Error::InvalidTypedData("data isn't UTF-8".to_string()))?;
+                Ok((TypedData::parse(json)?.hash()?, VEncoding::Legacy))
+            }
+            EthDataType::PersonalMessage => Ok((hash_message(&self.sign_data), VEncoding::Legacy)),
+            EthDataType::TypedTransaction => Ok((keccak256(&self.sign_data), VEncoding::Parity)),
+        }
+    }
+}
+
+/// Signature (`eth-signature`, EIP-4527) shown by the offline wallet for the watch-only wallet to
+/// scan. It's `r || s || v` with `v` encoded as the request expects, 27 or 28 for messages and
+/// typed data, EIP-155 `v` for legacy transactions and the y parity for typed transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthSignature {
+    /// Id of the signed request
+    request_id: Option<Uuid>,
+    /// Signature
+    signature: Signature,
+    /// Encoded `v`
+    v: u64,
+    /// Name of the signing wallet
+    origin: Option<String>,
+}
+
+impl EthSignature {
+    /// UR type of the signature
+    pub const UR_TYPE: &'static str = "eth-signature";
+
+    /// Creates the signature with the encoded `v`
+    pub fn new(signature: Signature, v: u64) -> Self {
+        Self {
+            request_id: None,
+            signature,
+            v,
+            origin: None,
+        }
+    }
+
+    /// Sets the id of the signed request
+    pub fn with_request_id(mut self, request_id: Uuid) -> Self {
+        self.request_id = Some(request_id);
+        self
+    }
+
+    /// Sets the name of the signing wallet
+    pub fn with_origin(mut self, origin: impl Into<String>) -> Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    /// Returns the id of the signed request
+    pub fn request_id(&self) -> Option<Uuid> {
+        self.request_id
+    }
+
+    /// Returns the signature
+    pub fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    /// Returns the encoded `v`
+    pub fn v(&self) -> u64 {
+        self.v
+    }
+
+    /// Returns the name of the signing wallet
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// Encodes the signature into the `eth-signature` UR
+    pub fn to_ur(&self) -> Ur {
+        let mut bytes = self.signature.to_rs().to_vec();
+        let v = self.v.to_be_bytes();
+        let start = v.iter().position(|byte| *byte != 0).unwrap_or(v.len() - 1);
+        bytes.extend_from_slice(&v[start..]);
+
+        let mut map = Vec::new();
+        if let Some(request_id) = self.request_id {
+            map.push((1, encode_uuid(request_id)));
+        }
+        map.push((2, Cbor::Bytes(bytes)));
+        if let Some(origin) = &self.origin {
+            map.push((3, Cbor::Text(origin.clone())));
+        }
+        registry_ur(Self::UR_TYPE, Cbor::Map(map))
+    }
+
+    /// Decodes the signature from the `eth-signature` UR
+    pub fn from_ur(ur: &Ur) -> Result<Self> {
+        let cbor = registry_cbor(ur, Self::UR_TYPE)?;
+        let map = cbor.untag(TAG_ETH_SIGNATURE)?.as_map("eth-signature")?;
+        let bytes = field(map, 2)
+            .ok_or_else(|| invalid_ur("eth-signature has no signature"))?
+            .as_bytes("signature")?;
+        if !(65..=72).contains(&bytes.len()) {
+            return Err(invalid_ur("signature has to be 65 to 72 bytes long"));
+        }
+
+        let v = bytes[64..]
+            .iter()
+            .fold(0u64, |v, byte| v << 8 | u64::from(*byte));
+        let y_parity = match v {
+            0 | 1 => v == 1,
+            27 | 28 => v == 28,
+            v if v >= 35 => (v - 35) % 2 == 1,
+            v => return Err(Error::InvalidRecoveryId(v)),
+        };
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&bytes[..32]);
+        s.copy_from_slice(&bytes[32..64]);
+
+        Ok(Self {
+            request_id: field(map, 1).map(decode_uuid).transpose()?,
+            signature: Signature::new(r, s, y_parity)?,
+            v,
+            origin: field(map, 3)
+                .map(|origin| decode_text(origin, "origin"))
+                .transpose()?,
+        })
+    }
+}
+
+/// CBOR data item, only the types used by the registry types
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Cbor {
+    /// Unsigned integer
+    Uint(u64),
+    /// Byte string
+    Bytes(Vec<u8>),
+    /// UTF-8 text
+    Text(String),
+    /// Array
+    Array(Vec<Cbor>),
+    /// Map with integer keys
+    Map(Vec<(u64, Cbor)>),
+    /// Tagged item
+    Tag(u64, Box<Cbor>),
+    /// `true` or `false`
+    Bool(bool),
+}
+
+impl Cbor {
+    /// Encodes the item, the map entries have to be sorted by the key
+    fn encode(&self) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        self.encode_into(&mut encoded);
+        encoded
+    }
+
+    /// Appends the encoded item
+    fn encode_into(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::Uint(value) => encode_header(out, 0, *value),
+            Self::Bytes(bytes) => {
+                encode_header(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            Self::Text(text) => {
+                encode_header(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            Self::Array(items) => {
+                encode_header(out, 4, items.len() as u64);
+                items.iter().for_each(|item| item.encode_into(out));
+            }
+            Self::Map(entries) => {
+                encode_header(out, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    encode_header(out, 0, *key);
+                    value.encode_into(out);
+                }
+            }
+            Self::Tag(tag, item) => {
+                encode_header(out, 6, *tag);
+                item.encode_into(out);
+            }
+            Self::Bool(value) => out.push(if *value { 0xf5 } else { 0xf4 }),
+        }
+    }
+
+    /// Decodes the item, fails if any bytes are left
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut data = bytes;
+        let item = decode_item(&mut data, 0)?;
+        if !data.is_empty() {
+            return Err(invalid_ur("trailing bytes after CBOR"));
+        }
+        Ok(item)
+    }
+
+    /// Returns the tagged item if the tag matches, or the item itself if it isn't tagged
+    fn untag(&self, tag: u64) -> Result<&Self> {
+        match self {
+            Self::Tag(actual, item) if *actual == tag => Ok(item),
+            Self::Tag(actual, _) => Err(invalid_ur(format!(
+                "unexpected CBOR tag {actual}, expected {tag}"
+            ))),
+            item => Ok(item),
+        }
+    }
+
+    /// Returns the unsigned integer
+    fn as_uint(&self, name: &str) -> Result<u64> {
+        match self {
+            Self::Uint(value) => Ok(*value),
+            _ => Err(invalid_ur(format!("{name} isn't an unsigned integer"))),
+        }
+    }
+
+    /// Returns the byte string
+    fn as_bytes(&self, name: &str) -> Result<&[u8]> {
+        match self {
+            Self::Bytes(bytes) => Ok(bytes),
+            _ => Err(invalid_ur(format!("{name} isn't a byte string"))),
+        }
+    }
+
+    /// Returns the boolean
+    fn as_bool(&self, name: &str) -> Result<bool> {
+        match self {
+            Self::Bool(value) => Ok(*value),
+            _ => Err(invalid_ur(format!("{name} isn't a boolean"))),
+        }
+    }
+
+    /// Returns the items of the array
+    fn as_array(&self, name: &str) -> Result<&[Cbor]> {
+        match self {
+            Self::Array(items) => Ok(items),
+            _ => Err(invalid_ur(format!("{name} isn't an array"))),
+        }
+    }
+
+    /// Returns the entries of the map
+    fn as_map(&self, name: &str) -> Result<&[(u64, Cbor)]> {
+        match self {
+            Self::Map(entries) => Ok(entries),
+            _ => Err(invalid_ur(format!("{name} isn't a map"))),
+        }
+    }
+}
+
+/// Appends the header with the major type and the argument in the shortest form
+fn encode_header(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+/// Decodes the next item and advances the data past it
+fn decode_item(data: &mut &[u8], depth: usize) -> Result<Cbor> {
+    if depth > MAX_DEPTH {
+        return Err(invalid_ur("CBOR is nested too deep"));
+    }
+    let initial = take(data, 1)?[0];
+    let major = initial >> 5;
+    if major == 7 {
+        return match initial {
+            0xf4 => Ok(Cbor::Bool(false)),
+            0xf5 => Ok(Cbor::Bool(true)),
+            _ => Err(invalid_ur(format!(
+                "unsupported CBOR simple value {initial:#04x}"
+            ))),
+        };
+    }
+
+    let value = match initial & 0x1f {
+        info @ 0..=23 => u64::from(info),
+        info @ 24..=27 => take(data, 1 << (info - 24))?
+            .iter()
+            .fold(0u64, |value, byte| value << 8 | u64::from(*byte)),
+        _ => return Err(invalid_ur("indefinite length CBOR isn't supported")),
+    };
+    match major {
+        0 => Ok(Cbor::Uint(value)),
+        2 => Ok(Cbor::Bytes(take(data, value)?.to_vec())),
+        3 => String::from_utf8(take(data, value)?.to_vec())
+            .map(Cbor::Text)
+            .map_err(|_| invalid_ur("CBOR text isn't UTF-8")),
+        4 => (0..value)
+            .map(|_| decode_item(data, depth + 1))
+            .collect::<Result<_>>()
+            .map(Cbor::Array),
+        5 => (0..value)
+            .map(|_| -> Result<(u64, Cbor)> {
+                let key = decode_item(data, depth + 1)?.as_uint("CBOR map key")?;
+                Ok((key, decode_item(data, depth + 1)?))
+            })
+            .collect::<Result<_>>()
+            .map(Cbor::Map),
+        6 => Ok(Cbor::Tag(value, Box::new(decode_item(data, depth + 1)?))),
+        _ => Err(invalid_ur("negative CBOR integers aren't supported")),
+    }
+}
+
+/// Takes the bytes from the start of the data
+fn take<'a>(data: &mut &'a [u8], len: u64) -> Result<&'a [u8]> {
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|len| *len <= data.len())
+        .ok_or_else(|| invalid_ur("CBOR is truncated"))?;
+    let (taken, rest) = data.split_at(len);
+    *data = rest;
+    Ok(taken)
+}
+
+/// Returns the value of the map entry with the key
+fn field(map: &[(u64, Cbor)], key: u64) -> Option<&Cbor> {
+    map.iter()
+        .find(|(entry_key, _)| *entry_key == key)
+        .map(|(_, value)| value)
+}
+
+/// Creates the UR of the untagged registry type
+fn registry_ur(ur_type: &str, cbor: Cbor) -> Ur {
+    Ur::new(ur_type, cbor.encode()).expect("registry type is valid")
+}
+
+/// Decodes the CBOR of the UR, fails if the type doesn't match
+fn registry_cbor(ur: &Ur, ur_type: &str) -> Result<Cbor> {
+    if ur.ur_type() != ur_type {
+        return Err(invalid_ur(format!(
+            "expected {ur_type}, got {}",
+            ur.ur_type()
+        )));
+    }
+    Cbor::decode(ur.cbor())
+}
+
+/// Encodes the path as the tagged `crypto-keypath`
+fn encode_keypath(path: &DerivationPath, source_fingerprint: Option<[u8; 4]>) -> Cbor {
+    let components = path
+        .indexes()
+        .iter()
+        .flat_map(|index| {
+            [
+                Cbor::Uint(u64::from(index & !HARDENED)),
+                Cbor::Bool(*index >= HARDENED),
+            ]
+        })
+        .collect();
+    let mut map = vec![(1, Cbor::Array(components))];
+    if let Some(fingerprint) = source_fingerprint {
+        map.push((2, Cbor::Uint(u32::from_be_bytes(fingerprint).into())));
+    }
+    Cbor::Tag(TAG_KEYPATH, Box::new(Cbor::Map(map)))
+}
+
+/// Decodes the path and the source fingerprint from the `crypto-keypath`. Wildcards and ranges
+/// aren't supported, the path has to point to a single key.
+fn decode_keypath(cbor: &Cbor) -> Result<(DerivationPath, Option<[u8; 4]>)> {
+    let map = cbor.untag(TAG_KEYPATH)?.as_map("crypto-keypath")?;
+    let components = field(map, 1)
+        .ok_or_else(|| invalid_ur("crypto-keypath has no components"))?
+        .as_array("components")?;
+    if components.len() % 2 != 0 {
+        return Err(invalid_ur(
+            "components have to be pairs of index and hardened flag",
+        ));
+    }
+
+    let indexes = components
+        .chunks(2)
+        .map(|pair| -> Result<u32> {
+            let index = u32::try_from(pair[0].as_uint("path component")?)
+                .ok()
+                .filter(|index| *index < HARDENED)
+                .ok_or_else(|| invalid_ur("path index is out of range"))?;
+            let hardened = pair[1].as_bool("hardened flag")?;
+            Ok(if hardened { index + HARDENED } else { index })
+        })
+        .collect::<Result<_>>()?;
+    let source_fingerprint = field(map, 2).map(decode_fingerprint).transpose()?;
+    Ok((DerivationPath::new(indexes), source_fingerprint))
+}
+
+/// Decodes the 4 byte fingerprint encoded as an integer
+fn decode_fingerprint(cbor: &Cbor) -> Result<[u8; 4]> {
+    u32::try_from(cbor.as_uint("fingerprint")?)
+        .map(u32::to_be_bytes)
+        .map_err(|_| invalid_ur("fingerprint is too large"))
+}
+
+/// Encodes the UUID as the tagged byte string
+fn encode_uuid(uuid: Uuid) -> Cbor {
+    Cbor::Tag(TAG_UUID, Box::new(Cbor::Bytes(uuid.as_bytes().to_vec())))
+}
+
+/// Decodes the tagged UUID
+fn decode_uuid(cbor: &Cbor) -> Result<Uuid> {
+    Uuid::from_slice(cbor.untag(TAG_UUID)?.as_bytes("request id")?)
+        .map_err(|_| invalid_ur("request id isn't 16 bytes long"))
+}
+
+/// Decodes the text
+fn decode_text(cbor: &Cbor, name: &str) -> Result<String> {
+    match cbor {
+        Cbor::Text(text) => Ok(text.clone()),
+        _ => Err(invalid_ur(format!("{name} isn't a text"))),
+    }
+}
+
+/// Splits the lowercased UR into the type, the sequence of the part and the Bytewords
+fn split_ur(s: &str) -> Result<(String, Option<(usize, usize)>, String)> {
+    let s = s.trim().to_ascii_lowercase();
+    let rest = s
+        .strip_prefix("ur:")
+        .ok_or_else(|| invalid_ur("missing ur: prefix"))?;
+    let components: Vec<&str> = rest.split('/').collect();
+    match components[..] {
+        [ur_type, bytewords] => Ok((ur_type.to_string(), None, bytewords.to_string())),
+        [ur_type, sequence, bytewords] => {
+            let (num, len) = sequence
+                .split_once('-')
+                .and_then(|(num, len)| Some((num.parse().ok()?, len.parse().ok()?)))
+                .filter(|(num, len)| *num > 0 && *len > 0)
+                .ok_or_else(|| invalid_ur(format!("invalid sequence {sequence}")))?;
+            Ok((ur_type.to_string(), Some((num, len)), bytewords.to_string()))
+        }
+        _ => Err(invalid_ur("expected ur:type/payload")),
+    }
+}
+
+/// Returns the sorted indexes of the fragments mixed into the part. The parts up to the sequence
+/// length hold one fragment each, the degree and the fragments of the parts after it are drawn
+/// from the generator seeded by the sequence number and the checksum.
+fn choose_fragments(seq_num: u32, seq_len: usize, checksum: u32) -> Vec<usize> {
+    if seq_num as usize <= seq_len {
+        return vec![seq_num as usize - 1];
+    }
+    let mut seed = seq_num.to_be_bytes().to_vec();
+    seed.extend_from_slice(&checksum.to_be_bytes());
+    let mut rng = Xoshiro256::new(&seed);
+    let degree = choose_degree(seq_len, &mut rng);
+
+    // The first `degree` fragments of the shuffled ones
+    let mut remaining: Vec<usize> = (0..seq_len).collect();
+    let mut indexes: Vec<usize> = (0..degree)
+        .map(|_| remaining.remove(rng.next_below(remaining.len())))
+        .collect();
+    indexes.sort_unstable();
+    indexes
+}
+
+/// Chooses how many fragments are mixed, `k` with the probability proportional to `1 / k`. Uses
+/// the alias method step by step as the reference implementation, so the parts match.
+fn choose_degree(seq_len: usize, rng: &mut Xoshiro256) -> usize {
+    let weights: Vec<f64> = (1..=seq_len).map(|k| 1.0 / k as f64).collect();
+    let sum: f64 = weights.iter().sum();
+    let mut scaled: Vec<f64> = weights.iter().map(|w| w * seq_len as f64 / sum).collect();
+    let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+        (0..seq_len).rev().partition(|&i| scaled[i] < 1.0);
+
+    let mut probabilities = vec![0.0; seq_len];
+    let mut aliases = vec![0; seq_len];
+    while let (Some(&less), Some(&more)) = (small.last(), large.last()) {
+        small.pop();
+        large.pop();
+        probabilities[less] = scaled[less];
+        aliases[less] = more;
+        scaled[more] += scaled[less] - 1.0;
+        if scaled[more] < 1.0 {
+            small.push(more);
+        } else {
+            large.push(more);
+        }
+    }
+    for i in large.into_iter().chain(small) {
+        probabilities[i] = 1.0;
+    }
+
+    let column = (seq_len as f64 * rng.next_f64()) as usize;
+    let degree = if rng.next_f64() < probabilities[column] {
+        column
+    } else {
+        aliases[column]
+    };
+    degree + 1
+}
+
+/// Xoshiro256** seeded by the SHA-256 of the seed, the generator of the fountain codes
+struct Xoshiro256([u64; 4]);
+
+impl Xoshiro256 {
+    /// Creates the generator, the state is the hash read as big-endian words
+    fn new(seed: &[u8]) -> Self {
+        let hash = Sha256::digest(seed);
+        let mut state = [0; 4];
+        for (word, bytes) in state.iter_mut().zip(hash.chunks(8)) {
+            *word = u64::from_be_bytes(bytes.try_into().expect("8 bytes"));
+        }
+        Self(state)
+    }
+
+    /// Returns the next random word
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.0;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Returns a number in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        self.next_u64() as f64 / (u64::MAX as f64 + 1.0)
+    }
+
+    /// Returns a number in `[0, n)`
+    fn next_below(&mut self, n: usize) -> usize {
+        (self.next_f64() * n as f64) as usize
+    }
+}
+
+/// XORs the data into the target, the data shorter than the target is padded with zeroes
+fn xor(target: &mut [u8], data: &[u8]) {
+    for (target, byte) in target.iter_mut().zip(data) {
+        *target ^= byte;
+    }
+}
+
+/// Encodes the data with its CRC32 checksum into minimal Bytewords
+fn encode_bytewords(data: &[u8]) -> String {
+    let checksum = crc32(data).to_be_bytes();
+    data.iter()
+        .chain(checksum.iter())
+        .flat_map(|byte| {
+            let word = BYTEWORDS[usize::from(*byte)].as_bytes();
+            [char::from(word[0]), char::from(word[3])]
+        })
+        .collect()
+}
+
+/// Decodes the minimal Bytewords and checks the CRC32 checksum
+fn decode_bytewords(encoded: &str) -> Result<Vec<u8>> {
+    if encoded.len() % 2 != 0 {
+        return Err(invalid_ur("Bytewords have odd length"));
+    }
+    let mut data = encoded
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            BYTEWORDS
+                .iter()
+                .position(|word| word.as_bytes()[0] == pair[0] && word.as_bytes()[3] == pair[1])
+                .map(|index| index as u8)
+                .ok_or_else(|| invalid_ur("invalid Byteword"))
+        })
+        .collect::<Result<Vec<u8>>>()?;
+    if data.len() < 4 {
+        return Err(invalid_ur("Bytewords are too short"));
+    }
+    let checksum = data.split_off(data.len() - 4);
+    if checksum != crc32(&data).to_be_bytes() {
+        return Err(invalid_ur("invalid Bytewords checksum"));
+    }
+    Ok(data)
+}
+
+/// CRC32 with the IEEE polynomial, the checksum of Bytewords and of multi-part URs
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Creates the error of the malformed UR
+fn invalid_ur(reason: impl Into<String>) -> Error {
+    Error::InvalidUr(reason.into())
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const REQUEST: &str = "ur:eth-sign-request/osadtpdagdndcawmgtfrkigrpmndutdnbtkgfssbjnaogrisihjzjzjlcxktjljpjzieaxaxaaadahtaaddyoeadlecsdwykcsfnykaeykaewkaewkaocybgeehfksamghwfnetbvwcypmloynwktoimrolfjpkktkzmrhcpiyatisjnihjyhsjnhsjkjeeyzorkmu";
+
+    const SIGNATURE: &str = "ur:eth-signature/oeadtpdagdndcawmgtfrkigrpmndutdnbtkgfssbjnaohdfpoxhsykaslokgttnnehdwbnhdfgkevszmmndybtfncymhrpayoshnskroaxcswdwnhevwkemtytchhljztytnpmfgiakofrpkkbkslsjtamkiadiawloesfwzzmkpfhhpcwhdmnwnhh";
+
+    #[test]
+    fn test_ur() {
+        let ur = Ur::from_str(
+            "ur:crypto-seed/oeadgdstaslplabghydrpfmkbggufgludprfgmaotpiecffltnlpqdenos",
+        )
+        .unwrap();
+        assert_eq!(
+            "a20150c7098580125e2ab0981253468b2dbc5202d8641947da",
+            hex::encode(ur.cbor())
+        );
+        assert!(Ur::from_str(
+            "ur:crypto-seed/oeadgdstaslplabghydrpfmkbggufgludprfgmaotpiecffltnlpqdenoa"
+        )
+        .is_err());
+
+        let ur = Ur::new("bytes", (0..=255).collect()).unwrap();
+        let parts = ur.to_parts(60);
+        assert_eq!(5, parts.len());
+        assert!(parts[0].starts_with("ur:bytes/1-5/"));
+        assert!(Ur::from_str(&parts[0]).is_err());
+
+        let mut decoder = UrDecoder::new();
+        assert_eq!(None, decoder.receive(&parts[3]).unwrap());
+        assert_eq!(None, decoder.receive(&parts[3].to_uppercase()).unwrap());
+        assert_eq!((1, 5), decoder.progress());
+        assert!(decoder
+            .receive(&Ur::new("bytes", vec![0; 256]).unwrap().to_parts(60)[0])
+            .is_err());
+        for part in [&parts[0], &parts[4], &parts[1]] {
+            assert_eq!(None, decoder.receive(part).unwrap());
+        }
+        assert_eq!(Some(ur), decoder.receive(&parts[2]).unwrap());
+    }
+
+    #[test]
+    fn test_fountain() {
+        // Vectors of the reference implementation
+        let mut rng = Xoshiro256::new(b"Wolf");
+        let numbers: Vec<u64> = (0..12).map(|_| rng.next_u64() % 100).collect();
+        assert_eq!(vec![42, 81, 85, 8, 82, 84, 76, 73, 70, 88, 2, 74], numbers);
+
+        let degrees: Vec<usize> = (1..=20)
+            .map(|i| choose_degree(11, &mut Xoshiro256::new(format!("Wolf-{i}").as_bytes())))
+            .collect();
+        assert_eq!(
+            vec![11, 3, 6, 5, 2, 1, 2, 11, 1, 3, 9, 10, 10, 4, 2, 1, 1, 2, 1, 1],
+            degrees
+        );
+
+        let mut rng = Xoshiro256::new(b"Wolf");
+        let message: Vec<u8> = (0..1024).map(|_| rng.next_below(256) as u8).collect();
+        let checksum = crc32(&message);
+        assert_eq!(0x2f19f3bb, checksum);
+        let fragments: Vec<Vec<usize>> = (11..=30)
+            .map(|seq_num| choose_fragments(seq_num, 11, checksum))
+            .collect();
+        let expected: Vec<Vec<usize>> = vec![
+            vec![10],
+            vec![9],
+            vec![2, 5, 6, 8, 9, 10],
+            vec![8],
+            vec![1, 5],
+            vec![1],
+            vec![0, 2, 4, 5, 8, 10],
+            vec![5],
+            vec![2],
+            vec![2],
+            vec![0, 1, 3, 4, 5, 7, 9, 10],
+            vec![0, 1, 2, 3, 5, 6, 8, 9, 10],
+            vec![0, 2, 4, 5, 7, 8, 9, 10],
+            vec![3, 5],
+            vec![4],
+            (0..=10).collect(),
+            vec![0, 1, 3, 4, 5, 6, 7, 9, 10],
+            vec![6],
+            vec![5, 6],
+            vec![7],
+        ];
+        assert_eq!(expected, fragments);
+
+        // The even fragments are missed and recovered from the fountain coded parts
+        let ur = Ur::new("bytes", message).unwrap();
+        assert_eq!(11, ur.to_parts(100).len());
+        let mut decoder = UrDecoder::new();
+        for seq_num in [1, 3, 5, 7, 9, 11].into_iter().chain(12..22) {
+            assert_eq!(None, decoder.receive(&ur.part(100, seq_num)).unwrap());
+        }
+        assert_eq!(
+            Some(ur.clone()),
+            decoder.receive(&ur.part(100, 22)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_eth_sign_request() {
+        let private_key = PrivateKey::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let request_id = Uuid::parse_str("9b1deb4d-3b7d-4bad-9bdd-2b0d7b3dcb6d").unwrap();
+        let request = EthSignRequest::new(
+            b"hello world".to_vec(),
+            EthDataType::PersonalMessage,
+            DerivationPath::ethereum(0),
+        )
+        .with_request_id(request_id)
+        .with_chain_id(1)
+        .with_source_fingerprint([0x12, 0x34, 0x56, 0x78])
+        .with_address(private_key.to_address())
+        .with_origin("metamask");
+        assert_eq!(REQUEST, request.to_ur().to_string());
+        assert_eq!(
+            request,
+            EthSignRequest::from_ur(&REQUEST.parse().unwrap()).unwrap()
+        );
+
+        let signature = request.sign(&private_key).unwrap();
+        assert_eq!(SIGNATURE, signature.to_ur().to_string());
+        assert_eq!(
+            signature,
+            EthSignature::from_ur(&SIGNATURE.parse().unwrap()).unwrap()
+        );
+        assert!(EthSignature::from_ur(&REQUEST.parse().unwrap()).is_err());
+
+        let other = PrivateKey::from_str(
+            "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        )
+        .unwrap();
+        assert!(request.sign(&other).is_err());
+    }
+
+    #[test]
+    fn test_crypto_hdkey() {
+        let master = ExtendedPrivateKey::from_str("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi").unwrap();
+        let key = CryptoHdKey::from_master(&master, "m/44'/60'/0'")
+            .unwrap()
+            .with_name("Offline wallet")
+            .with_note("account.standard");
+        assert_eq!(Some(master.fingerprint()), key.source_fingerprint());
+
+        let decoded = CryptoHdKey::from_ur(&key.to_ur().to_string().parse().unwrap()).unwrap();
+        assert_eq!(key, decoded);
+        assert_eq!("m/44'/60'/0'", decoded.origin().unwrap().to_string());
+    }
+}
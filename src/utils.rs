@@ -0,0 +1,30 @@
+use crate::Address;
+use sha3::{Digest, Keccak256};
+
+/// Calculates the Keccak256 hash of the given data
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Removes the leading `0x` from the hex string if there is one
+pub(crate) fn strip_hex_prefix(s: &str) -> &str {
+    s.strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s)
+}
+
+/// Converts the integer into 256 bit big-endian integer
+pub(crate) fn uint256(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// ABI encodes the address into the 32 byte word
+pub(crate) fn address_word(address: &Address) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address.as_bytes());
+    word
+}
@@ -0,0 +1,576 @@
+use crate::search::{default_threads, parallel_maximize, parallel_search};
+#[cfg(feature = "aho-corasick")]
+use crate::PatternSet;
+use crate::{derive_addresses, Address, AddressPattern, Error, PrivateKey, Result, SearchProgress};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::panic;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Number of keys a thread tries between checking if another thread found a match by default
+const DEFAULT_BATCH_SIZE: u64 = 256;
+
+/// Searches for a private key whose address matches the pattern, e.g. starts with `dead`. Random
+/// keys are generated in parallel from the OS random number generator, so the found key is as
+/// secure as any other random key.
+///
+/// The expected number of attempts grows 16 times with every hex character of the pattern, the
+/// search doesn't end if no address can match.
+/// ```
+/// use ethereum_private_key_to_address::{AddressPattern, VanitySearch};
+///
+/// let pattern = AddressPattern::new().with_prefix("0xab").unwrap();
+/// let (private_key, address) = VanitySearch::new(pattern).search();
+///
+/// assert_eq!(private_key.to_address(), address);
+/// assert!(address.to_string().starts_with("0xab"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VanitySearch {
+    /// Pattern the address has to match
+    pattern: AddressPattern,
+    /// Number of threads
+    threads: usize,
+    /// Number of keys a thread tries between checking if the search is over
+    batch_size: u64,
+    /// Progress of the previous runs of the search
+    resumed: SearchProgress,
+}
+
+impl VanitySearch {
+    /// Creates the search for the pattern, using one thread per core
+    pub fn new(pattern: AddressPattern) -> Self {
+        Self {
+            pattern,
+            threads: default_threads(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            resumed: SearchProgress::default(),
+        }
+    }
+
+    /// Sets the number of threads
+    ///
+    /// # Panics
+    ///
+    /// If the number of threads is zero.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "number of threads can't be zero");
+        self.threads = threads;
+        self
+    }
+
+    /// Sets the number of keys a thread tries between checking if the search is over. Smaller
+    /// batches stop the threads sooner and report the progress more precisely, larger batches
+    /// spend less time on the shared counters.
+    ///
+    /// # Panics
+    ///
+    /// If the batch size is zero.
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        assert!(batch_size > 0, "batch size can't be zero");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Resumes the search from the checkpoint, the reported attempts and elapsed time continue
+    /// from it. The checkpoint has to be saved by a search for the same pattern.
+    pub fn with_checkpoint(mut self, checkpoint: &VanityCheckpoint) -> Self {
+        self.resumed = SearchProgress::new(checkpoint.attempts, checkpoint.elapsed);
+        self
+    }
+
+    /// Returns the pattern
+    pub fn pattern(&self) -> &AddressPattern {
+        &self.pattern
+    }
+
+    /// Returns the number of threads
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Returns the number of keys a thread tries between checking if the search is over
+    pub fn batch_size(&self) -> u64 {
+        self.batch_size
+    }
+
+    /// Searches until a matching key is found, returns the key and its address
+    pub fn search(&self) -> (PrivateKey, Address) {
+        self.search_with_progress(|_| {})
+    }
+
+    /// Searches until a matching key is found, calling the callback with the progress about twice
+    /// a second
+    pub fn search_with_progress(
+        &self,
+        progress: impl FnMut(SearchProgress),
+    ) -> (PrivateKey, Address) {
+        let never = AtomicBool::new(false);
+        self.search_cancellable(&never, progress)
+            .expect("search can't be cancelled")
+    }
+
+    /// Searches for a key of every pattern of the set at once, until each pattern has a key or the
+    /// cancel flag is set. The address has to match the pattern of the search as well, which can
+    /// hold the conditions common to all patterns. Every found key is passed to `found` with the
+    /// index of its pattern right away. Returns the key of each pattern, `None` for the patterns
+    /// which weren't found before the search was cancelled.
+    /// ```no_run
+    /// use std::sync::atomic::AtomicBool;
+    /// use ethereum_private_key_to_address::{AddressPattern, PatternSet, VanitySearch};
+    ///
+    /// let set = PatternSet::new(vec![
+    ///     AddressPattern::new().with_prefix("0xdead").unwrap(),
+    ///     AddressPattern::new().with_prefix("0xbeef").unwrap(),
+    /// ])
+    /// .unwrap();
+    ///
+    /// let cancel = AtomicBool::new(false);
+    /// let search = VanitySearch::new(AddressPattern::new());
+    /// let keys = search.search_set(&set, &cancel, |_| {}, |index, _, address| {
+    ///     println!("pattern {index}: {address}");
+    /// });
+    /// ```
+    #[cfg(feature = "aho-corasick")]
+    pub fn search_set(
+        &self,
+        set: &PatternSet,
+        cancel: &AtomicBool,
+        mut progress: impl FnMut(SearchProgress),
+        mut found: impl FnMut(usize, &PrivateKey, &Address),
+    ) -> Vec<Option<(PrivateKey, Address)>> {
+        let mut keys = vec![None; set.len()];
+        // Indexes of the patterns without a key in the set
+        let mut remaining: Vec<usize> = (0..set.len()).collect();
+        let mut last_progress = self.resumed;
+
+        while !remaining.is_empty() {
+            let patterns = remaining
+                .iter()
+                .map(|index| set.patterns()[*index].clone())
+                .collect();
+            let remaining_set =
+                PatternSet::new(patterns).expect("subset of the patterns can be built");
+
+            let resumed = last_progress;
+            let mut report = |current: SearchProgress| {
+                last_progress = current;
+                progress(current);
+            };
+            let result = parallel_search(
+                self.threads,
+                resumed,
+                cancel,
+                &mut report,
+                |attempts, stop| loop {
+                    for _ in 0..self.batch_size {
+                        let private_key = PrivateKey::random();
+                        let address = private_key.to_address();
+                        if !self.pattern.matches(&address) {
+                            continue;
+                        }
+                        if let Some(index) = remaining_set.matches(&address) {
+                            return Some((index, private_key, address));
+                        }
+                    }
+                    attempts.fetch_add(self.batch_size, Ordering::Relaxed);
+                    if stop.load(Ordering::Relaxed) {
+                        return None;
+                    }
+                },
+            );
+
+            let (index, private_key, address) = match result {
+                Some(result) => result,
+                None => break,
+            };
+            let index = remaining.remove(index);
+            found(index, &private_key, &address);
+            keys[index] = Some((private_key, address));
+        }
+        keys
+    }
+
+    /// Searches for the key whose address matches the pattern and starts with the most zero bytes,
+    /// until the cancel flag is set or an address with 20 zero bytes is found. Every address
+    /// starting with more zero bytes than the best one so far is passed to `improved`, so it can be
+    /// saved right away. Returns the best key, `None` if the search was cancelled before any
+    /// address matched.
+    ///
+    /// Each zero byte saves 12 gas of calldata in every transaction passing the address, but takes
+    /// 256 times more attempts than the previous one.
+    /// ```no_run
+    /// use std::sync::atomic::AtomicBool;
+    /// use ethereum_private_key_to_address::{AddressPattern, VanitySearch};
+    ///
+    /// let search = VanitySearch::new(AddressPattern::new());
+    /// let cancel = AtomicBool::new(false);
+    /// let best = search.maximize_leading_zero_bytes(&cancel, |_| {}, |_, address| {
+    ///     println!("{address} has {} leading zero bytes", address.leading_zero_bytes());
+    /// });
+    /// ```
+    pub fn maximize_leading_zero_bytes(
+        &self,
+        cancel: &AtomicBool,
+        mut progress: impl FnMut(SearchProgress),
+        mut improved: impl FnMut(&PrivateKey, &Address),
+    ) -> Option<(PrivateKey, Address)> {
+        parallel_maximize(
+            self.threads,
+            self.batch_size,
+            20,
+            cancel,
+            &mut progress,
+            &mut |(private_key, address): &(PrivateKey, Address)| improved(private_key, address),
+            || {
+                move || {
+                    let private_key = PrivateKey::random();
+                    let address = private_key.to_address();
+                    self.pattern
+                        .matches(&address)
+                        .then(|| (address.leading_zero_bytes(), (private_key, address)))
+                }
+            },
+        )
+    }
+
+    /// Starts the search on a background thread, the returned handle streams the progress and
+    /// cancels or waits for the search. Meant for frontends which can't block on the search.
+    /// ```
+    /// use ethereum_private_key_to_address::{AddressPattern, VanitySearch};
+    ///
+    /// let pattern = AddressPattern::new().with_prefix("0xab").unwrap();
+    /// let handle = VanitySearch::new(pattern).spawn();
+    ///
+    /// for progress in handle.progress() {
+    ///     println!("{progress}");
+    /// }
+    /// let (private_key, address) = handle.join().unwrap();
+    /// ```
+    pub fn spawn(&self) -> VanitySearchHandle {
+        let search = self.clone();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (sender, progress) = mpsc::channel();
+
+        let thread = {
+            let cancel = Arc::clone(&cancel);
+            thread::spawn(move || {
+                search.search_cancellable(&cancel, |progress| {
+                    // The receiver may be gone, the search goes on until it's cancelled
+                    let _ = sender.send(progress);
+                })
+            })
+        };
+        VanitySearchHandle {
+            cancel,
+            progress,
+            thread,
+        }
+    }
+
+    /// Searches until a matching key is found or the cancel flag is set, calling the callback with
+    /// the progress about twice a second. Returns `None` if the search was cancelled, the threads
+    /// are stopped within about half a second of setting the flag.
+    ///
+    /// To cancel the search from a channel, check the receiver in the callback and set the flag.
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use std::time::Duration;
+    /// use ethereum_private_key_to_address::{AddressPattern, VanitySearch};
+    ///
+    /// let pattern = AddressPattern::new().with_leading_zero_bytes(20).unwrap();
+    /// let search = VanitySearch::new(pattern).with_threads(1);
+    ///
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let handle = {
+    ///     let cancel = Arc::clone(&cancel);
+    ///     thread::spawn(move || search.search_cancellable(&cancel, |_| {}))
+    /// };
+    ///
+    /// thread::sleep(Duration::from_millis(100));
+    /// cancel.store(true, Ordering::Relaxed);
+    /// assert!(handle.join().unwrap().is_none());
+    /// ```
+    pub fn search_cancellable(
+        &self,
+        cancel: &AtomicBool,
+        mut progress: impl FnMut(SearchProgress),
+    ) -> Option<(PrivateKey, Address)> {
+        parallel_search(
+            self.threads,
+            self.resumed,
+            cancel,
+            &mut progress,
+            |attempts, stop| loop {
+                // The public keys of the batch are hashed together
+                let keys: Vec<PrivateKey> =
+                    (0..self.batch_size).map(|_| PrivateKey::random()).collect();
+                let addresses = derive_addresses(&keys);
+                if let Some(index) = addresses
+                    .iter()
+                    .position(|address| self.pattern.matches(address))
+                {
+                    return Some((keys[index].clone(), addresses[index]));
+                }
+                attempts.fetch_add(self.batch_size, Ordering::Relaxed);
+                if stop.load(Ordering::Relaxed) {
+                    return None;
+                }
+            },
+        )
+    }
+}
+
+/// Checkpoint of a long running vanity search, to resume it after a restart with
+/// `VanitySearch::with_checkpoint()`.
+///
+/// The keys are independent random keys, so an interrupted search loses nothing but the statistics
+/// and the checkpoint stores only the attempts and the elapsed time. There's no RNG state which
+/// would let anyone reading the file recreate the tried keys.
+/// ```no_run
+/// use ethereum_private_key_to_address::{AddressPattern, VanityCheckpoint, VanitySearch};
+///
+/// let pattern = AddressPattern::new().with_prefix("0xdeadbeef").unwrap();
+/// let mut search = VanitySearch::new(pattern);
+/// if let Ok(checkpoint) = VanityCheckpoint::load("vanity.json") {
+///     search = search.with_checkpoint(&checkpoint);
+/// }
+///
+/// let (private_key, address) = search.search_with_progress(|progress| {
+///     VanityCheckpoint::from(progress).save("vanity.json").unwrap();
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VanityCheckpoint {
+    /// Number of keys tried so far
+    attempts: u64,
+    /// Time spent on the search so far
+    elapsed: Duration,
+}
+
+impl VanityCheckpoint {
+    /// Returns the number of keys tried so far
+    pub fn attempts(&self) -> u64 {
+        self.attempts
+    }
+
+    /// Returns the time spent on the search so far
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Reads the checkpoint from the JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).map_err(|error| io_error(path, error))?;
+        serde_json::from_str(&json)
+            .map_err(|error| Error::Io(format!("{path:?}: invalid checkpoint: {error}")))
+    }
+
+    /// Writes the checkpoint to the JSON file. The file is replaced at once, so a crash while
+    /// saving keeps the previous checkpoint.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let json = serde_json::to_string(self).expect("checkpoint is serializable");
+        fs::write(&temp, json).map_err(|error| io_error(path, error))?;
+        fs::rename(&temp, path).map_err(|error| io_error(path, error))
+    }
+}
+
+impl From<SearchProgress> for VanityCheckpoint {
+    fn from(progress: SearchProgress) -> Self {
+        Self {
+            attempts: progress.attempts(),
+            elapsed: progress.elapsed(),
+        }
+    }
+}
+
+/// Converts the I/O error of the checkpoint file
+fn io_error(path: &Path, error: std::io::Error) -> Error {
+    Error::Io(format!("{path:?}: {error}"))
+}
+
+/// Vanity search running on a background thread, created by `VanitySearch::spawn()`
+#[derive(Debug)]
+pub struct VanitySearchHandle {
+    /// Flag cancelling the search
+    cancel: Arc<AtomicBool>,
+    /// Progress reported about twice a second
+    progress: Receiver<SearchProgress>,
+    /// Thread running the search
+    thread: JoinHandle<Option<(PrivateKey, Address)>>,
+}
+
+impl VanitySearchHandle {
+    /// Returns the receiver of the progress, iterating it blocks until the search ends
+    pub fn progress(&self) -> &Receiver<SearchProgress> {
+        &self.progress
+    }
+
+    /// Returns the most recent progress reported since the last call without blocking, `None` if
+    /// there's no new report
+    pub fn latest_progress(&self) -> Option<SearchProgress> {
+        self.progress.try_iter().last()
+    }
+
+    /// Cancels the search, the threads stop within about half a second
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if the search has ended
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Waits for the search to end, returns the key and its address, or `None` if the search was
+    /// cancelled
+    pub fn join(self) -> Option<(PrivateKey, Address)> {
+        self.thread
+            .join()
+            .unwrap_or_else(|error| panic::resume_unwind(error))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_search() {
+        let pattern = AddressPattern::new()
+            .with_prefix("a")
+            .unwrap()
+            .with_contains("b")
+            .unwrap()
+            .with_suffix("c")
+            .unwrap();
+        let (private_key, address) = VanitySearch::new(pattern.clone())
+            .with_threads(2)
+            .with_batch_size(16)
+            .search();
+
+        assert!(pattern.matches(&address));
+        assert_eq!(private_key.to_address(), address);
+    }
+
+    #[test]
+    fn test_search_cancellable() {
+        let pattern = AddressPattern::new().with_leading_zero_bytes(20).unwrap();
+        let cancel = AtomicBool::new(true);
+        let search = VanitySearch::new(pattern).with_threads(2);
+
+        assert!(search.search_cancellable(&cancel, |_| {}).is_none());
+    }
+
+    #[test]
+    fn test_maximize_leading_zero_bytes() {
+        let pattern = AddressPattern::new().with_suffix("a").unwrap();
+        let search = VanitySearch::new(pattern.clone()).with_threads(2);
+        let cancel = AtomicBool::new(false);
+        let mut improvements: Vec<Address> = Vec::new();
+        let (private_key, address) = search
+            .maximize_leading_zero_bytes(
+                &cancel,
+                |_| {},
+                |_, address| {
+                    improvements.push(*address);
+                    if address.leading_zero_bytes() > 0 {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                },
+            )
+            .unwrap();
+
+        assert_eq!(private_key.to_address(), address);
+        assert!(pattern.matches(&address));
+        assert!(address.leading_zero_bytes() > 0);
+        assert_eq!(Some(&address), improvements.last());
+        assert!(improvements
+            .windows(2)
+            .all(|pair| pair[0].leading_zero_bytes() < pair[1].leading_zero_bytes()));
+    }
+
+    #[cfg(feature = "aho-corasick")]
+    #[test]
+    fn test_search_set() {
+        let set = PatternSet::new(vec![
+            AddressPattern::new().with_prefix("a").unwrap(),
+            AddressPattern::new().with_suffix("bc").unwrap(),
+            AddressPattern::new().with_contains("def").unwrap(),
+        ])
+        .unwrap();
+        let cancel = AtomicBool::new(false);
+        let mut found_indexes = Vec::new();
+        let keys = VanitySearch::new(AddressPattern::new())
+            .with_threads(2)
+            .search_set(
+                &set,
+                &cancel,
+                |_| {},
+                |index, _, _| found_indexes.push(index),
+            );
+
+        found_indexes.sort_unstable();
+        assert_eq!(vec![0, 1, 2], found_indexes);
+        for (pattern, key) in set.patterns().iter().zip(keys) {
+            let (private_key, address) = key.unwrap();
+            assert_eq!(private_key.to_address(), address);
+            assert!(pattern.matches(&address));
+        }
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let path = std::env::temp_dir().join(format!(
+            "ethereum-private-key-to-address-vanity-{}.json",
+            std::process::id()
+        ));
+        let checkpoint = VanityCheckpoint::from(SearchProgress::new(1000, Duration::from_secs(5)));
+        checkpoint.save(&path).unwrap();
+        let loaded = VanityCheckpoint::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(checkpoint, loaded);
+        assert!(VanityCheckpoint::load(&path).is_err());
+
+        let pattern = AddressPattern::new().with_leading_zero_bytes(20).unwrap();
+        let search = VanitySearch::new(pattern)
+            .with_threads(1)
+            .with_checkpoint(&loaded);
+        let cancel = AtomicBool::new(false);
+        let mut reported = None;
+        search.search_cancellable(&cancel, |progress| {
+            reported = Some(progress);
+            cancel.store(true, Ordering::Relaxed);
+        });
+        let reported = reported.unwrap();
+        assert!(reported.attempts() > 1000);
+        assert!(reported.elapsed() > Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_spawn() {
+        let pattern = AddressPattern::new().with_prefix("ab").unwrap();
+        let handle = VanitySearch::new(pattern.clone()).with_threads(2).spawn();
+        let (_, address) = handle.join().unwrap();
+        assert!(pattern.matches(&address));
+
+        let pattern = AddressPattern::new().with_leading_zero_bytes(20).unwrap();
+        let handle = VanitySearch::new(pattern).with_threads(1).spawn();
+        let progress = handle.progress().recv().unwrap();
+        assert!(progress.attempts() > 0);
+
+        handle.cancel();
+        assert!(handle.join().is_none());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::utils::strip_hex_prefix;
+use crate::{Error, ExtendedPrivateKey, Mnemonic, PrivateKey, Result, DEFAULT_DERIVATION_PATH};
+use std::str::FromStr;
+
+/// Format of the wallet input detected by `Wallet::detect()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WalletFormat {
+    /// Raw private key as 64 hex characters, optionally with the `0x` prefix
+    PrivateKey,
+    /// BIP-39 mnemonic phrase
+    Mnemonic,
+    /// BIP-32 extended private key `xprv...`
+    ExtendedPrivateKey,
+    /// Keystore V3 JSON
+    Keystore,
+    /// Mnemonic keystore container created by `Mnemonic::to_keystore()`
+    MnemonicKeystore,
+    /// 2014 presale wallet JSON
+    PresaleWallet,
+}
+
+/// Entry point for tools that accept a private key in any format the user has.
+///
+/// The format of the input is detected automatically, see `WalletFormat` for the supported ones.
+/// ```
+/// use ethereum_private_key_to_address::Wallet;
+///
+/// let inputs = [
+///     "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+///     "test test test test test test test test test test test junk",
+/// ];
+/// for input in inputs {
+///     let private_key = Wallet::parse(input, None).unwrap();
+///     assert_eq!(private_key.address(), "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Wallet;
+
+impl Wallet {
+    /// Detects the format of the input without decrypting or validating it
+    pub fn detect(input: &str) -> Result<WalletFormat> {
+        let input = input.trim();
+
+        if input.starts_with('{') {
+            let json: serde_json::Value = serde_json::from_str(input)
+                .map_err(|error| Error::InvalidKeystore(error.to_string()))?;
+            let has = |field: &str| json.get(field).is_some();
+            return if has("encseed") {
+                Ok(WalletFormat::PresaleWallet)
+            } else if has("x-mnemonic") {
+                Ok(WalletFormat::MnemonicKeystore)
+            } else if has("crypto") || has("Crypto") {
+                Ok(WalletFormat::Keystore)
+            } else {
+                Err(Error::UnknownWalletFormat)
+            };
+        }
+        if input.starts_with("xprv") {
+            return Ok(WalletFormat::ExtendedPrivateKey);
+        }
+        let hex = strip_hex_prefix(input);
+        if hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Ok(WalletFormat::PrivateKey);
+        }
+        if input.split_whitespace().count() >= 12 {
+            return Ok(WalletFormat::Mnemonic);
+        }
+        Err(Error::UnknownWalletFormat)
+    }
+
+    /// Detects the format of the input and returns the private key.
+    ///
+    /// Keystores need the password to be decrypted. For mnemonics the key at
+    /// `DEFAULT_DERIVATION_PATH` is returned, the password of a plain mnemonic is used as the
+    /// BIP-39 passphrase while the mnemonic from a keystore is used without passphrase. Raw and
+    /// extended keys ignore the password.
+    pub fn parse(input: &str, password: Option<&str>) -> Result<PrivateKey> {
+        let required = || password.ok_or(Error::PasswordRequired);
+        let input = input.trim();
+
+        match Self::detect(input)? {
+            WalletFormat::PrivateKey => PrivateKey::from_str(input),
+            WalletFormat::Mnemonic => Mnemonic::parse(input)?
+                .derive_key_with_passphrase(DEFAULT_DERIVATION_PATH, password.unwrap_or("")),
+            WalletFormat::ExtendedPrivateKey => {
+                Ok(ExtendedPrivateKey::from_str(input)?.into_private_key())
+            }
+            WalletFormat::Keystore => PrivateKey::from_keystore(input, required()?),
+            WalletFormat::MnemonicKeystore => {
+                Mnemonic::from_keystore(input, required()?)?.derive_key(DEFAULT_DERIVATION_PATH)
+            }
+            WalletFormat::PresaleWallet => PrivateKey::from_presale_wallet(input, required()?),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::KeystoreOptions;
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const PHRASE: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_detect() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let options = KeystoreOptions::pbkdf2(1024);
+        let keystore = private_key.to_keystore("password", &options).unwrap();
+        let mnemonic_keystore = Mnemonic::parse(PHRASE)
+            .unwrap()
+            .to_keystore("password", &options, None)
+            .unwrap();
+        let xprv = ExtendedPrivateKey::from_seed(&[1; 32]).unwrap().to_xprv();
+
+        for (input, format) in [
+            (PRIVATE_KEY, WalletFormat::PrivateKey),
+            (
+                format!(" 0x{PRIVATE_KEY}\n").as_str(),
+                WalletFormat::PrivateKey,
+            ),
+            (PHRASE, WalletFormat::Mnemonic),
+            (xprv.as_str(), WalletFormat::ExtendedPrivateKey),
+            (keystore.as_str(), WalletFormat::Keystore),
+            (mnemonic_keystore.as_str(), WalletFormat::MnemonicKeystore),
+            (
+                r#"{"encseed": "00", "ethaddr": "00"}"#,
+                WalletFormat::PresaleWallet,
+            ),
+        ] {
+            assert_eq!(format, Wallet::detect(input).unwrap());
+        }
+        for input in ["", "0x1234", "test test test", "{}"] {
+            assert_eq!(Err(Error::UnknownWalletFormat), Wallet::detect(input));
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let options = KeystoreOptions::pbkdf2(1024);
+
+        assert_eq!(private_key, Wallet::parse(PRIVATE_KEY, None).unwrap());
+        assert_eq!(private_key, Wallet::parse(PHRASE, None).unwrap());
+        assert_eq!(
+            "0x9313778b3753108128b9c476ebdd42fbd566f4ed",
+            Wallet::parse(PHRASE, Some("TREZOR")).unwrap().address()
+        );
+
+        let keystore = private_key.to_keystore("password", &options).unwrap();
+        assert_eq!(
+            private_key,
+            Wallet::parse(&keystore, Some("password")).unwrap()
+        );
+        assert_eq!(Err(Error::PasswordRequired), Wallet::parse(&keystore, None));
+
+        let mnemonic_keystore = Mnemonic::parse(PHRASE)
+            .unwrap()
+            .to_keystore("password", &options, None)
+            .unwrap();
+        assert_eq!(
+            private_key,
+            Wallet::parse(&mnemonic_keystore, Some("password")).unwrap()
+        );
+
+        let master = ExtendedPrivateKey::from_seed(&[1; 32]).unwrap();
+        assert_eq!(
+            master.private_key(),
+            &Wallet::parse(&master.to_xprv(), None).unwrap()
+        );
+    }
+}
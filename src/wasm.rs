@@ -0,0 +1,58 @@
+use crate::utils::strip_hex_prefix;
+use crate::{Address, PrivateKey, PublicKey};
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// Calculates the checksummed address of the hex private key, the `0x` prefix is optional
+#[wasm_bindgen(js_name = privateKeyToAddress)]
+pub fn private_key_to_address(private_key: &str) -> Result<String, JsError> {
+    Ok(PrivateKey::from_str(private_key)?
+        .to_address()
+        .to_checksum())
+}
+
+/// Returns the 64 byte public key of the hex private key as hex, without the 0x04 prefix
+#[wasm_bindgen(js_name = publicKey)]
+pub fn public_key(private_key: &str) -> Result<String, JsError> {
+    Ok(PrivateKey::from_str(private_key)?.public_key())
+}
+
+/// Calculates the checksummed address of the hex public key. Accepts the uncompressed key with or
+/// without the 0x04 prefix and the 33 byte compressed key.
+#[wasm_bindgen(js_name = publicKeyToAddress)]
+pub fn public_key_to_address(public_key: &str) -> Result<String, JsError> {
+    Ok(PublicKey::from_str(public_key)?.address().to_checksum())
+}
+
+/// Converts the address into the EIP-55 checksummed form. Fails if the address is mixed-case with
+/// an invalid checksum.
+#[wasm_bindgen(js_name = toChecksumAddress)]
+pub fn to_checksum_address(address: &str) -> Result<String, JsError> {
+    Ok(Address::from_str(address)?.to_checksum())
+}
+
+/// Returns true if the address is in the EIP-55 checksummed form
+#[wasm_bindgen(js_name = isChecksumAddress)]
+pub fn is_checksum_address(address: &str) -> bool {
+    Address::from_str(address)
+        .is_ok_and(|parsed| parsed.to_checksum()[2..] == *strip_hex_prefix(address))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_checksum_address() {
+        assert!(is_checksum_address(
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+        ));
+        assert!(!is_checksum_address(
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        ));
+        assert!(!is_checksum_address(
+            "0xF39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+        ));
+        assert!(!is_checksum_address("0xf39f"));
+    }
+}